@@ -0,0 +1,148 @@
+use criterion::{criterion_group, criterion_main, BatchSize, Criterion};
+use std::{hint::black_box, time::Duration};
+use transforms::{
+    core::{Buffer, BufferConfig, Storage},
+    geometry::{Quaternion, Transform, Vector3},
+    time::Timestamp,
+};
+
+/// Number of transforms a buffer holds before the measured operations, 10 s at 1 kHz.
+const SAMPLES: u64 = 10_000;
+
+/// Number of transforms inserted per measured batch.
+const INSERTS: u64 = 100;
+
+fn create_transform(timestamp: Timestamp) -> Transform {
+    Transform {
+        translation: Vector3 {
+            x: 1.0,
+            y: 0.0,
+            z: 0.0,
+        },
+        rotation: Quaternion {
+            w: 1.0,
+            x: 0.0,
+            y: 0.0,
+            z: 0.0,
+        },
+        timestamp,
+        parent: "a".into(),
+        child: "b".into(),
+    }
+}
+
+fn backends() -> [(&'static str, Storage); 2] {
+    [
+        ("btree", Storage::BTree),
+        (
+            "ring",
+            Storage::Ring {
+                capacity: (SAMPLES + INSERTS) as usize,
+            },
+        ),
+    ]
+}
+
+/// Creates a buffer holding `SAMPLES` transforms, one per millisecond, ending now.
+fn create_filled_buffer(storage: Storage) -> (Buffer, Timestamp) {
+    let mut buffer = Buffer::with_config(BufferConfig {
+        storage,
+        ..BufferConfig::new(Duration::from_secs(60))
+    });
+    let start = (Timestamp::now() - Duration::from_millis(SAMPLES)).unwrap();
+    for i in 0..SAMPLES {
+        buffer.insert(create_transform(
+            (start + Duration::from_millis(i)).unwrap(),
+        ));
+    }
+    (buffer, start)
+}
+
+fn benchmark_buffer_insert(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer");
+
+    for (name, storage) in backends() {
+        let (buffer, start) = create_filled_buffer(storage);
+        let end = (start + Duration::from_millis(SAMPLES)).unwrap();
+
+        group.bench_function(format!("insert_monotonic_{name}"), |b| {
+            b.iter_batched(
+                || buffer.clone(),
+                |mut buffer| {
+                    for i in 0..INSERTS {
+                        buffer.insert(create_transform((end + Duration::from_micros(i)).unwrap()));
+                    }
+                    black_box(buffer)
+                },
+                BatchSize::SmallInput,
+            );
+        });
+
+        group.bench_function(format!("insert_out_of_order_{name}"), |b| {
+            b.iter_batched(
+                || buffer.clone(),
+                |mut buffer| {
+                    for i in 0..INSERTS {
+                        // Half a millisecond after samples spread across the buffer
+                        let offset = Duration::from_micros(i * SAMPLES / INSERTS * 1000 + 500);
+                        buffer.insert(create_transform((start + offset).unwrap()));
+                    }
+                    black_box(buffer)
+                },
+                BatchSize::SmallInput,
+            );
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_buffer_lookup(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer");
+
+    for (name, storage) in backends() {
+        let (buffer, start) = create_filled_buffer(storage);
+
+        group.bench_function(format!("lookup_{name}"), |b| {
+            let mut i = 0;
+            b.iter(|| {
+                // Interpolate between two samples, walking through the buffer
+                let offset = Duration::from_micros((i % (SAMPLES - 1)) * 1000 + 500);
+                i += 1;
+                let _ = black_box(buffer.get(&(start + offset).unwrap()));
+            });
+        });
+
+        group.bench_function(format!("lookup_exact_{name}"), |b| {
+            // Hit stored samples exactly, walking through the buffer
+            let timestamps: Vec<_> = (0..SAMPLES)
+                .map(|i| (start + Duration::from_millis(i)).unwrap())
+                .collect();
+            let mut iter = timestamps.iter().cycle();
+            b.iter(|| black_box(buffer.get(iter.next().unwrap())));
+        });
+    }
+
+    group.finish();
+}
+
+fn benchmark_buffer_static(c: &mut Criterion) {
+    let mut group = c.benchmark_group("buffer");
+    let mut buffer = Buffer::new(Duration::from_secs(60));
+    buffer.insert(create_transform(Timestamp::zero()));
+    let timestamp = Timestamp::now();
+
+    group.bench_function("lookup_static", |b| {
+        b.iter(|| black_box(buffer.get(black_box(&timestamp))));
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    benchmark_buffer_insert,
+    benchmark_buffer_lookup,
+    benchmark_buffer_static
+);
+criterion_main!(benches);
@@ -1,5 +1,13 @@
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
-use std::time::Duration;
+use criterion::{criterion_group, criterion_main, Criterion};
+use std::{
+    hint::black_box,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+    time::Duration,
+};
 use transforms::{
     geometry::{Quaternion, Transform, Vector3},
     time::Timestamp,
@@ -23,8 +31,8 @@ fn create_sample_transform() -> Transform {
             z: 0.0,
         },
         timestamp: Timestamp::now(),
-        parent: "a".to_string(),
-        child: "b".to_string(),
+        parent: "a".into(),
+        child: "b".into(),
     }
 }
 
@@ -82,8 +90,8 @@ fn benchmark_sync_tree_climb(c: &mut Criterion) {
         // Prepare registry with 1000 transforms
         for i in 0..1000 {
             let mut transform = Transform::identity();
-            transform.parent = i.to_string();
-            transform.child = (i + 1).to_string();
+            transform.parent = i.to_string().into();
+            transform.child = (i + 1).to_string().into();
             let _ = registry.add_transform(transform);
         }
 
@@ -105,29 +113,81 @@ fn benchmark_sync_tree_climb_common_parent_elim(c: &mut Criterion) {
 
         // Prepare registry with 1000 transforms
         let mut transform = Transform::identity();
-        transform.parent = "a_999".to_string();
-        transform.child = "b_0".to_string();
+        transform.parent = "a_999".into();
+        transform.child = "b_0".into();
         let _ = registry.add_transform(transform);
 
         let mut transform = Transform::identity();
-        transform.parent = "a_999".to_string();
-        transform.child = "c_0".to_string();
+        transform.parent = "a_999".into();
+        transform.child = "c_0".into();
         let _ = registry.add_transform(transform);
 
         for i in 0..1000 {
             let mut transform = Transform::identity();
-            transform.parent = "a_".to_string() + &i.to_string();
-            transform.child = "a_".to_string() + &(i + 1).to_string();
+            transform.parent = ("a_".to_string() + &i.to_string()).into();
+            transform.child = ("a_".to_string() + &(i + 1).to_string()).into();
             let _ = registry.add_transform(transform);
 
             let mut transform = Transform::identity();
-            transform.parent = "b_".to_string() + &i.to_string();
-            transform.child = "b_".to_string() + &(i + 1).to_string();
+            transform.parent = ("b_".to_string() + &i.to_string()).into();
+            transform.child = ("b_".to_string() + &(i + 1).to_string()).into();
             let _ = registry.add_transform(transform);
 
             let mut transform = Transform::identity();
-            transform.parent = "c_".to_string() + &i.to_string();
-            transform.child = "c_".to_string() + &(i + 1).to_string();
+            transform.parent = ("c_".to_string() + &i.to_string()).into();
+            transform.child = ("c_".to_string() + &(i + 1).to_string()).into();
+            let _ = registry.add_transform(transform);
+        }
+
+        b.iter(|| {
+            let _ = black_box(registry.get_transform("b_999", "c_999", Timestamp::zero()));
+        });
+    });
+
+    group.finish();
+}
+
+/// Two 1000-frame branches hanging off the tip of a 1000-frame trunk.
+fn create_common_parent_tree() -> Vec<Transform> {
+    let mut transforms = Vec::new();
+    for (parent, child) in [("a_999", "b_0"), ("a_999", "c_0")] {
+        let mut transform = Transform::identity();
+        transform.parent = parent.into();
+        transform.child = child.into();
+        transforms.push(transform);
+    }
+    for i in 0..1000 {
+        for branch in ["a_", "b_", "c_"] {
+            let mut transform = Transform::identity();
+            transform.parent = (branch.to_string() + &i.to_string()).into();
+            transform.child = (branch.to_string() + &(i + 1).to_string()).into();
+            transforms.push(transform);
+        }
+    }
+    transforms
+}
+
+#[cfg(not(feature = "async"))]
+fn benchmark_sync_cached_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("sync");
+    group.sample_size(1000);
+
+    // Snapshots do not cache paths, so they resolve the path on every lookup
+    group.bench_function("common_parent_uncached", |b| {
+        let mut registry = Registry::new(Duration::from_secs(60));
+        for transform in create_common_parent_tree() {
+            let _ = registry.add_transform(transform);
+        }
+        let snapshot = registry.snapshot();
+
+        b.iter(|| {
+            let _ = black_box(snapshot.get_transform("b_999", "c_999", Timestamp::zero()));
+        });
+    });
+
+    group.bench_function("common_parent_cached", |b| {
+        let mut registry = Registry::new(Duration::from_secs(60));
+        for transform in create_common_parent_tree() {
             let _ = registry.add_transform(transform);
         }
 
@@ -139,6 +199,84 @@ fn benchmark_sync_tree_climb_common_parent_elim(c: &mut Criterion) {
     group.finish();
 }
 
+/// Writer workload used to contend with readers: overwrites a small, fixed window of
+/// samples on its own frame so the buffer size stays bounded for the whole benchmark.
+fn create_contending_transform(
+    t: Timestamp,
+    i: u64,
+) -> Transform {
+    let mut transform = create_sample_transform();
+    transform.timestamp = (t + Duration::from_micros(i % 100)).unwrap();
+    transform.child = "c".into();
+    transform
+}
+
+#[cfg(not(feature = "async"))]
+fn benchmark_sync_reader_under_contention(c: &mut Criterion) {
+    use std::sync::Mutex;
+
+    let mut group = c.benchmark_group("sync");
+    group.sample_size(1000);
+
+    group.bench_function("mutex_lookup_under_contention", |b| {
+        let registry = Arc::new(Mutex::new(Registry::new(Duration::from_secs(60))));
+        let transform = create_sample_transform();
+        let t = transform.timestamp;
+        let _ = registry.lock().unwrap().add_transform(transform);
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let registry = registry.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let mut i = 0;
+                while !stop.load(Ordering::Relaxed) {
+                    let transform = create_contending_transform(t, i);
+                    let _ = registry.lock().unwrap().add_transform(transform);
+                    i += 1;
+                }
+            })
+        };
+
+        b.iter(|| {
+            let _ = black_box(registry.lock().unwrap().get_transform("a", "b", t));
+        });
+
+        stop.store(true, Ordering::Relaxed);
+        writer.join().unwrap();
+    });
+
+    group.bench_function("reader_lookup_under_contention", |b| {
+        let mut registry = Registry::new(Duration::from_secs(60));
+        let transform = create_sample_transform();
+        let t = transform.timestamp;
+        let _ = registry.add_transform(transform);
+        let reader = registry.reader();
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let mut i = 0;
+                while !stop.load(Ordering::Relaxed) {
+                    let transform = create_contending_transform(t, i);
+                    let _ = registry.add_transform(transform);
+                    i += 1;
+                }
+            })
+        };
+
+        b.iter(|| {
+            let _ = black_box(reader.get_transform("a", "b", t));
+        });
+
+        stop.store(true, Ordering::Relaxed);
+        writer.join().unwrap();
+    });
+
+    group.finish();
+}
+
 #[cfg(feature = "async")]
 fn benchmark_async_transforms(c: &mut Criterion) {
     let mut group = c.benchmark_group("async");
@@ -206,8 +344,8 @@ fn benchmark_async_tree_climb(c: &mut Criterion) {
         rt.block_on(async {
             for i in 0..1000 {
                 let mut transform = Transform::identity();
-                transform.parent = i.to_string();
-                transform.child = (i + 1).to_string();
+                transform.parent = i.to_string().into();
+                transform.child = (i + 1).to_string().into();
                 let _ = registry.add_transform(transform).await;
             }
         });
@@ -222,19 +360,136 @@ fn benchmark_async_tree_climb(c: &mut Criterion) {
     group.finish();
 }
 
+#[cfg(feature = "async")]
+fn benchmark_async_reader_under_contention(c: &mut Criterion) {
+    let mut group = c.benchmark_group("async");
+    group.sample_size(1000);
+
+    let rt = Runtime::new().unwrap();
+
+    group.bench_function("locked_lookup_under_contention", |b| {
+        let registry = Arc::new(Registry::new(Duration::from_secs(60)));
+        let transform = create_sample_transform();
+        let t = transform.timestamp;
+        let _ = rt.block_on(registry.add_transform(transform));
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let registry = registry.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let rt = Runtime::new().unwrap();
+                let mut i = 0;
+                while !stop.load(Ordering::Relaxed) {
+                    let transform = create_contending_transform(t, i);
+                    let _ = rt.block_on(registry.add_transform(transform));
+                    i += 1;
+                }
+            })
+        };
+
+        b.iter(|| {
+            let _ = black_box(rt.block_on(registry.get_transform("a", "b", t)));
+        });
+
+        stop.store(true, Ordering::Relaxed);
+        writer.join().unwrap();
+    });
+
+    group.bench_function("reader_lookup_under_contention", |b| {
+        let registry = Arc::new(Registry::new(Duration::from_secs(60)));
+        let transform = create_sample_transform();
+        let t = transform.timestamp;
+        let _ = rt.block_on(registry.add_transform(transform));
+        let reader = rt.block_on(registry.reader());
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let writer = {
+            let registry = registry.clone();
+            let stop = stop.clone();
+            thread::spawn(move || {
+                let rt = Runtime::new().unwrap();
+                let mut i = 0;
+                while !stop.load(Ordering::Relaxed) {
+                    let transform = create_contending_transform(t, i);
+                    let _ = rt.block_on(registry.add_transform(transform));
+                    i += 1;
+                }
+            })
+        };
+
+        b.iter(|| {
+            let _ = black_box(reader.get_transform("a", "b", t));
+        });
+
+        stop.store(true, Ordering::Relaxed);
+        writer.join().unwrap();
+    });
+
+    group.finish();
+}
+
+#[cfg(feature = "async")]
+fn benchmark_async_cached_chain(c: &mut Criterion) {
+    let mut group = c.benchmark_group("async");
+    group.sample_size(1000);
+
+    let rt = Runtime::new().unwrap();
+
+    // Snapshots do not cache paths, so they resolve the path on every lookup
+    group.bench_function("common_parent_uncached", |b| {
+        let registry = Registry::new(Duration::from_secs(60));
+        let snapshot = rt.block_on(async {
+            for transform in create_common_parent_tree() {
+                let _ = registry.add_transform(transform).await;
+            }
+            registry.snapshot().await
+        });
+
+        b.iter(|| {
+            let _ = black_box(snapshot.get_transform("b_999", "c_999", Timestamp::zero()));
+        });
+    });
+
+    group.bench_function("common_parent_cached", |b| {
+        let registry = Registry::new(Duration::from_secs(60));
+        rt.block_on(async {
+            for transform in create_common_parent_tree() {
+                let _ = registry.add_transform(transform).await;
+            }
+        });
+
+        b.iter(|| {
+            rt.block_on(async {
+                let _ = black_box(
+                    registry
+                        .get_transform("b_999", "c_999", Timestamp::zero())
+                        .await,
+                );
+            });
+        });
+    });
+
+    group.finish();
+}
+
 #[cfg(not(feature = "async"))]
 criterion_group!(
     benches,
     benchmark_sync_transforms,
     benchmark_sync_transforms_with_preparation,
     benchmark_sync_tree_climb,
-    benchmark_sync_tree_climb_common_parent_elim
+    benchmark_sync_tree_climb_common_parent_elim,
+    benchmark_sync_reader_under_contention,
+    benchmark_sync_cached_chain
 );
 #[cfg(feature = "async")]
 criterion_group!(
     benches,
     benchmark_async_transforms,
     benchmark_async_transforms_with_preparation,
-    benchmark_async_tree_climb
+    benchmark_async_tree_climb,
+    benchmark_async_reader_under_contention,
+    benchmark_async_cached_chain
 );
 criterion_main!(benches);
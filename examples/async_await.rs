@@ -49,7 +49,7 @@ async fn main() {
             let t = generate_transform(time);
 
             // Add the transform and catch potential errors
-            if let Err(e) = registry_writer.add_transform(t.clone()).await {
+            if let Err(e) = registry_writer.add_transform(t).await {
                 error!("Error adding transform: {:?}", e);
             }
             tokio::time::sleep(Duration::from_millis(500)).await;
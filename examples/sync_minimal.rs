@@ -42,7 +42,7 @@ fn main() {
     let transform = generate_transform(time);
 
     // Add the transform
-    if let Err(e) = registry.add_transform(transform.clone()) {
+    if let Err(e) = registry.add_transform(transform) {
         error!("Error adding transform: {:?}", e);
     }
 
@@ -51,7 +51,7 @@ async fn main() {
             let mut r = registry_writer.lock().await;
 
             // Add the transform to the registry
-            if let Err(e) = r.add_transform(t.clone()) {
+            if let Err(e) = r.add_transform(t) {
                 error!("Error adding transform: {:?}", e);
             }
             drop(r);
@@ -0,0 +1,428 @@
+use crate::geometry::Transform;
+use std::time::Duration;
+
+/// Settings for a single `Buffer`.
+///
+/// A registry applies one configuration to every buffer it creates, and individual frames
+/// can be given their own through `Registry::set_frame_config`.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use transforms::core::{BufferConfig, Storage};
+///
+/// let config = BufferConfig {
+///     storage: Storage::Ring { capacity: 1024 },
+///     ..BufferConfig::new(Duration::from_secs(10))
+/// };
+/// assert_eq!(config.max_age, Duration::from_secs(10));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BufferConfig {
+    /// How long transforms are kept before they expire.
+    pub max_age: Duration,
+    /// How the buffer stores its transforms.
+    pub storage: Storage,
+    /// How lookups between stored transforms are answered, unless a query chooses otherwise.
+    pub interpolation: InterpolationMode,
+    /// How densely the buffer keeps transforms that arrive faster than needed, if at all.
+    pub decimation: Option<Decimation>,
+    /// How far interpolation may stray from the input when redundant transforms are
+    /// dropped, if they are.
+    pub compression: Option<Compression>,
+    /// How transforms age out of a hot window into a compact archive, if the buffer keeps
+    /// one. Without an archive, transforms are kept at full rate until they expire.
+    pub archive: Option<ArchivePolicy>,
+    /// The longest gap between two transforms that lookups answer across, if limited.
+    /// Lookups inside a longer gap, such as a sensor dropout, fail with
+    /// `BufferError::InterpolationGap` rather than make up a transform. The limit applies to
+    /// archived transforms too, so it should exceed the interval the archive keeps them at.
+    pub max_interpolation_gap: Option<Duration>,
+    /// How fast the frame may plausibly move, if inserts are checked against it.
+    pub motion_limits: Option<MotionLimits>,
+    /// What happens to a transform at a timestamp the buffer already holds a transform for.
+    pub duplicates: DuplicatePolicy,
+    /// How far behind the newest stored transform a transform may arrive, if limited.
+    /// Later transforms are rejected with `BufferError::LateTransform`.
+    pub max_lateness: Option<Duration>,
+}
+
+impl BufferConfig {
+    /// Creates a configuration with the given max_age and the default `Storage`.
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            max_age,
+            storage: Storage::default(),
+            interpolation: InterpolationMode::default(),
+            decimation: None,
+            compression: None,
+            archive: None,
+            max_interpolation_gap: None,
+            motion_limits: None,
+            duplicates: DuplicatePolicy::default(),
+            max_lateness: None,
+        }
+    }
+}
+
+/// The data structure a `Buffer` keeps its transforms in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Storage {
+    /// An ordered map. Handles any insertion order at logarithmic cost.
+    #[default]
+    BTree,
+    /// A preallocated ring of at most `capacity` transforms, searched by bisection.
+    ///
+    /// Appending transforms in timestamp order and expiring old ones are constant time.
+    /// Out-of-order transforms are still inserted in order, at the cost of shifting the
+    /// newer ones. Once the ring is full, the oldest transform is dropped to make room.
+    /// A capacity of zero is treated as one.
+    Ring {
+        /// The maximum number of transforms the ring holds.
+        capacity: usize,
+    },
+}
+
+/// How a buffer answers a lookup at a timestamp it holds no transform for.
+///
+/// Every mode answers only within the time range of the buffer, from its oldest to its
+/// newest transform, and returns a transform stored at exactly the requested timestamp as
+/// it is. Static transforms are valid at any time whatever the mode.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use transforms::{
+///     core::{Buffer, InterpolationMode},
+///     geometry::Transform,
+///     time::Timestamp,
+/// };
+///
+/// let mut buffer = Buffer::new(Duration::from_secs(10));
+/// let t = Timestamp::now();
+/// let mut transform = Transform::identity();
+/// transform.timestamp = t;
+/// buffer.insert(transform);
+/// transform.timestamp = (t + Duration::from_secs(1)).unwrap();
+/// transform.translation.x = 1.;
+/// buffer.insert(transform);
+///
+/// let between = (t + Duration::from_millis(300)).unwrap();
+/// let x = |mode| buffer.get_with(&between, mode).ok().map(|tf| tf.translation.x);
+/// assert_eq!(x(InterpolationMode::Linear), Some(0.3));
+/// assert_eq!(x(InterpolationMode::Nearest), Some(0.));
+/// assert_eq!(x(InterpolationMode::Previous), Some(0.));
+/// assert!(x(InterpolationMode::Exact).is_none());
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum InterpolationMode {
+    /// Interpolates the translation linearly and the rotation by slerp.
+    #[default]
+    Linear,
+    /// Returns the transform closest in time, the older one on a tie.
+    Nearest,
+    /// Returns the newest transform at or before the timestamp, a zero-order hold.
+    Previous,
+    /// Only returns transforms stored at exactly the timestamp.
+    Exact,
+    /// Interpolates the translation by a cubic Hermite spline and the rotation by SQUAD,
+    /// taking tangents from the transforms either side, so motion keeps a continuous
+    /// velocity across stored transforms. See `Transform::interpolate_cubic`.
+    Cubic,
+}
+
+/// Thins out transforms that arrive closer together than `min_interval`.
+///
+/// Only transforms newer than everything in the buffer are decimated; transforms that fill
+/// in older history, replace a stored timestamp or are static are always inserted. Thinned
+/// out transforms skip the expiry pass an insert otherwise runs.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use transforms::core::{BufferConfig, Decimation, DecimationMode};
+///
+/// // Keep at most 100 transforms per second, always ending in the newest one
+/// let config = BufferConfig {
+///     decimation: Some(Decimation {
+///         mode: DecimationMode::Merge,
+///         ..Decimation::rate(100.)
+///     }),
+///     ..BufferConfig::new(Duration::from_secs(10))
+/// };
+/// assert_eq!(
+///     config.decimation.unwrap().min_interval,
+///     Duration::from_millis(10)
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Decimation {
+    /// The minimum time between two stored transforms.
+    pub min_interval: Duration,
+    /// What happens to a transform that arrives sooner.
+    pub mode: DecimationMode,
+}
+
+impl Decimation {
+    /// Creates a decimation that drops transforms closer together than `min_interval`.
+    pub fn interval(min_interval: Duration) -> Self {
+        Self {
+            min_interval,
+            mode: DecimationMode::default(),
+        }
+    }
+
+    /// Creates a decimation that drops transforms arriving faster than `hz` per second.
+    ///
+    /// A rate that is not positive and finite keeps every transform.
+    pub fn rate(hz: f64) -> Self {
+        let min_interval = Duration::try_from_secs_f64(1. / hz).unwrap_or_default();
+        Self::interval(min_interval)
+    }
+}
+
+/// What a decimating buffer does with a transform that arrives too soon.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DecimationMode {
+    /// The transform is discarded, so the newest stored transform can lag behind the input
+    /// by up to `min_interval`.
+    #[default]
+    Drop,
+    /// The transform replaces the newest stored transform until that one is `min_interval`
+    /// past the transform before it, so the buffer always ends in the newest input.
+    Merge,
+}
+
+/// How many transforms a buffer has thinned out through `Decimation` and `Compression`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct DecimationStats {
+    /// Transforms discarded by `DecimationMode::Drop`.
+    pub dropped: u64,
+    /// Stored transforms replaced by a newer one under `DecimationMode::Merge`.
+    pub merged: u64,
+    /// Stored transforms removed by `Compression` because interpolation reproduces them.
+    pub compressed: u64,
+}
+
+/// What a buffer does with a transform at a timestamp it already holds a transform for.
+///
+/// Static transforms always replace the stored one.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use transforms::{
+///     core::{Buffer, BufferConfig, DuplicatePolicy},
+///     geometry::Transform,
+///     time::Timestamp,
+/// };
+///
+/// let mut buffer = Buffer::with_config(BufferConfig {
+///     duplicates: DuplicatePolicy::KeepFirst,
+///     ..BufferConfig::new(Duration::from_secs(10))
+/// });
+/// let mut transform = Transform::identity();
+/// transform.timestamp = Timestamp::now();
+/// buffer.insert(transform);
+/// transform.translation.x = 1.;
+/// buffer.insert(transform);
+///
+/// assert_eq!(buffer.latest().unwrap().translation.x, 0.);
+/// assert_eq!(buffer.rejection_stats().duplicates, 1);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DuplicatePolicy {
+    /// The new transform replaces the stored one.
+    #[default]
+    Replace,
+    /// The stored transform is kept and the new one discarded.
+    KeepFirst,
+    /// The new transform is rejected with `BufferError::DuplicateTimestamp`.
+    Reject,
+}
+
+/// How many transforms a buffer has turned away, by the reason they were turned away.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RejectionStats {
+    /// Transforms rejected for arriving more than `BufferConfig::max_lateness` late.
+    pub late: u64,
+    /// Transforms at a stored timestamp, rejected or discarded per `DuplicatePolicy`.
+    pub duplicates: u64,
+    /// Transforms rejected for exceeding the `MotionLimits` of the buffer.
+    pub anomalies: u64,
+}
+
+/// Drops transforms that interpolating between their neighbours reproduces closely enough.
+///
+/// As each transform is appended, the one before it is dropped if interpolating from the
+/// transform before that to the new one reproduces it, and every transform dropped since,
+/// within both tolerances. `Buffer::get` then answers within the tolerances of what it
+/// would have answered with every transform kept, in the configured `InterpolationMode`.
+/// Cubic lookups also depend on transforms beyond the neighbours either side, which later
+/// inserts move, and exact ones cannot answer at a dropped transform, so a buffer
+/// configured with `Cubic` or `Exact` keeps every transform. Like `Decimation`, compression
+/// only applies to transforms newer than everything in the buffer.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use transforms::core::{BufferConfig, Compression};
+///
+/// // Ten minutes of odometry, reproduced within a millimetre and a milliradian
+/// let config = BufferConfig {
+///     compression: Some(Compression {
+///         translation_tolerance: 1e-3,
+///         rotation_tolerance: 1e-3,
+///     }),
+///     ..BufferConfig::new(Duration::from_secs(600))
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Compression {
+    /// The largest distance between an interpolated and a dropped translation.
+    pub translation_tolerance: f64,
+    /// The largest angle, in radians, between an interpolated and a dropped rotation.
+    pub rotation_tolerance: f64,
+}
+
+impl Compression {
+    /// Returns whether `approximation` is within both tolerances of `transform`.
+    pub(crate) fn reproduces(
+        &self,
+        approximation: &Transform,
+        transform: &Transform,
+    ) -> bool {
+        let offset = approximation.translation - transform.translation;
+        if offset.dot(offset).sqrt() > self.translation_tolerance {
+            return false;
+        }
+
+        let (a, b) = (approximation.rotation, transform.rotation);
+        let cos_half = (a.w * b.w + a.x * b.x + a.y * b.y + a.z * b.z) / (a.norm() * b.norm());
+        // q and -q are the same rotation
+        2. * cos_half.abs().min(1.).acos() <= self.rotation_tolerance
+    }
+}
+
+/// Splits the history of a buffer into a hot window at full rate and a compact archive.
+///
+/// Transforms stay in the hot window, in the configured `Storage`, until they are older than
+/// `hot_window`. They then age into an archive that thins them out with its own decimation
+/// and compression and keeps them until `BufferConfig::max_age`. Lookups are served from
+/// both tiers alike, interpolating across the boundary between them. Transforms more than
+/// `hot_window` older than the newest one when they arrive go straight to the archive, as
+/// do those a full ring would otherwise drop.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use transforms::core::{ArchivePolicy, BufferConfig, Compression, Decimation};
+///
+/// // Five seconds at full rate, then three hours at 10 Hz within a millimetre
+/// let config = BufferConfig {
+///     archive: Some(ArchivePolicy {
+///         decimation: Some(Decimation::rate(10.)),
+///         compression: Some(Compression {
+///             translation_tolerance: 1e-3,
+///             rotation_tolerance: 1e-3,
+///         }),
+///         ..ArchivePolicy::new(Duration::from_secs(5))
+///     }),
+///     ..BufferConfig::new(Duration::from_secs(3 * 60 * 60))
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ArchivePolicy {
+    /// How long transforms stay in the hot window. Should be shorter than `max_age`.
+    pub hot_window: Duration,
+    /// How the archive thins out the transforms aging into it, if at all.
+    pub decimation: Option<Decimation>,
+    /// How far the archive may stray from the transforms aging into it, if it compresses.
+    pub compression: Option<Compression>,
+}
+
+impl ArchivePolicy {
+    /// Creates a policy that archives transforms older than `hot_window` as they are.
+    pub fn new(hot_window: Duration) -> Self {
+        Self {
+            hot_window,
+            decimation: None,
+            compression: None,
+        }
+    }
+
+    /// Returns the configuration of the archive of a buffer configured with `config`.
+    pub(crate) fn archive_config(
+        &self,
+        config: &BufferConfig,
+    ) -> BufferConfig {
+        BufferConfig {
+            max_age: config.max_age,
+            storage: Storage::BTree,
+            interpolation: config.interpolation,
+            decimation: self.decimation,
+            compression: self.compression,
+            archive: None,
+            max_interpolation_gap: config.max_interpolation_gap,
+            motion_limits: None,
+            duplicates: DuplicatePolicy::default(),
+            max_lateness: None,
+        }
+    }
+}
+
+/// Limits on how fast a frame may plausibly move. Every limit is off by default.
+///
+/// Each inserted transform is compared with the stored transform before it, and for the
+/// accelerations also with the one before that, to catch sudden jumps such as a
+/// relocalization teleporting `map -> odom`. Velocities are in translation units and radians
+/// per second, accelerations in the same per second squared. Static transforms and
+/// transforms that change the parent are not checked.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use transforms::core::{AnomalyAction, BufferConfig, MotionLimits};
+///
+/// // A ground robot neither drives faster than 3 m/s nor turns faster than 2 rad/s
+/// let config = BufferConfig {
+///     motion_limits: Some(MotionLimits {
+///         max_linear_velocity: Some(3.),
+///         max_angular_velocity: Some(2.),
+///         action: AnomalyAction::Flag,
+///         ..MotionLimits::default()
+///     }),
+///     ..BufferConfig::new(Duration::from_secs(10))
+/// };
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct MotionLimits {
+    /// The maximum speed of the origin of the frame relative to its parent.
+    pub max_linear_velocity: Option<f64>,
+    /// The maximum rate of rotation of the frame relative to its parent.
+    pub max_angular_velocity: Option<f64>,
+    /// The maximum change in linear velocity per second.
+    pub max_linear_acceleration: Option<f64>,
+    /// The maximum change in angular velocity per second.
+    pub max_angular_acceleration: Option<f64>,
+    /// What happens to a transform that exceeds a limit.
+    pub action: AnomalyAction,
+}
+
+/// What a buffer does with a transform that exceeds its `MotionLimits`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnomalyAction {
+    /// The transform is rejected with `BufferError::MotionAnomaly` and nothing changes.
+    #[default]
+    Reject,
+    /// The transform is inserted, and the `MotionAnomaly` returned by `Buffer::try_insert`
+    /// and passed to the anomaly hook of a registry.
+    Flag,
+}
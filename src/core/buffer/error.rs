@@ -1,4 +1,10 @@
-use crate::errors::TransformError;
+use crate::{
+    core::{Limit, MotionAnomaly},
+    errors::TransformError,
+    frame::FrameId,
+    time::Timestamp,
+};
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -9,6 +15,42 @@ pub enum BufferError {
     #[error("No transforms available matching your criteria")]
     NoTransformAvailable,
 
+    #[error("Gap of {0:?} between transforms exceeds the max interpolation gap of {1:?}")]
+    InterpolationGap(Duration, Duration),
+
+    #[error("Transform from {0} to {1} would create a cycle in the frame tree")]
+    FrameCycle(FrameId, FrameId),
+
+    #[error("Transform from \"{0}\" to \"{1}\" has an empty frame name")]
+    EmptyFrameName(FrameId, FrameId),
+
+    #[error("Frame {0} cannot be its own parent")]
+    SelfParented(FrameId),
+
+    #[error("Transform from {0} to {1} has a non-finite translation")]
+    NonFiniteTranslation(FrameId, FrameId),
+
+    #[error("Transform from {0} to {1} has a non-finite rotation")]
+    NonFiniteRotation(FrameId, FrameId),
+
+    #[error("Transform from {0} to {1} has a zero-length rotation")]
+    ZeroRotation(FrameId, FrameId),
+
+    #[error("Transform from {0} to {1} has a rotation of norm {2}, not a unit quaternion")]
+    NonUnitRotation(FrameId, FrameId, f64),
+
+    #[error("{0}")]
+    MotionAnomaly(MotionAnomaly),
+
+    #[error("Transform from {0} to {1} at {2} duplicates a stored timestamp")]
+    DuplicateTimestamp(FrameId, FrameId, Timestamp),
+
+    #[error("Transform from {0} to {1} at {2} arrived more than {3:?} behind the newest one")]
+    LateTransform(FrameId, FrameId, Timestamp, Duration),
+
+    #[error("Limit of {1} {0} exceeded")]
+    LimitExceeded(Limit, usize),
+
     #[error("Transform error: {0}")]
     TransformError(#[from] TransformError),
 }
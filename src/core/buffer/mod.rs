@@ -1,8 +1,9 @@
 //! A module for managing a buffer of transforms with timestamps.
 //!
 //! This module provides the `Buffer` struct, which is designed to store and manage
-//! a collection of transforms, each associated with a timestamp. The buffer uses
-//! a binary tree to efficiently store and retrieve transforms based on their timestamps.
+//! a collection of transforms, each associated with a timestamp. By default the buffer
+//! uses a binary tree to efficiently store and retrieve transforms based on their
+//! timestamps; a preallocated ring buffer can be selected instead through `BufferConfig`.
 //!
 //! # Features
 //!
@@ -18,6 +19,20 @@
 //!   If an exact match is not found, the buffer can interpolate between the nearest transforms to
 //!   provide an estimated transform at the requested timestamp.
 //!
+//! - **Decimation and Compression**: High-rate input can be thinned out to a minimum interval,
+//!   and transforms that interpolation reproduces within a tolerance can be dropped, so long
+//!   histories take a fraction of the memory.
+//!
+//! - **Tiered History**: With an `ArchivePolicy`, recent transforms are kept at full rate while
+//!   older ones age into a thinned out archive, and both are served by the same lookups.
+//!
+//! - **Duplicate and Late Samples**: A `DuplicatePolicy` decides whether a transform at a
+//!   stored timestamp replaces it, is discarded or is rejected, and `max_lateness` rejects
+//!   transforms arriving too far behind the newest one. Both are counted in `RejectionStats`.
+//!
+//! - **Anomaly Detection**: With `MotionLimits`, transforms that would move their frame
+//!   implausibly fast, such as a sudden relocalization jump, are rejected or flagged on insert.
+//!
 //! - **Static Lookup Mode**: The buffer supports a static lookup mode. When a timestamp with
 //!   nanoseconds set to zero is supplied, the buffer will return a static transform if available.
 //!   This is useful for scenarios where a constant transform is needed regardless of the timestamp.
@@ -69,20 +84,37 @@
 //!
 //! # Modules
 //!
+//! - `config`: Contains the `BufferConfig`, `Storage`, `Decimation`, `Compression`, `ArchivePolicy`, `InterpolationMode` and `MotionLimits` types for configuring a buffer.
 //! - `error`: Contains the `BufferError` type for error handling.
+//! - `motion`: Contains the `MotionAnomaly` type reporting a transform that exceeds its `MotionLimits`.
 //!
 //! # Structs
 //!
 //! - `Buffer`: The main struct for managing the buffer of transforms.
+//! - `BufferConfig`: The settings a buffer is created with.
 //!
 //! # Types
 //!
 //! - `NearestTransforms`: A type alias for a tuple containing the nearest transforms before and after a given timestamp.
 
-use crate::{geometry::Transform, time::Timestamp};
-use std::{collections::BTreeMap, time::Duration};
+use crate::{
+    core::lookup::{LinkLookup, LinkSource},
+    errors::TransformError,
+    geometry::Transform,
+    time::Timestamp,
+};
+use std::{iter, time::Duration};
+mod config;
 mod error;
+mod motion;
+mod storage;
+pub use config::{
+    AnomalyAction, ArchivePolicy, BufferConfig, Compression, Decimation, DecimationMode,
+    DecimationStats, DuplicatePolicy, InterpolationMode, MotionLimits, RejectionStats, Storage,
+};
 pub use error::BufferError;
+pub use motion::{MotionAnomaly, MotionQuantity};
+use storage::Samples;
 
 type NearestTransforms<'a> = (
     Option<(&'a Timestamp, &'a Transform)>,
@@ -92,20 +124,47 @@ type NearestTransforms<'a> = (
 /// A buffer that stores transforms ordered by timestamps.
 ///
 /// The `Buffer` struct is designed to manage a collection of transforms,
-/// each associated with a timestamp. It uses a binary tree to efficiently
-/// store and retrieve transforms based on their timestamps.
+/// each associated with a timestamp. The `Storage` selected in its `BufferConfig`
+/// determines how transforms are kept ordered by their timestamps.
 ///
 /// # Fields
 ///
-/// - `data`: A `BTreeMap` where each key is a `Timestamp` and each value is a `Transform`.
-/// - `max_age`: A `Duration` that defines the max_age for each entry, determining how long
-///   entries remain valid.
+/// - `data`: The transforms, ordered by `Timestamp`, in the configured `Storage`.
+/// - `config`: The `BufferConfig` holding the max_age, which determines how long entries
+///   remain valid, and the storage backend.
 /// - `is_static`: A boolean flag that, when set to true, converts the buffer to a static
 ///   lookup if a timestamp with nanoseconds set to zero is supplied. Any
+/// - `decimated`: How many transforms the configured `Decimation` and `Compression` have
+///   thinned out.
+/// - `rejected`: How many transforms the configured `DuplicatePolicy`, `max_lateness` and
+///   `MotionLimits` have turned away.
+/// - `segment`: The transforms compression has dropped between the two newest stored ones,
+///   which any further interpolation across them must still reproduce.
+/// - `archive`: The older tier of the history if an `ArchivePolicy` is configured, holding
+///   the transforms that aged out of `data`.
+#[derive(Clone)]
 pub struct Buffer {
-    data: BTreeMap<Timestamp, Transform>,
-    max_age: Duration,
+    data: Samples,
+    config: BufferConfig,
     is_static: bool,
+    decimated: DecimationStats,
+    rejected: RejectionStats,
+    segment: Vec<Transform>,
+    archive: Option<Box<Buffer>>,
+}
+
+/// The most transforms compression drops between two stored transforms.
+///
+/// Bounds the memory spent on remembering them, at the cost of storing one transform per
+/// `MAX_SEGMENT` even while the input is perfectly linear.
+const MAX_SEGMENT: usize = 64;
+
+/// What the configured `Decimation` does with an inserted transform.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Admission {
+    Insert,
+    Drop,
+    Merge,
 }
 
 impl Buffer {
@@ -122,15 +181,91 @@ impl Buffer {
     /// let mut buffer = Buffer::new(max_age);
     /// ```
     pub fn new(max_age: Duration) -> Self {
+        Self::with_config(BufferConfig::new(max_age))
+    }
+
+    /// Creates a new buffer with the specified configuration.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::core::{Buffer, BufferConfig, Storage};
+    ///
+    /// let config = BufferConfig {
+    ///     storage: Storage::Ring { capacity: 1024 },
+    ///     ..BufferConfig::new(Duration::from_secs(10))
+    /// };
+    /// let mut buffer = Buffer::with_config(config);
+    /// assert_eq!(buffer.config(), config);
+    /// ```
+    pub fn with_config(config: BufferConfig) -> Self {
         Self {
-            data: BTreeMap::new(),
-            max_age,
+            data: Samples::new(config.storage),
+            config,
             is_static: false,
+            decimated: DecimationStats::default(),
+            rejected: RejectionStats::default(),
+            segment: Vec::new(),
+            archive: config
+                .archive
+                .map(|policy| Box::new(Self::with_config(policy.archive_config(&config)))),
+        }
+    }
+
+    /// Returns the configuration of the buffer.
+    pub fn config(&self) -> BufferConfig {
+        self.config
+    }
+
+    /// Applies a new configuration, moving the stored transforms into the new storage.
+    ///
+    /// Transforms that the new configuration cannot hold, such as those beyond the
+    /// capacity of a ring, are dropped oldest first, unless the new configuration archives
+    /// them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{
+    ///     core::{Buffer, BufferConfig, Storage},
+    ///     geometry::Transform,
+    /// };
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// buffer.insert(Transform::identity());
+    ///
+    /// buffer.reconfigure(BufferConfig {
+    ///     storage: Storage::Ring { capacity: 16 },
+    ///     ..buffer.config()
+    /// });
+    /// assert_eq!(buffer.latest(), Some(&Transform::identity()));
+    /// ```
+    pub fn reconfigure(
+        &mut self,
+        config: BufferConfig,
+    ) {
+        let archived = self.archive.take().map(|archive| archive.data.into_vec());
+        let samples = std::mem::replace(&mut self.data, Samples::new(config.storage));
+        self.config = config;
+        self.segment.clear();
+        self.archive = config
+            .archive
+            .map(|policy| Box::new(Self::with_config(policy.archive_config(&config))));
+        for transform in archived.into_iter().flatten().chain(samples.into_vec()) {
+            self.store(transform);
+        }
+        if !self.is_static {
+            self.age();
         }
     }
 
     /// Adds a transform to the buffer.
     ///
+    /// A transform rejected by the configured `DuplicatePolicy`, `max_lateness` or
+    /// `MotionLimits` is dropped; `try_insert` reports why.
+    ///
     /// # Examples
     ///
     /// ```
@@ -173,8 +308,187 @@ impl Buffer {
         &mut self,
         transform: Transform,
     ) {
+        let _ = self.try_insert(transform);
+    }
+
+    /// Adds a transform to the buffer after checking it against the configured
+    /// `max_lateness`, `DuplicatePolicy` and `MotionLimits`.
+    ///
+    /// Rejected transforms, and duplicates discarded under `DuplicatePolicy::KeepFirst`,
+    /// are counted in `rejection_stats`.
+    ///
+    /// # Returns
+    ///
+    /// The `MotionAnomaly` of a transform that exceeds a limit but was inserted under
+    /// `AnomalyAction::Flag`, `None` for one within the limits.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, and leaves the stored transforms unchanged, if the transform is
+    /// rejected:
+    ///
+    /// - `BufferError::LateTransform` if it is more than `max_lateness` older than the
+    ///   newest stored transform.
+    /// - `BufferError::DuplicateTimestamp` if a transform is stored at its timestamp under
+    ///   `DuplicatePolicy::Reject`.
+    /// - `BufferError::MotionAnomaly` if it exceeds a limit under `AnomalyAction::Reject`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{
+    ///     core::{Buffer, BufferConfig, MotionLimits, MotionQuantity},
+    ///     errors::BufferError,
+    ///     geometry::Transform,
+    ///     time::Timestamp,
+    /// };
+    ///
+    /// let mut buffer = Buffer::with_config(BufferConfig {
+    ///     motion_limits: Some(MotionLimits {
+    ///         max_linear_velocity: Some(2.),
+    ///         ..MotionLimits::default()
+    ///     }),
+    ///     ..BufferConfig::new(Duration::from_secs(10))
+    /// });
+    /// let mut transform = Transform::identity();
+    /// transform.timestamp = Timestamp::now();
+    /// buffer.try_insert(transform).unwrap();
+    ///
+    /// // Ten metres in a tenth of a second
+    /// transform.timestamp = (transform.timestamp + Duration::from_millis(100)).unwrap();
+    /// transform.translation.x = 10.;
+    /// let result = buffer.try_insert(transform);
+    /// assert!(matches!(
+    ///     result,
+    ///     Err(BufferError::MotionAnomaly(anomaly))
+    ///         if anomaly.quantity == MotionQuantity::LinearVelocity
+    /// ));
+    /// assert_eq!(buffer.len(), 1);
+    /// ```
+    pub fn try_insert(
+        &mut self,
+        transform: Transform,
+    ) -> Result<Option<MotionAnomaly>, BufferError> {
+        let anomaly = self
+            .screen(&transform)
+            .inspect_err(|error| self.count_rejection(error))?;
+        self.insert_screened(transform);
+        Ok(anomaly)
+    }
+
+    /// Checks a transform against the configured `MotionLimits`, without inserting it.
+    ///
+    /// The transform is compared with the stored transforms before its timestamp, in
+    /// either tier, so out-of-order transforms are checked against their own history.
+    ///
+    /// # Returns
+    ///
+    /// The first limit the transform exceeds, or `None` if it exceeds none or the buffer
+    /// has no limits.
+    pub fn check_motion(
+        &self,
+        transform: &Transform,
+    ) -> Option<MotionAnomaly> {
+        let limits = self.config.motion_limits?;
+        if self.is_static || transform.timestamp.nanoseconds == 0 {
+            return None;
+        }
+        let before = |timestamp: Timestamp| {
+            self.get_nearest(&Timestamp {
+                nanoseconds: timestamp.nanoseconds.checked_sub(1)?,
+            })
+            .0
+            .map(|(_, tf)| tf)
+        };
+        let previous = before(transform.timestamp);
+        let before_previous = previous.and_then(|tf| before(tf.timestamp));
+        motion::check(&limits, transform, previous, before_previous)
+    }
+
+    /// Checks a transform against the configured `max_lateness`, `DuplicatePolicy` and
+    /// `MotionLimits`, failing if any of them rejects it.
+    ///
+    /// Duplicates discarded under `DuplicatePolicy::KeepFirst` are let through without a
+    /// motion check, for `insert_screened` to discard.
+    pub(crate) fn screen(
+        &self,
+        transform: &Transform,
+    ) -> Result<Option<MotionAnomaly>, BufferError> {
+        let (parent, child, timestamp) = (transform.parent, transform.child, transform.timestamp);
+        if timestamp.nanoseconds != 0 {
+            if let (Some(window), Some(newest)) = (self.config.max_lateness, self.data.last()) {
+                if (newest.timestamp - timestamp).is_ok_and(|behind| behind > window) {
+                    return Err(BufferError::LateTransform(parent, child, timestamp, window));
+                }
+            }
+            if self.holds(&timestamp) {
+                match self.config.duplicates {
+                    DuplicatePolicy::Replace => {}
+                    DuplicatePolicy::KeepFirst => return Ok(None),
+                    DuplicatePolicy::Reject => {
+                        return Err(BufferError::DuplicateTimestamp(parent, child, timestamp));
+                    }
+                }
+            }
+        }
+
+        let anomaly = self.check_motion(transform);
+        match (anomaly, self.config.motion_limits) {
+            (Some(anomaly), Some(limits)) if limits.action == AnomalyAction::Reject => {
+                Err(BufferError::MotionAnomaly(anomaly))
+            }
+            _ => Ok(anomaly),
+        }
+    }
+
+    /// Adds a transform that `screen` has let through.
+    pub(crate) fn insert_screened(
+        &mut self,
+        transform: Transform,
+    ) {
+        if self.config.duplicates == DuplicatePolicy::KeepFirst
+            && transform.timestamp.nanoseconds != 0
+            && self.holds(&transform.timestamp)
+        {
+            self.rejected.duplicates += 1;
+            return;
+        }
+
+        match self.admit(&transform) {
+            Admission::Insert => {}
+            Admission::Drop => {
+                self.decimated.dropped += 1;
+                return;
+            }
+            Admission::Merge => {
+                self.data.pop_last();
+                self.data.insert(transform);
+                self.decimated.merged += 1;
+                self.recheck_segment();
+                return;
+            }
+        }
+
         self.is_static = transform.timestamp.nanoseconds == 0;
-        self.data.insert(transform.timestamp, transform);
+        let compressed = self.compresses(&transform);
+        if compressed {
+            if let Some(last) = self.data.pop_last() {
+                self.segment.push(last);
+                self.decimated.compressed += 1;
+            }
+        } else if self
+            .data
+            .last()
+            .is_some_and(|last| transform.timestamp > last.timestamp)
+        {
+            // Keeping the newest stored transform closes the span it was dropped from
+            self.segment.clear();
+        }
+        self.store(transform);
+        if !compressed {
+            self.recheck_segment();
+        }
 
         if !self.is_static {
             self.delete_expired();
@@ -183,6 +497,10 @@ impl Buffer {
 
     /// Retrieves a transform from the buffer at the specified timestamp.
     ///
+    /// Static transforms and transforms stored at exactly `timestamp` are copied out as
+    /// stored. Any other timestamp is answered from the nearest stored transforms in the
+    /// configured `InterpolationMode`, which interpolates between them by default.
+    ///
     /// # Examples
     ///
     /// ```
@@ -231,9 +549,57 @@ impl Buffer {
         &self,
         timestamp: &Timestamp,
     ) -> Result<Transform, BufferError> {
+        self.get_with(timestamp, self.config.interpolation)
+    }
+
+    /// Retrieves a transform from the buffer at the specified timestamp, answering in `mode`
+    /// rather than the configured `InterpolationMode`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BufferError::NoTransformAvailable` if `timestamp` is outside the time range
+    /// of the buffer, or off a stored transform in `InterpolationMode::Exact`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{
+    ///     core::{Buffer, InterpolationMode},
+    ///     geometry::Transform,
+    ///     time::Timestamp,
+    /// };
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// let t = Timestamp::now();
+    /// let mut transform = Transform::identity();
+    /// transform.timestamp = t;
+    /// buffer.insert(transform);
+    /// transform.timestamp = (t + Duration::from_secs(1)).unwrap();
+    /// buffer.insert(transform);
+    ///
+    /// let between = (t + Duration::from_millis(700)).unwrap();
+    /// let nearest = buffer.get_with(&between, InterpolationMode::Nearest);
+    /// assert_eq!(nearest.unwrap().timestamp, transform.timestamp);
+    /// ```
+    pub fn get_with(
+        &self,
+        timestamp: &Timestamp,
+        mode: InterpolationMode,
+    ) -> Result<Transform, BufferError> {
+        self.get_sourced(timestamp, mode)
+            .map(|(transform, _)| transform)
+    }
+
+    /// Retrieves a transform in `mode`, along with how it was obtained.
+    fn get_sourced(
+        &self,
+        timestamp: &Timestamp,
+        mode: InterpolationMode,
+    ) -> Result<(Transform, LinkSource), BufferError> {
         if self.is_static {
             match self.data.get(&Timestamp { nanoseconds: 0 }) {
-                Some(tf) => return Ok(tf.clone()),
+                Some(tf) => return Ok((*tf, LinkSource::Static)),
                 None => return Err(BufferError::NoTransformAvailable),
             }
         };
@@ -241,15 +607,531 @@ impl Buffer {
         let (before, after) = self.get_nearest(timestamp);
 
         match (before, after) {
-            (Some(before), Some(after)) => Ok(Transform::interpolate(
-                before.1.clone(),
-                after.1.clone(),
-                *timestamp,
-            )?),
+            // Exact hits are returned as stored, without interpolating
+            (Some(before), _) if before.0 == timestamp => Ok((*before.1, LinkSource::Exact)),
+            // Lookups inside a gap fail in any mode that answers between transforms
+            (Some(before), Some(after)) if mode != InterpolationMode::Exact => {
+                self.check_gap(before.0, after.0)?;
+                self.between(before, after, timestamp, mode)
+            }
             _ => Err(BufferError::NoTransformAvailable),
         }
     }
 
+    /// Answers a lookup between two stored transforms in `mode`.
+    fn between(
+        &self,
+        before: (&Timestamp, &Transform),
+        after: (&Timestamp, &Transform),
+        timestamp: &Timestamp,
+        mode: InterpolationMode,
+    ) -> Result<(Transform, LinkSource), BufferError> {
+        let transform = match mode {
+            InterpolationMode::Linear => Self::interpolate(before.1, after.1, *timestamp)?,
+            InterpolationMode::Nearest => {
+                let since_before = timestamp.nanoseconds - before.0.nanoseconds;
+                let until_after = after.0.nanoseconds - timestamp.nanoseconds;
+                let nearest = if since_before <= until_after {
+                    before.1
+                } else {
+                    after.1
+                };
+                return Ok((*nearest, LinkSource::Held));
+            }
+            InterpolationMode::Previous => return Ok((*before.1, LinkSource::Held)),
+            InterpolationMode::Exact => return Err(BufferError::NoTransformAvailable),
+            InterpolationMode::Cubic => {
+                let (previous, next) = self.neighbours(before.1, after.1);
+                Transform::interpolate_cubic(previous, before.1, after.1, next, *timestamp)?
+            }
+        };
+        Ok((transform, LinkSource::Interpolated))
+    }
+
+    /// Retrieves a transform like `lookup`, along with how it was obtained.
+    pub(crate) fn trace(
+        &self,
+        timestamp: &Timestamp,
+        mode: Option<InterpolationMode>,
+    ) -> Result<(Transform, LinkLookup), BufferError> {
+        let mode = mode.unwrap_or(self.config.interpolation);
+        let (mut transform, source) = self.get_sourced(timestamp, mode)?;
+
+        let samples = match source {
+            LinkSource::Static => Vec::new(),
+            LinkSource::Exact | LinkSource::Held => vec![transform.timestamp],
+            LinkSource::Interpolated => {
+                let (Some(before), Some(after)) = self.get_nearest(timestamp) else {
+                    return Err(BufferError::NoTransformAvailable);
+                };
+                let mut samples = vec![*before.0, *after.0];
+                if mode == InterpolationMode::Cubic {
+                    let (previous, next) = self.neighbours(before.1, after.1);
+                    samples.splice(0..0, previous.map(|tf| tf.timestamp));
+                    samples.extend(next.map(|tf| tf.timestamp));
+                }
+                samples
+            }
+        };
+        let staleness = samples
+            .iter()
+            .map(|sample| {
+                Duration::from_nanos(sample.nanoseconds.abs_diff(timestamp.nanoseconds) as u64)
+            })
+            .min()
+            .unwrap_or_default();
+
+        let link = LinkLookup {
+            parent: transform.parent,
+            child: transform.child,
+            source,
+            samples,
+            staleness,
+        };
+        if !self.is_static {
+            transform.timestamp = *timestamp;
+        }
+        Ok((transform, link))
+    }
+
+    /// Retrieves a transform in `mode` if a query chose one, or in the configured mode.
+    ///
+    /// Held transforms are stamped with the requested timestamp, so that links answered in
+    /// different modes still compose along a chain.
+    pub(crate) fn lookup(
+        &self,
+        timestamp: &Timestamp,
+        mode: Option<InterpolationMode>,
+    ) -> Result<Transform, BufferError> {
+        let mut transform = self.get_with(timestamp, mode.unwrap_or(self.config.interpolation))?;
+        if !self.is_static {
+            transform.timestamp = *timestamp;
+        }
+        Ok(transform)
+    }
+
+    /// Returns whether the buffer holds a static transform, which is valid at any time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{core::Buffer, geometry::Transform};
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// buffer.insert(Transform::identity());
+    /// assert!(buffer.is_static());
+    /// ```
+    pub fn is_static(&self) -> bool {
+        self.is_static
+    }
+
+    /// Returns the most recent transform stored in the buffer, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{core::Buffer, geometry::Transform};
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// assert!(buffer.latest().is_none());
+    ///
+    /// buffer.insert(Transform::identity());
+    /// assert_eq!(buffer.latest(), Some(&Transform::identity()));
+    /// ```
+    pub fn latest(&self) -> Option<&Transform> {
+        self.data.last().or_else(|| self.archive.as_ref()?.latest())
+    }
+
+    /// Returns the timestamps of the oldest and newest transforms in the buffer.
+    ///
+    /// Returns `None` if the buffer is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{core::Buffer, geometry::Transform, time::Timestamp};
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// buffer.insert(Transform::identity());
+    ///
+    /// assert_eq!(
+    ///     buffer.time_range(),
+    ///     Some((Timestamp::zero(), Timestamp::zero()))
+    /// );
+    /// ```
+    pub fn time_range(&self) -> Option<(Timestamp, Timestamp)> {
+        let (first, last) = match self
+            .archive
+            .as_ref()
+            .and_then(|archive| archive.time_range())
+        {
+            Some((first, last)) => (first, self.data.last().map_or(last, |tf| tf.timestamp)),
+            None => (self.data.first()?.timestamp, self.data.last()?.timestamp),
+        };
+        Some((first, last))
+    }
+
+    /// Returns how many transforms the configured `Decimation` and `Compression` have
+    /// thinned out.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{
+    ///     core::{Buffer, BufferConfig, Decimation},
+    ///     geometry::Transform,
+    ///     time::Timestamp,
+    /// };
+    ///
+    /// let mut buffer = Buffer::with_config(BufferConfig {
+    ///     decimation: Some(Decimation::interval(Duration::from_millis(10))),
+    ///     ..BufferConfig::new(Duration::from_secs(10))
+    /// });
+    ///
+    /// let mut transform = Transform::identity();
+    /// transform.timestamp = Timestamp::now();
+    /// buffer.insert(transform);
+    /// transform.timestamp = (transform.timestamp + Duration::from_millis(1)).unwrap();
+    /// buffer.insert(transform);
+    ///
+    /// assert_eq!(buffer.len(), 1);
+    /// assert_eq!(buffer.decimation_stats().dropped, 1);
+    /// ```
+    pub fn decimation_stats(&self) -> DecimationStats {
+        let archived = self
+            .archive
+            .as_ref()
+            .map(|archive| archive.decimation_stats())
+            .unwrap_or_default();
+        DecimationStats {
+            dropped: self.decimated.dropped + archived.dropped,
+            merged: self.decimated.merged + archived.merged,
+            compressed: self.decimated.compressed + archived.compressed,
+        }
+    }
+
+    /// Returns how many transforms the configured `DuplicatePolicy`, `max_lateness` and
+    /// `MotionLimits` have turned away.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{
+    ///     core::{Buffer, BufferConfig},
+    ///     geometry::Transform,
+    ///     time::Timestamp,
+    /// };
+    ///
+    /// let mut buffer = Buffer::with_config(BufferConfig {
+    ///     max_lateness: Some(Duration::from_millis(100)),
+    ///     ..BufferConfig::new(Duration::from_secs(10))
+    /// });
+    ///
+    /// let mut transform = Transform::identity();
+    /// let t = Timestamp::now();
+    /// transform.timestamp = t;
+    /// buffer.insert(transform);
+    /// transform.timestamp = (t - Duration::from_secs(1)).unwrap();
+    /// assert!(buffer.try_insert(transform).is_err());
+    ///
+    /// assert_eq!(buffer.rejection_stats().late, 1);
+    /// ```
+    pub fn rejection_stats(&self) -> RejectionStats {
+        self.rejected
+    }
+
+    /// Returns the number of transforms stored in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{core::Buffer, geometry::Transform};
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// assert!(buffer.is_empty());
+    ///
+    /// buffer.insert(Transform::identity());
+    /// assert_eq!(buffer.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.data.len() + self.archive.as_ref().map_or(0, |archive| archive.len())
+    }
+
+    /// Returns whether the buffer holds no transforms.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Estimates the bytes the buffer holds, including the storage of its transforms.
+    ///
+    /// A ring is counted at its full capacity, since it is allocated up front.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{core::Buffer, geometry::Transform};
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// let empty = buffer.memory_usage();
+    ///
+    /// buffer.insert(Transform::identity());
+    /// assert!(buffer.memory_usage() > empty);
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        // Compression is counted at the most it remembers, so inserts never grow it
+        let segment = match self.config.compression {
+            Some(_) => MAX_SEGMENT * size_of::<Transform>(),
+            None => 0,
+        };
+        let archive = self
+            .archive
+            .as_ref()
+            .map_or(0, |archive| archive.memory_usage());
+        size_of::<Self>() + self.data.heap_bytes() + segment + archive
+    }
+
+    /// Counts a transform rejected by `screen` in the `rejection_stats`.
+    fn count_rejection(
+        &mut self,
+        error: &BufferError,
+    ) {
+        match error {
+            BufferError::LateTransform(..) => self.rejected.late += 1,
+            BufferError::DuplicateTimestamp(..) => self.rejected.duplicates += 1,
+            BufferError::MotionAnomaly(..) => self.rejected.anomalies += 1,
+            _ => {}
+        }
+    }
+
+    /// Returns whether a transform is stored at exactly `timestamp`, in either tier.
+    fn holds(
+        &self,
+        timestamp: &Timestamp,
+    ) -> bool {
+        self.get_nearest(timestamp)
+            .0
+            .is_some_and(|(stored, _)| stored == timestamp)
+    }
+
+    /// Decides whether the configured `Decimation` thins out `transform`.
+    fn admit(
+        &self,
+        transform: &Transform,
+    ) -> Admission {
+        let Some(decimation) = self.config.decimation else {
+            return Admission::Insert;
+        };
+        let Some(last) = self.data.last() else {
+            return Admission::Insert;
+        };
+        // Only input newer than the whole buffer is thinned out
+        let Ok(since_last) = transform.timestamp - last.timestamp else {
+            return Admission::Insert;
+        };
+        // A transform that changes the parent always gets through
+        if self.is_static || since_last.is_zero() || transform.parent != last.parent {
+            return Admission::Insert;
+        }
+
+        match decimation.mode {
+            DecimationMode::Drop if since_last < decimation.min_interval => Admission::Drop,
+            DecimationMode::Merge => {
+                let open = self.data.penultimate().is_some_and(|previous| {
+                    (last.timestamp - previous.timestamp)
+                        .is_ok_and(|interval| interval < decimation.min_interval)
+                });
+                if open {
+                    Admission::Merge
+                } else {
+                    Admission::Insert
+                }
+            }
+            DecimationMode::Drop => Admission::Insert,
+        }
+    }
+
+    /// Decides whether the configured `Compression` drops the newest stored transform in
+    /// favour of `transform`.
+    fn compresses(
+        &self,
+        transform: &Transform,
+    ) -> bool {
+        let Some(compression) = self.config.compression else {
+            return false;
+        };
+        let (Some(anchor), Some(last)) = (self.data.penultimate(), self.data.last()) else {
+            return false;
+        };
+        if transform.timestamp <= last.timestamp
+            || transform.parent != last.parent
+            || anchor.parent != last.parent
+            || self.segment.len() >= MAX_SEGMENT
+            // Cubic lookups also lean on the transforms beyond the span, which later inserts
+            // move, so only the spans next to it would stay bounded
+            || self.config.interpolation == InterpolationMode::Cubic
+        {
+            return false;
+        }
+
+        // Everything dropped since the anchor must survive a lookup across it
+        self.segment
+            .iter()
+            .chain(iter::once(last))
+            .all(|dropped| self.approximates(&compression, anchor, transform, dropped))
+    }
+
+    /// Returns whether a lookup between `before` and `after`, in the configured
+    /// `InterpolationMode`, answers within the `Compression` tolerances of `dropped`.
+    fn approximates(
+        &self,
+        compression: &Compression,
+        before: &Transform,
+        after: &Transform,
+        dropped: &Transform,
+    ) -> bool {
+        self.between(
+            (&before.timestamp, before),
+            (&after.timestamp, after),
+            &dropped.timestamp,
+            self.config.interpolation,
+        )
+        .is_ok_and(|(approximation, _)| compression.reproduces(&approximation, dropped))
+    }
+
+    /// Checks the transforms compression dropped against the stored transforms now around
+    /// them, and stores them again if interpolating no longer reproduces them.
+    ///
+    /// Needed whenever an insert changes the span they were dropped from without extending
+    /// it, by filling it in out of order or by merging into its newest end.
+    fn recheck_segment(&mut self) {
+        let Some(compression) = self.config.compression else {
+            return;
+        };
+        // Dropped transforms since replaced, or expired along with the anchor, need no bound
+        let reproduced =
+            self.segment
+                .iter()
+                .all(|dropped| match self.data.nearest(&dropped.timestamp) {
+                    (Some((_, before)), Some((_, after))) => {
+                        before.timestamp == dropped.timestamp
+                            || (before.parent == after.parent
+                                && self.approximates(&compression, before, after, dropped))
+                    }
+                    _ => true,
+                });
+
+        if reproduced {
+            // Only what was dropped after the anchor stays open to further compression
+            let anchor = self.data.penultimate().map(|tf| tf.timestamp);
+            self.segment
+                .retain(|dropped| anchor.is_some_and(|anchor| dropped.timestamp > anchor));
+            return;
+        }
+        for dropped in std::mem::take(&mut self.segment) {
+            if self.data.get(&dropped.timestamp).is_none() {
+                self.store(dropped);
+                self.decimated.compressed -= 1;
+            }
+        }
+    }
+
+    /// Interpolates between two stored transforms, as lookups between them do.
+    fn interpolate(
+        before: &Transform,
+        after: &Transform,
+        timestamp: Timestamp,
+    ) -> Result<Transform, BufferError> {
+        Ok(Transform::interpolate(before, after, timestamp)?)
+    }
+
+    /// Returns whether inserting `transform` stores one more transform, rather than
+    /// replacing a stored one or being thinned out by decimation.
+    ///
+    /// Counts both tiers: history older than the hot window is answered by the archive.
+    pub(crate) fn adds_sample(
+        &self,
+        transform: &Transform,
+    ) -> bool {
+        match self.archive.as_deref() {
+            Some(archive) if self.predates_hot(transform) => archive.adds_sample(transform),
+            _ => {
+                self.admit(transform) == Admission::Insert
+                    && self.data.get(&transform.timestamp).is_none()
+            }
+        }
+    }
+
+    /// Estimates the bytes inserting `transform` adds to `memory_usage`, before any
+    /// expired transforms are removed.
+    ///
+    /// Includes the archive, for history older than the hot window and for the oldest
+    /// transform a full ring ages into it.
+    pub(crate) fn growth(
+        &self,
+        transform: &Transform,
+    ) -> usize {
+        let archive = self.archive.as_deref();
+        if let Some(archive) = archive.filter(|_| self.predates_hot(transform)) {
+            return archive.growth(transform);
+        }
+        if !self.adds_sample(transform) {
+            return 0;
+        }
+        let aged = archive
+            .filter(|_| self.data.is_full())
+            .zip(self.data.first())
+            .map_or(0, |(archive, oldest)| archive.growth(oldest));
+        self.data.entry_bytes() + aged
+    }
+
+    /// Returns whether `transform` is dynamic and older than the hot window behind the
+    /// newest transform, so that an archive stores it directly.
+    ///
+    /// Late transforms inside the window stay in the hot tier, even if they are older than
+    /// every transform it holds.
+    fn predates_hot(
+        &self,
+        transform: &Transform,
+    ) -> bool {
+        let Some(policy) = self.config.archive else {
+            return false;
+        };
+        transform.timestamp.nanoseconds != 0
+            && self
+                .data
+                .last()
+                .and_then(|newest| (newest.timestamp - policy.hot_window).ok())
+                .is_some_and(|threshold| transform.timestamp < threshold)
+    }
+
+    /// Removes and returns the oldest transform in the buffer.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{core::Buffer, geometry::Transform};
+    ///
+    /// let mut buffer = Buffer::new(Duration::from_secs(10));
+    /// buffer.insert(Transform::identity());
+    ///
+    /// assert_eq!(buffer.pop_oldest(), Some(Transform::identity()));
+    /// assert!(buffer.is_empty());
+    /// ```
+    pub fn pop_oldest(&mut self) -> Option<Transform> {
+        self.archive
+            .as_mut()
+            .and_then(|archive| archive.pop_oldest())
+            .or_else(|| self.data.pop_first())
+    }
+
     /// Retrieves the nearest transforms before and after the given timestamp.
     ///
     /// This function returns a tuple containing the nearest transform before
@@ -258,28 +1140,109 @@ impl Buffer {
     fn get_nearest(
         &self,
         timestamp: &Timestamp,
-    ) -> NearestTransforms {
-        let before = self.data.range(..=timestamp).next_back();
+    ) -> NearestTransforms<'_> {
+        let (before, after) = self.data.nearest(timestamp);
+        match &self.archive {
+            // Everything archived is older than the hot window, so the archive only answers
+            // below it, bridging to the oldest hot transform
+            Some(archive) if before.is_none() => {
+                let (archived, archived_after) = archive.get_nearest(timestamp);
+                (archived, archived_after.or(after))
+            }
+            _ => (before, after),
+        }
+    }
+
+    /// Checks that lookups may answer between transforms at `before` and `after`.
+    fn check_gap(
+        &self,
+        before: &Timestamp,
+        after: &Timestamp,
+    ) -> Result<(), BufferError> {
+        let Some(max_gap) = self.config.max_interpolation_gap else {
+            return Ok(());
+        };
+        let gap = (*after - *before).map_err(TransformError::from)?;
+        if gap > max_gap {
+            return Err(BufferError::InterpolationGap(gap, max_gap));
+        }
+        Ok(())
+    }
 
-        if let Some((t, _)) = before {
-            if t == timestamp {
-                return (before, before);
+    /// Retrieves the transforms just before `before` and just after `after`, across both
+    /// tiers, if they share the parent of the segment between them.
+    fn neighbours<'a>(
+        &'a self,
+        before: &Transform,
+        after: &Transform,
+    ) -> (Option<&'a Transform>, Option<&'a Transform>) {
+        // Nothing is stored past either end of the time range
+        let previous = before
+            .timestamp
+            .nanoseconds
+            .checked_sub(1)
+            .and_then(|nanoseconds| self.get_nearest(&Timestamp { nanoseconds }).0)
+            .map(|(_, tf)| tf);
+        let next = after
+            .timestamp
+            .nanoseconds
+            .checked_add(1)
+            .and_then(|nanoseconds| self.get_nearest(&Timestamp { nanoseconds }).1)
+            .map(|(_, tf)| tf);
+        let same_parent = |tf: &&Transform| tf.parent == before.parent;
+        (previous.filter(same_parent), next.filter(same_parent))
+    }
+
+    /// Stores a transform in the tier it belongs to.
+    ///
+    /// Dynamic transforms older than the hot window go straight to the archive, and a full
+    /// ring ages its oldest transform into the archive rather than dropping it.
+    fn store(
+        &mut self,
+        transform: Transform,
+    ) {
+        let predates_hot = self.predates_hot(&transform);
+        if let Some(archive) = self.archive.as_mut() {
+            if predates_hot {
+                archive.insert(transform);
+                return;
+            }
+            if self.data.is_full() && self.data.get(&transform.timestamp).is_none() {
+                if let Some(oldest) = self.data.pop_first() {
+                    archive.insert(oldest);
+                }
             }
         }
+        self.data.insert(transform);
+    }
 
-        let after = self.data.range(timestamp..).next();
-        (before, after)
+    /// Moves the transforms older than the hot window into the archive.
+    fn age(&mut self) {
+        let (Some(policy), Some(archive)) = (self.config.archive, self.archive.as_mut()) else {
+            return;
+        };
+        let Ok(threshold) = Timestamp::now() - policy.hot_window else {
+            return;
+        };
+        while self.data.first().is_some_and(|tf| tf.timestamp < threshold) {
+            if let Some(transform) = self.data.pop_first() {
+                archive.insert(transform);
+            }
+        }
+        archive.delete_expired();
     }
 
     /// Removes expired transforms from the buffer based on the max_age.
     ///
     /// This function deletes all transforms from the buffer that have a
-    /// timestamp older than the current time minus the max_age.
+    /// timestamp older than the current time minus the max_age, and ages
+    /// transforms past the hot window into the archive.
     fn delete_expired(&mut self) {
-        let timestamp_threshold = Timestamp::now() - self.max_age;
+        let timestamp_threshold = Timestamp::now() - self.config.max_age;
         if let Ok(t) = timestamp_threshold {
-            self.data.retain(|&k, _| k >= t);
+            self.data.expire_before(t);
         }
+        self.age();
     }
 }
 
@@ -0,0 +1,159 @@
+use super::MotionLimits;
+use crate::{
+    frame::FrameId,
+    geometry::{Transform, Vector3},
+    time::Timestamp,
+};
+use std::fmt;
+
+/// A transform that moved its frame faster than its `MotionLimits` allow.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MotionAnomaly {
+    /// The parent frame of the transform.
+    pub parent: FrameId,
+    /// The child frame of the transform.
+    pub child: FrameId,
+    /// The timestamp of the transform.
+    pub timestamp: Timestamp,
+    /// The quantity that exceeded its limit.
+    pub quantity: MotionQuantity,
+    /// The value of the quantity between the transform and the ones stored before it.
+    pub value: f64,
+    /// The limit it exceeded.
+    pub limit: f64,
+}
+
+/// A quantity limited by `MotionLimits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MotionQuantity {
+    /// `MotionLimits::max_linear_velocity`
+    LinearVelocity,
+    /// `MotionLimits::max_angular_velocity`
+    AngularVelocity,
+    /// `MotionLimits::max_linear_acceleration`
+    LinearAcceleration,
+    /// `MotionLimits::max_angular_acceleration`
+    AngularAcceleration,
+}
+
+impl fmt::Display for MotionQuantity {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::LinearVelocity => write!(f, "linear velocity"),
+            Self::AngularVelocity => write!(f, "angular velocity"),
+            Self::LinearAcceleration => write!(f, "linear acceleration"),
+            Self::AngularAcceleration => write!(f, "angular acceleration"),
+        }
+    }
+}
+
+impl fmt::Display for MotionAnomaly {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "Transform from {} to {} at {} has a {} of {}, over the limit of {}",
+            self.parent, self.child, self.timestamp, self.quantity, self.value, self.limit
+        )
+    }
+}
+
+/// The linear and angular velocity of a frame between two transforms.
+struct Velocity {
+    linear: Vector3,
+    angular: Vector3,
+}
+
+impl Velocity {
+    /// Computes the velocity from `from` to `to`, with the angular velocity taken along
+    /// the shortest arc, or `None` if they are not in timestamp order.
+    fn between(
+        from: &Transform,
+        to: &Transform,
+    ) -> Option<Self> {
+        let seconds = (to.timestamp - from.timestamp).ok()?.as_secs_f64();
+        if seconds <= 0. {
+            return None;
+        }
+
+        let mut delta = to.rotation * from.rotation.conjugate();
+        if delta.w < 0. {
+            delta = delta.scale(-1.);
+        }
+        // The logarithm of a unit quaternion is half its rotation vector
+        let half = delta.ln();
+        let rotation = Vector3::new(half.x, half.y, half.z) * 2.;
+
+        Some(Self {
+            linear: (to.translation - from.translation) / seconds,
+            angular: rotation / seconds,
+        })
+    }
+}
+
+/// Finds the first limit that `transform` exceeds, given the stored transform before it
+/// and the one before that.
+pub(super) fn check(
+    limits: &MotionLimits,
+    transform: &Transform,
+    previous: Option<&Transform>,
+    before_previous: Option<&Transform>,
+) -> Option<MotionAnomaly> {
+    let previous = previous.filter(|tf| tf.parent == transform.parent)?;
+    let velocity = Velocity::between(previous, transform)?;
+    let anomaly = |quantity, value: f64, limit: Option<f64>| {
+        limit
+            .filter(|limit| value > *limit)
+            .map(|limit| MotionAnomaly {
+                parent: transform.parent,
+                child: transform.child,
+                timestamp: transform.timestamp,
+                quantity,
+                value,
+                limit,
+            })
+    };
+    let norm = |v: Vector3| v.dot(v).sqrt();
+
+    let exceeded = anomaly(
+        MotionQuantity::LinearVelocity,
+        norm(velocity.linear),
+        limits.max_linear_velocity,
+    )
+    .or_else(|| {
+        anomaly(
+            MotionQuantity::AngularVelocity,
+            norm(velocity.angular),
+            limits.max_angular_velocity,
+        )
+    });
+    if exceeded.is_some() {
+        return exceeded;
+    }
+
+    // Accelerations compare the velocities over the two intervals, whose midpoints are
+    // half of both intervals apart
+    let before_previous = before_previous.filter(|tf| tf.parent == transform.parent)?;
+    let earlier = Velocity::between(before_previous, previous)?;
+    let span = (transform.timestamp - before_previous.timestamp)
+        .ok()?
+        .as_secs_f64()
+        / 2.;
+    anomaly(
+        MotionQuantity::LinearAcceleration,
+        norm(velocity.linear - earlier.linear) / span,
+        limits.max_linear_acceleration,
+    )
+    .or_else(|| {
+        anomaly(
+            MotionQuantity::AngularAcceleration,
+            norm(velocity.angular - earlier.angular) / span,
+            limits.max_angular_acceleration,
+        )
+    })
+}
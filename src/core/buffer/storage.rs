@@ -0,0 +1,253 @@
+use crate::{
+    core::buffer::{config::Storage, NearestTransforms},
+    geometry::Transform,
+    time::Timestamp,
+};
+use std::collections::{BTreeMap, VecDeque};
+
+/// The transforms of a buffer, ordered by timestamp, in the configured data structure.
+#[derive(Clone)]
+pub(super) enum Samples {
+    BTree(BTreeMap<Timestamp, Transform>),
+    Ring(Ring),
+}
+
+/// A bounded, sorted ring of transforms.
+#[derive(Clone)]
+pub(super) struct Ring {
+    samples: VecDeque<Transform>,
+    capacity: usize,
+}
+
+impl Samples {
+    pub fn new(storage: Storage) -> Self {
+        match storage {
+            Storage::BTree => Self::BTree(BTreeMap::new()),
+            Storage::Ring { capacity } => {
+                let capacity = capacity.max(1);
+                Self::Ring(Ring {
+                    samples: VecDeque::with_capacity(capacity),
+                    capacity,
+                })
+            }
+        }
+    }
+
+    /// Inserts a transform, replacing any transform with the same timestamp.
+    pub fn insert(
+        &mut self,
+        transform: Transform,
+    ) {
+        match self {
+            Self::BTree(map) => {
+                map.insert(transform.timestamp, transform);
+            }
+            Self::Ring(ring) => ring.insert(transform),
+        }
+    }
+
+    /// Returns the transform stored at exactly `timestamp`.
+    pub fn get(
+        &self,
+        timestamp: &Timestamp,
+    ) -> Option<&Transform> {
+        match self {
+            Self::BTree(map) => map.get(timestamp),
+            Self::Ring(ring) => {
+                let index = ring.position(timestamp);
+                ring.samples
+                    .get(index)
+                    .filter(|tf| tf.timestamp == *timestamp)
+            }
+        }
+    }
+
+    /// Returns the nearest transforms at or before and at or after `timestamp`.
+    pub fn nearest(
+        &self,
+        timestamp: &Timestamp,
+    ) -> NearestTransforms<'_> {
+        match self {
+            Self::BTree(map) => {
+                let before = map.range(..=timestamp).next_back();
+
+                if let Some((t, _)) = before {
+                    if t == timestamp {
+                        return (before, before);
+                    }
+                }
+
+                let after = map.range(timestamp..).next();
+                (before, after)
+            }
+            Self::Ring(ring) => {
+                let index = ring
+                    .samples
+                    .partition_point(|tf| tf.timestamp <= *timestamp);
+                let before = index
+                    .checked_sub(1)
+                    .and_then(|i| ring.samples.get(i))
+                    .map(|tf| (&tf.timestamp, tf));
+
+                if let Some((t, _)) = before {
+                    if t == timestamp {
+                        return (before, before);
+                    }
+                }
+
+                let after = ring.samples.get(index).map(|tf| (&tf.timestamp, tf));
+                (before, after)
+            }
+        }
+    }
+
+    /// Returns the oldest transform.
+    pub fn first(&self) -> Option<&Transform> {
+        match self {
+            Self::BTree(map) => map.values().next(),
+            Self::Ring(ring) => ring.samples.front(),
+        }
+    }
+
+    /// Returns the newest transform.
+    pub fn last(&self) -> Option<&Transform> {
+        match self {
+            Self::BTree(map) => map.values().next_back(),
+            Self::Ring(ring) => ring.samples.back(),
+        }
+    }
+
+    /// Returns the transform before the newest one.
+    pub fn penultimate(&self) -> Option<&Transform> {
+        match self {
+            Self::BTree(map) => map.values().nth_back(1),
+            Self::Ring(ring) => ring
+                .samples
+                .len()
+                .checked_sub(2)
+                .and_then(|i| ring.samples.get(i)),
+        }
+    }
+
+    /// Returns whether inserting a new timestamp would drop the oldest transform.
+    pub fn is_full(&self) -> bool {
+        match self {
+            Self::BTree(_) => false,
+            Self::Ring(ring) => ring.samples.len() >= ring.capacity,
+        }
+    }
+
+    /// Returns the number of stored transforms.
+    pub fn len(&self) -> usize {
+        match self {
+            Self::BTree(map) => map.len(),
+            Self::Ring(ring) => ring.samples.len(),
+        }
+    }
+
+    /// Estimates the bytes allocated for the transforms.
+    pub fn heap_bytes(&self) -> usize {
+        match self {
+            // Entries are counted as stored, ignoring the slack in the nodes of the map
+            Self::BTree(map) => map.len() * size_of::<(Timestamp, Transform)>(),
+            Self::Ring(ring) => ring.samples.capacity() * size_of::<Transform>(),
+        }
+    }
+
+    /// Estimates the bytes one more transform adds to the allocation.
+    pub fn entry_bytes(&self) -> usize {
+        match self {
+            Self::BTree(_) => size_of::<(Timestamp, Transform)>(),
+            // A ring allocates its capacity up front
+            Self::Ring(_) => 0,
+        }
+    }
+
+    /// Removes and returns the oldest transform.
+    pub fn pop_first(&mut self) -> Option<Transform> {
+        match self {
+            Self::BTree(map) => map.pop_first().map(|(_, tf)| tf),
+            Self::Ring(ring) => ring.samples.pop_front(),
+        }
+    }
+
+    /// Removes and returns the newest transform.
+    pub fn pop_last(&mut self) -> Option<Transform> {
+        match self {
+            Self::BTree(map) => map.pop_last().map(|(_, tf)| tf),
+            Self::Ring(ring) => ring.samples.pop_back(),
+        }
+    }
+
+    /// Removes every transform older than `threshold`.
+    pub fn expire_before(
+        &mut self,
+        threshold: Timestamp,
+    ) {
+        match self {
+            Self::BTree(map) => {
+                while map
+                    .first_key_value()
+                    .is_some_and(|(timestamp, _)| *timestamp < threshold)
+                {
+                    map.pop_first();
+                }
+            }
+            Self::Ring(ring) => {
+                while ring
+                    .samples
+                    .front()
+                    .is_some_and(|tf| tf.timestamp < threshold)
+                {
+                    ring.samples.pop_front();
+                }
+            }
+        }
+    }
+
+    /// Consumes the samples, returning the transforms oldest first.
+    pub fn into_vec(self) -> Vec<Transform> {
+        match self {
+            Self::BTree(map) => map.into_values().collect(),
+            Self::Ring(ring) => ring.samples.into(),
+        }
+    }
+}
+
+impl Ring {
+    /// Returns the index of the first transform not older than `timestamp`.
+    fn position(
+        &self,
+        timestamp: &Timestamp,
+    ) -> usize {
+        self.samples.partition_point(|tf| tf.timestamp < *timestamp)
+    }
+
+    fn insert(
+        &mut self,
+        transform: Transform,
+    ) {
+        // Monotonic input only ever appends
+        let index = match self.samples.back() {
+            Some(last) if last.timestamp >= transform.timestamp => {
+                self.position(&transform.timestamp)
+            }
+            _ => self.samples.len(),
+        };
+
+        if let Some(existing) = self.samples.get_mut(index) {
+            if existing.timestamp == transform.timestamp {
+                *existing = transform;
+                return;
+            }
+        }
+
+        if self.samples.len() < self.capacity {
+            self.samples.insert(index, transform);
+        } else if index > 0 {
+            self.samples.pop_front();
+            self.samples.insert(index - 1, transform);
+        }
+        // Otherwise the transform is older than everything a full ring holds and is dropped
+    }
+}
@@ -1,10 +1,16 @@
 #[cfg(test)]
 mod buffer_tests {
     use crate::{
-        core::Buffer,
+        core::{
+            AnomalyAction, ArchivePolicy, Buffer, BufferConfig, Compression, Decimation,
+            DecimationMode, DecimationStats, DuplicatePolicy, InterpolationMode, LinkSource,
+            MotionLimits, MotionQuantity, RejectionStats, Storage,
+        },
+        errors::BufferError,
         geometry::{Quaternion, Transform, Vector3},
         time::Timestamp,
     };
+    use approx::assert_abs_diff_eq;
     use std::time::Duration;
 
     fn create_transform(t: Timestamp) -> Transform {
@@ -36,7 +42,7 @@ mod buffer_tests {
         let mut buffer = Buffer::new(Duration::from_secs(10));
         let t = Timestamp::now();
         let transform = create_transform(t);
-        buffer.insert(transform.clone());
+        buffer.insert(transform);
 
         let mut r = buffer.get(&transform.timestamp);
 
@@ -56,7 +62,7 @@ mod buffer_tests {
         let t = Timestamp::zero();
         let transform = create_transform(t);
 
-        buffer.insert(transform.clone());
+        buffer.insert(transform);
 
         let mut r = buffer.get(&(transform.timestamp + Duration::from_secs(1)).unwrap());
 
@@ -68,6 +74,21 @@ mod buffer_tests {
         assert_eq!(r.unwrap(), transform);
     }
 
+    #[test]
+    fn static_after_dynamic() {
+        let mut buffer = Buffer::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        buffer.insert(create_transform(t));
+
+        let mut transform = create_transform(Timestamp::zero());
+        transform.translation.x = 5.0;
+        buffer.insert(transform);
+
+        // The static transform is served at any time, even where dynamic ones are stored
+        assert_eq!(buffer.get(&t).unwrap(), transform);
+        assert_eq!(buffer.get(&Timestamp::zero()).unwrap(), transform);
+    }
+
     #[test]
     fn get_nearest() {
         let mut buffer = Buffer::new(Duration::from_secs(10));
@@ -77,9 +98,9 @@ mod buffer_tests {
         let p2 = create_transform((t - Duration::from_secs(1)).unwrap());
         let p3 = create_transform(t);
 
-        buffer.insert(p1.clone());
-        buffer.insert(p2.clone());
-        buffer.insert(p3.clone());
+        buffer.insert(p1);
+        buffer.insert(p2);
+        buffer.insert(p3);
 
         // Exact match
         let (before, after) = buffer.get_nearest(&p2.timestamp);
@@ -124,9 +145,9 @@ mod buffer_tests {
         let p2 = create_transform((t - Duration::from_secs(1)).unwrap());
         let p3 = create_transform(t);
 
-        buffer.insert(p1.clone());
-        buffer.insert(p2.clone());
-        buffer.insert(p3.clone());
+        buffer.insert(p1);
+        buffer.insert(p2);
+        buffer.insert(p3);
 
         let get_1 = buffer.get(&(t - Duration::from_secs(2)).unwrap());
         let get_2 = buffer.get(&(t - Duration::from_secs(1)).unwrap());
@@ -153,7 +174,7 @@ mod buffer_tests {
         let mut buffer = Buffer::new(Duration::from_secs(1));
         let t = Timestamp::now();
         let point = create_transform(t);
-        buffer.insert(point.clone());
+        buffer.insert(point);
 
         // Before the point
         let (before, after) = buffer.get_nearest(&(t - Duration::from_secs(1)).unwrap());
@@ -179,10 +200,1222 @@ mod buffer_tests {
         let p1 = create_transform((t - Duration::from_secs(2)).unwrap());
         let p2 = create_transform(t);
 
-        buffer.insert(p1.clone());
-        buffer.insert(p2.clone());
+        buffer.insert(p1);
+        buffer.insert(p2);
+
+        assert!(buffer.get(&p1.timestamp).is_err());
+        assert!(buffer.get(&p2.timestamp).is_ok());
+    }
+
+    #[test]
+    fn ring_insert_and_get() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 8 },
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let t = Timestamp::now();
+        let p1 = create_transform(t);
+        let mut p2 = create_transform((t + Duration::from_secs(2)).unwrap());
+        p2.translation.x = 3.0;
+
+        buffer.insert(p1);
+        buffer.insert(p2);
+
+        assert_eq!(buffer.get(&p1.timestamp).unwrap(), p1);
+        assert_eq!(buffer.get(&p2.timestamp).unwrap(), p2);
+
+        let r = buffer.get(&(t + Duration::from_secs(1)).unwrap()).unwrap();
+        assert_eq!(r.translation.x, 2.0);
+
+        assert!(buffer.get(&(t + Duration::from_secs(3)).unwrap()).is_err());
+    }
+
+    #[test]
+    fn ring_out_of_order_insert() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 8 },
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let t = Timestamp::now();
+
+        let p1 = create_transform((t - Duration::from_secs(2)).unwrap());
+        let p2 = create_transform((t - Duration::from_secs(1)).unwrap());
+        let p3 = create_transform(t);
+
+        buffer.insert(p3);
+        buffer.insert(p1);
+        buffer.insert(p2);
+
+        let (before, after) = buffer.get_nearest(&p2.timestamp);
+        assert_eq!(before.unwrap(), (&p2.timestamp, &p2));
+        assert_eq!(after.unwrap(), (&p2.timestamp, &p2));
+
+        let p_mid = (p1.timestamp + Duration::from_millis(500)).unwrap();
+        let (before, after) = buffer.get_nearest(&p_mid);
+        assert_eq!(before.unwrap(), (&p1.timestamp, &p1));
+        assert_eq!(after.unwrap(), (&p2.timestamp, &p2));
+
+        assert_eq!(buffer.time_range(), Some((p1.timestamp, p3.timestamp)));
+    }
+
+    #[test]
+    fn ring_replaces_same_timestamp() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 8 },
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let t = Timestamp::now();
+
+        let p1 = create_transform(t);
+        let mut p2 = create_transform(t);
+        p2.translation.x = 5.0;
+
+        buffer.insert(p1);
+        buffer.insert(p2);
+
+        assert_eq!(buffer.get(&t).unwrap(), p2);
+        assert_eq!(buffer.time_range(), Some((t, t)));
+    }
+
+    #[test]
+    fn ring_capacity_drops_oldest() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 2 },
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let t = Timestamp::now();
+
+        let p1 = create_transform((t - Duration::from_secs(2)).unwrap());
+        let p2 = create_transform((t - Duration::from_secs(1)).unwrap());
+        let p3 = create_transform(t);
+
+        buffer.insert(p1);
+        buffer.insert(p2);
+        buffer.insert(p3);
+
+        assert!(buffer.get(&p1.timestamp).is_err());
+        assert_eq!(buffer.time_range(), Some((p2.timestamp, p3.timestamp)));
+
+        // Older than everything a full ring holds
+        buffer.insert(p1);
+        assert_eq!(buffer.time_range(), Some((p2.timestamp, p3.timestamp)));
+    }
+
+    #[test]
+    fn ring_delete_expired() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 8 },
+            ..BufferConfig::new(Duration::from_secs(1))
+        });
+
+        let t = Timestamp::now();
+        let p1 = create_transform((t - Duration::from_secs(2)).unwrap());
+        let p2 = create_transform(t);
+
+        buffer.insert(p1);
+        buffer.insert(p2);
 
         assert!(buffer.get(&p1.timestamp).is_err());
         assert!(buffer.get(&p2.timestamp).is_ok());
     }
+
+    #[test]
+    fn ring_static() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 8 },
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let transform = create_transform(Timestamp::zero());
+        buffer.insert(transform);
+
+        let r = buffer.get(&Timestamp::now());
+        assert_eq!(r.unwrap(), transform);
+    }
+
+    #[test]
+    fn reconfigure_keeps_transforms() {
+        let mut buffer = Buffer::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+
+        let p1 = create_transform((t - Duration::from_secs(2)).unwrap());
+        let p2 = create_transform((t - Duration::from_secs(1)).unwrap());
+        let p3 = create_transform(t);
+
+        buffer.insert(p1);
+        buffer.insert(p2);
+        buffer.insert(p3);
+
+        let config = BufferConfig {
+            storage: Storage::Ring { capacity: 2 },
+            ..buffer.config()
+        };
+        buffer.reconfigure(config);
+
+        assert_eq!(buffer.config(), config);
+        assert!(buffer.get(&p1.timestamp).is_err());
+        assert_eq!(buffer.get(&p2.timestamp).unwrap(), p2);
+        assert_eq!(buffer.get(&p3.timestamp).unwrap(), p3);
+    }
+
+    #[test]
+    fn len_memory_usage_and_pop_oldest() {
+        let mut buffer = Buffer::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        assert!(buffer.is_empty());
+        let empty = buffer.memory_usage();
+
+        let p1 = create_transform((t - Duration::from_secs(1)).unwrap());
+        let p2 = create_transform(t);
+        buffer.insert(p1);
+        buffer.insert(p2);
+        buffer.insert(p2);
+        assert_eq!(buffer.len(), 2);
+        assert!(buffer.memory_usage() > empty);
+
+        assert_eq!(buffer.pop_oldest(), Some(p1));
+        assert_eq!(buffer.len(), 1);
+        assert_eq!(buffer.pop_oldest(), Some(p2));
+        assert_eq!(buffer.pop_oldest(), None);
+        assert_eq!(buffer.memory_usage(), empty);
+
+        // A ring holds its capacity whether or not it is full
+        let mut ring = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 4 },
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let full = ring.memory_usage();
+        ring.insert(p1);
+        assert_eq!(ring.len(), 1);
+        assert_eq!(ring.memory_usage(), full);
+    }
+
+    fn timestamps(buffer: &Buffer) -> Vec<Timestamp> {
+        let mut buffer = buffer.clone();
+        std::iter::from_fn(|| buffer.pop_oldest())
+            .map(|tf| tf.timestamp)
+            .collect()
+    }
+
+    #[test]
+    fn decimation_drop() {
+        let t = Timestamp::now();
+        let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+
+        for storage in [Storage::BTree, Storage::Ring { capacity: 16 }] {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                storage,
+                decimation: Some(Decimation {
+                    mode: DecimationMode::Drop,
+                    ..Decimation::interval(Duration::from_millis(10))
+                }),
+                ..BufferConfig::new(Duration::from_secs(10))
+            });
+            for ms in 0..25 {
+                buffer.insert(create_transform(at(ms)));
+            }
+            assert_eq!(timestamps(&buffer), vec![at(0), at(10), at(20)]);
+            assert_eq!(
+                buffer.decimation_stats(),
+                DecimationStats {
+                    dropped: 22,
+                    merged: 0,
+                    compressed: 0,
+                }
+            );
+
+            // Older history and replaced timestamps are not decimated
+            buffer.insert(create_transform(at(5)));
+            buffer.insert(create_transform(at(20)));
+            assert_eq!(timestamps(&buffer), vec![at(0), at(5), at(10), at(20)]);
+            assert_eq!(buffer.decimation_stats().dropped, 22);
+        }
+    }
+
+    #[test]
+    fn decimation_merge() {
+        let t = Timestamp::now();
+        let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+
+        for storage in [Storage::BTree, Storage::Ring { capacity: 16 }] {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                storage,
+                decimation: Some(Decimation {
+                    mode: DecimationMode::Merge,
+                    ..Decimation::interval(Duration::from_millis(10))
+                }),
+                ..BufferConfig::new(Duration::from_secs(10))
+            });
+            for ms in 0..25 {
+                buffer.insert(create_transform(at(ms)));
+            }
+            // The newest transform is always kept, closed intervals are min_interval apart
+            assert_eq!(timestamps(&buffer), vec![at(0), at(10), at(20), at(24)]);
+            assert_eq!(buffer.latest().unwrap().timestamp, at(24));
+            assert_eq!(
+                buffer.decimation_stats(),
+                DecimationStats {
+                    dropped: 0,
+                    merged: 21,
+                    compressed: 0,
+                }
+            );
+        }
+    }
+
+    #[test]
+    fn decimation_keeps_reparenting() {
+        let t = Timestamp::now();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            decimation: Some(Decimation {
+                mode: DecimationMode::Drop,
+                ..Decimation::interval(Duration::from_millis(10))
+            }),
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        buffer.insert(create_transform(t));
+
+        let mut moved = create_transform((t + Duration::from_millis(1)).unwrap());
+        moved.parent = "odom".into();
+        buffer.insert(moved);
+        assert_eq!(buffer.latest(), Some(&moved));
+        assert_eq!(buffer.decimation_stats(), DecimationStats::default());
+    }
+
+    #[test]
+    fn decimation_rate() {
+        assert_eq!(
+            Decimation::rate(200.).min_interval,
+            Duration::from_millis(5)
+        );
+        assert_eq!(Decimation::rate(0.).min_interval, Duration::ZERO);
+        assert_eq!(Decimation::rate(-1.).min_interval, Duration::ZERO);
+
+        // A zero interval keeps every transform
+        let t = Timestamp::now();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            decimation: Some(Decimation::rate(0.)),
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        buffer.insert(create_transform(t));
+        buffer.insert(create_transform((t + Duration::from_nanos(1)).unwrap()));
+        assert_eq!(buffer.len(), 2);
+    }
+
+    /// A transform turning about z and moving along a curve, `ms` milliseconds after `t`.
+    fn curve_at(
+        t: Timestamp,
+        ms: u64,
+    ) -> Transform {
+        let phase = ms as f64 * 1e-3;
+        let mut transform = create_transform((t + Duration::from_millis(ms)).unwrap());
+        transform.translation = Vector3 {
+            x: phase,
+            y: phase.sin(),
+            z: 0.,
+        };
+        transform.rotation = Quaternion {
+            w: (phase / 2.).cos(),
+            x: 0.,
+            y: 0.,
+            z: (phase / 2.).sin(),
+        };
+        transform
+    }
+
+    fn assert_within(
+        approximation: &Transform,
+        transform: &Transform,
+        tolerance: f64,
+    ) {
+        let compression = Compression {
+            translation_tolerance: tolerance,
+            rotation_tolerance: tolerance,
+        };
+        assert!(
+            compression.reproduces(approximation, transform),
+            "{approximation:?} is not within {tolerance} of {transform:?}"
+        );
+    }
+
+    #[test]
+    fn compression_drops_linear_motion() {
+        let t = Timestamp::now();
+        for storage in [Storage::BTree, Storage::Ring { capacity: 256 }] {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                storage,
+                compression: Some(Compression {
+                    translation_tolerance: 1e-9,
+                    rotation_tolerance: 1e-9,
+                }),
+                ..BufferConfig::new(Duration::from_secs(10))
+            });
+            let mut transforms = Vec::new();
+            for ms in 0..200 {
+                let mut transform = create_transform((t + Duration::from_millis(ms)).unwrap());
+                transform.translation.x = ms as f64 * 1e-2;
+                buffer.insert(transform);
+                transforms.push(transform);
+            }
+
+            // One transform is kept per full segment, plus both ends
+            assert_eq!(buffer.len(), 5);
+            assert_eq!(buffer.decimation_stats().compressed, 195);
+            assert_eq!(buffer.latest(), transforms.last());
+            for transform in &transforms {
+                assert_within(&buffer.get(&transform.timestamp).unwrap(), transform, 1e-9);
+            }
+        }
+    }
+
+    #[test]
+    fn compression_stays_within_tolerance() {
+        let t = Timestamp::now();
+        let tolerance = 1e-3;
+        let mut buffer = Buffer::with_config(BufferConfig {
+            compression: Some(Compression {
+                translation_tolerance: tolerance,
+                rotation_tolerance: tolerance,
+            }),
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let mut exact = Buffer::new(Duration::from_secs(10));
+        for ms in 0..2000 {
+            buffer.insert(curve_at(t, ms));
+            exact.insert(curve_at(t, ms));
+        }
+        assert!(buffer.len() < exact.len() / 4);
+
+        // Also between the input transforms, where both buffers interpolate
+        for us in (0..1_999_000).step_by(250) {
+            let timestamp = (t + Duration::from_micros(us)).unwrap();
+            let expected = exact.get(&timestamp).unwrap();
+            assert_within(&buffer.get(&timestamp).unwrap(), &expected, tolerance);
+        }
+    }
+
+    #[test]
+    fn compression_follows_the_interpolation_mode() {
+        let t = Timestamp::now();
+        let tolerance = 1e-3;
+        let ramp: Vec<_> = (0..100)
+            .map(|ms| {
+                let mut transform = create_transform((t + Duration::from_millis(ms)).unwrap());
+                transform.translation.x = ms as f64 * 1e-2;
+                transform
+            })
+            .collect();
+        let hold: Vec<_> = (100..200)
+            .map(|ms| create_transform((t + Duration::from_millis(ms)).unwrap()))
+            .collect();
+
+        for interpolation in [
+            InterpolationMode::Nearest,
+            InterpolationMode::Previous,
+            InterpolationMode::Exact,
+            InterpolationMode::Cubic,
+        ] {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                interpolation,
+                compression: Some(Compression {
+                    translation_tolerance: tolerance,
+                    rotation_tolerance: tolerance,
+                }),
+                ..BufferConfig::new(Duration::from_secs(10))
+            });
+            // A ramp linear interpolation would reproduce is a step for a hold
+            for transform in &ramp {
+                buffer.insert(*transform);
+            }
+            assert_eq!(buffer.decimation_stats().compressed, 0, "{interpolation:?}");
+
+            // Holding still, a hold keeps the step into the run, one transform per full
+            // segment and the newest
+            for transform in &hold {
+                buffer.insert(*transform);
+            }
+            let compressed = match interpolation {
+                InterpolationMode::Nearest | InterpolationMode::Previous => 97,
+                _ => 0,
+            };
+            assert_eq!(
+                buffer.decimation_stats().compressed,
+                compressed,
+                "{interpolation:?}"
+            );
+
+            for transform in ramp.iter().chain(&hold) {
+                assert_within(
+                    &buffer.get(&transform.timestamp).unwrap(),
+                    transform,
+                    tolerance,
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn compression_keeps_out_of_order_history() {
+        let t = Timestamp::now();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            compression: Some(Compression {
+                translation_tolerance: 1.,
+                rotation_tolerance: 1.,
+            }),
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        for ms in [0, 10, 20, 5, 30] {
+            buffer.insert(create_transform((t + Duration::from_millis(ms)).unwrap()));
+        }
+        // 10 is dropped by 20, 5 fills in history, 30 then drops 20
+        assert_eq!(buffer.len(), 3);
+        assert_eq!(buffer.decimation_stats().compressed, 2);
+        assert!(buffer.get(&(t + Duration::from_millis(5)).unwrap()).is_ok());
+    }
+
+    #[test]
+    fn compression_rechecks_out_of_order_inserts() {
+        let t = Timestamp::now();
+        let tolerance = 1e-3;
+        let at = |ms: u64, x: f64| {
+            let mut transform = create_transform((t + Duration::from_millis(ms)).unwrap());
+            transform.translation.x = x;
+            transform
+        };
+        for storage in [Storage::BTree, Storage::Ring { capacity: 256 }] {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                storage,
+                compression: Some(Compression {
+                    translation_tolerance: tolerance,
+                    rotation_tolerance: tolerance,
+                }),
+                ..BufferConfig::new(Duration::from_secs(10))
+            });
+            let transforms: Vec<_> = (0..50).map(|ms| at(ms, ms as f64 * 1e-2)).collect();
+            for transform in &transforms {
+                buffer.insert(*transform);
+            }
+            assert_eq!(buffer.len(), 2);
+
+            // An outlier in the middle of the run would bend interpolation across all of it
+            let outlier = at(25, 10.);
+            buffer.insert(outlier);
+            assert_eq!(buffer.get(&outlier.timestamp).unwrap(), outlier);
+            for transform in transforms
+                .iter()
+                .filter(|tf| tf.timestamp != outlier.timestamp)
+            {
+                assert_within(
+                    &buffer.get(&transform.timestamp).unwrap(),
+                    transform,
+                    tolerance,
+                );
+            }
+            // All but the one the outlier replaced are stored again
+            assert_eq!(buffer.len(), 50);
+            assert_eq!(buffer.decimation_stats().compressed, 1);
+        }
+    }
+
+    #[test]
+    fn compression_bounds_every_dropped_transform() {
+        let t = Timestamp::now();
+        let tolerance = 1e-3;
+        let mut buffer = Buffer::with_config(BufferConfig {
+            compression: Some(Compression {
+                translation_tolerance: tolerance,
+                rotation_tolerance: tolerance,
+            }),
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let mut transforms = Vec::new();
+        // Each new chord stays close to the transform before it, but not to older ones
+        for ms in 0..500 {
+            let mut transform = create_transform((t + Duration::from_millis(ms)).unwrap());
+            transform.translation.y = (ms * ms) as f64 * 1e-5;
+            buffer.insert(transform);
+            transforms.push(transform);
+        }
+
+        assert!(buffer.decimation_stats().compressed > 0);
+        for transform in &transforms {
+            assert_within(
+                &buffer.get(&transform.timestamp).unwrap(),
+                transform,
+                tolerance,
+            );
+        }
+    }
+
+    /// Fills a buffer with one transform every 10 ms over the last ten seconds.
+    fn fill_history(buffer: &mut Buffer) -> Vec<Transform> {
+        let start = (Timestamp::now() - Duration::from_secs(10)).unwrap();
+        let transforms: Vec<_> = (0..1000)
+            .map(|i| {
+                let mut transform =
+                    create_transform((start + Duration::from_millis(i * 10)).unwrap());
+                transform.translation.x = i as f64;
+                transform
+            })
+            .collect();
+        for transform in &transforms {
+            buffer.insert(*transform);
+        }
+        transforms
+    }
+
+    #[test]
+    fn archive_serves_both_tiers() {
+        for storage in [Storage::BTree, Storage::Ring { capacity: 128 }] {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                storage,
+                archive: Some(ArchivePolicy {
+                    decimation: Some(Decimation::interval(Duration::from_millis(100))),
+                    ..ArchivePolicy::new(Duration::from_secs(1))
+                }),
+                ..BufferConfig::new(Duration::from_secs(60))
+            });
+            let transforms = fill_history(&mut buffer);
+            let first = transforms[0];
+            let last = transforms[999];
+
+            // About a second at full rate, the nine before it at a tenth of the rate
+            assert!(buffer.len() < 250, "{} transforms kept", buffer.len());
+            assert!(buffer.decimation_stats().dropped > 750);
+            assert_eq!(buffer.time_range(), Some((first.timestamp, last.timestamp)));
+            assert_eq!(buffer.latest(), Some(&last));
+
+            // Archived transforms are found as stored and interpolated between
+            assert_eq!(buffer.get(&first.timestamp).unwrap(), first);
+            let between = (first.timestamp + Duration::from_millis(50)).unwrap();
+            assert_abs_diff_eq!(buffer.get(&between).unwrap().translation.x, 5.);
+
+            // Every timestamp up to the newest one resolves, across the boundary too
+            for transform in &transforms {
+                let r = buffer.get(&transform.timestamp).unwrap();
+                assert_abs_diff_eq!(r.translation.x, transform.translation.x, epsilon = 1e-6);
+            }
+
+            assert_eq!(buffer.pop_oldest(), Some(first));
+        }
+    }
+
+    #[test]
+    fn archive_takes_old_and_overflowing_transforms() {
+        // A ring too small for the hot window ages its oldest transforms early
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 4 },
+            archive: Some(ArchivePolicy {
+                decimation: Some(Decimation::interval(Duration::from_millis(100))),
+                ..ArchivePolicy::new(Duration::from_secs(1))
+            }),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        let t = Timestamp::now();
+        for ms in [0, 10, 20, 30, 40, 150] {
+            buffer.insert(create_transform((t + Duration::from_millis(ms)).unwrap()));
+        }
+        assert_eq!(buffer.len(), 5);
+        assert_eq!(buffer.decimation_stats().dropped, 1);
+
+        // History older than the hot window goes straight to the archive
+        let old = create_transform((t - Duration::from_secs(5)).unwrap());
+        buffer.insert(old);
+        assert_eq!(buffer.time_range().unwrap().0, old.timestamp);
+        assert_eq!(buffer.get(&old.timestamp).unwrap(), old);
+    }
+
+    #[test]
+    fn late_transforms_inside_the_hot_window_stay_hot() {
+        for storage in [Storage::BTree, Storage::Ring { capacity: 16 }] {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                storage,
+                archive: Some(ArchivePolicy {
+                    decimation: Some(Decimation::interval(Duration::from_millis(100))),
+                    ..ArchivePolicy::new(Duration::from_secs(1))
+                }),
+                ..BufferConfig::new(Duration::from_secs(60))
+            });
+            let t = Timestamp::now();
+            buffer.insert(create_transform(t));
+
+            // Older than every hot transform, but well inside the window behind the newest
+            let late: Vec<_> = [300, 250]
+                .into_iter()
+                .map(|ms| {
+                    let mut transform = create_transform((t - Duration::from_millis(ms)).unwrap());
+                    transform.translation.x = ms as f64;
+                    buffer.insert(transform);
+                    transform
+                })
+                .collect();
+
+            // Kept at full rate and as stored, rather than thinned out by the archive
+            assert_eq!(buffer.len(), 3);
+            assert_eq!(buffer.decimation_stats().dropped, 0);
+            for transform in &late {
+                assert_eq!(buffer.get(&transform.timestamp).unwrap(), *transform);
+            }
+        }
+    }
+
+    #[test]
+    fn archive_counts_towards_growth() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            storage: Storage::Ring { capacity: 2 },
+            archive: Some(ArchivePolicy::new(Duration::from_secs(1))),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        let t = Timestamp::now();
+        let old = create_transform((t - Duration::from_secs(5)).unwrap());
+        buffer.insert(create_transform(t));
+        buffer.insert(old);
+
+        // The ring allocates up front, but the archive grows with history and aging
+        let older = create_transform((t - Duration::from_secs(6)).unwrap());
+        assert!(buffer.adds_sample(&older));
+        assert!(buffer.growth(&older) > 0);
+        assert!(!buffer.adds_sample(&old));
+        assert_eq!(buffer.growth(&old), 0);
+
+        let next = create_transform((t + Duration::from_millis(10)).unwrap());
+        assert_eq!(buffer.growth(&next), 0);
+        buffer.insert(next);
+        let overflow = create_transform((t + Duration::from_millis(20)).unwrap());
+        let before = buffer.memory_usage();
+        let growth = buffer.growth(&overflow);
+        buffer.insert(overflow);
+        assert!(growth > 0);
+        assert_eq!(buffer.memory_usage(), before + growth);
+    }
+
+    #[test]
+    fn archive_reconfigure() {
+        let mut buffer = Buffer::new(Duration::from_secs(60));
+        let transforms = fill_history(&mut buffer);
+        let full = buffer.memory_usage();
+        assert_eq!(buffer.len(), 1000);
+
+        buffer.reconfigure(
+            Buffer::with_config(BufferConfig {
+                archive: Some(ArchivePolicy {
+                    decimation: Some(Decimation::interval(Duration::from_millis(100))),
+                    ..ArchivePolicy::new(Duration::from_secs(1))
+                }),
+                ..BufferConfig::new(Duration::from_secs(60))
+            })
+            .config(),
+        );
+        assert!(buffer.len() < 250);
+        assert!(buffer.memory_usage() < full);
+        assert_eq!(buffer.get(&transforms[0].timestamp).unwrap(), transforms[0]);
+
+        buffer.reconfigure(BufferConfig::new(Duration::from_secs(60)));
+        assert!(buffer.len() < 250);
+        assert_eq!(buffer.get(&transforms[0].timestamp).unwrap(), transforms[0]);
+    }
+
+    /// Inserts x = 0 at `t` and x = 10 one second later.
+    fn insert_pair(
+        buffer: &mut Buffer,
+        t: Timestamp,
+    ) {
+        let mut transform = create_transform(t);
+        transform.translation.x = 0.;
+        buffer.insert(transform);
+        transform.timestamp = (t + Duration::from_secs(1)).unwrap();
+        transform.translation.x = 10.;
+        buffer.insert(transform);
+    }
+
+    #[test]
+    fn interpolation_modes() {
+        let t = Timestamp::now();
+        let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            interpolation: InterpolationMode::Linear,
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        insert_pair(&mut buffer, t);
+        let x = |ms, mode| {
+            buffer
+                .get_with(&at(ms), mode)
+                .map(|tf| tf.translation.x)
+                .ok()
+        };
+
+        // At stored transforms, every mode returns them as stored
+        for mode in [
+            InterpolationMode::Linear,
+            InterpolationMode::Nearest,
+            InterpolationMode::Previous,
+            InterpolationMode::Exact,
+        ] {
+            assert_eq!(x(0, mode), Some(0.));
+            assert_eq!(x(1000, mode), Some(10.));
+            assert_eq!(
+                buffer.get_with(&at(1000), mode).unwrap().timestamp,
+                at(1000)
+            );
+
+            // Outside the buffer, no mode answers
+            assert!(x(1001, mode).is_none());
+            let before = (t - Duration::from_millis(1)).unwrap();
+            assert!(buffer.get_with(&before, mode).is_err());
+        }
+
+        // Between them, each mode answers its own way
+        assert_abs_diff_eq!(x(700, InterpolationMode::Linear).unwrap(), 7.);
+        assert_eq!(x(400, InterpolationMode::Nearest), Some(0.));
+        assert_eq!(x(500, InterpolationMode::Nearest), Some(0.));
+        assert_eq!(x(600, InterpolationMode::Nearest), Some(10.));
+        assert_eq!(x(999, InterpolationMode::Previous), Some(0.));
+        assert_eq!(
+            buffer
+                .get_with(&at(999), InterpolationMode::Previous)
+                .unwrap()
+                .timestamp,
+            t
+        );
+        assert!(x(500, InterpolationMode::Exact).is_none());
+    }
+
+    #[test]
+    fn held_transforms_are_traced() {
+        let t = Timestamp::now();
+        let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+        let mut buffer = Buffer::new(Duration::from_secs(10));
+        insert_pair(&mut buffer, t);
+
+        // Holding a transform is neither interpolating nor extrapolating past the buffer
+        for (mode, held) in [
+            (InterpolationMode::Nearest, at(1000)),
+            (InterpolationMode::Previous, t),
+        ] {
+            let (transform, link) = buffer.trace(&at(700), Some(mode)).unwrap();
+            assert_eq!(link.source, LinkSource::Held);
+            assert_eq!(link.samples, vec![held]);
+            assert_eq!(transform.timestamp, at(700));
+        }
+        let (_, link) = buffer.trace(&at(700), None).unwrap();
+        assert_eq!(link.source, LinkSource::Interpolated);
+        assert_eq!(link.staleness, Duration::from_millis(300));
+    }
+
+    #[test]
+    fn interpolation_mode_configured() {
+        let t = Timestamp::now();
+        let between = (t + Duration::from_millis(700)).unwrap();
+
+        let mut buffer = Buffer::with_config(BufferConfig {
+            interpolation: InterpolationMode::Previous,
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        insert_pair(&mut buffer, t);
+        assert_eq!(buffer.get(&between).unwrap().translation.x, 0.);
+        // A query can still choose another mode
+        let r = buffer.get_with(&between, InterpolationMode::Linear);
+        assert_abs_diff_eq!(r.unwrap().translation.x, 7.);
+
+        let mut buffer = Buffer::with_config(BufferConfig {
+            interpolation: InterpolationMode::Exact,
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        insert_pair(&mut buffer, t);
+        assert!(buffer.get(&between).is_err());
+
+        // Static transforms hold at any time, whatever the mode
+        let mut buffer = Buffer::with_config(BufferConfig {
+            interpolation: InterpolationMode::Exact,
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        buffer.insert(create_transform(Timestamp::zero()));
+        assert!(buffer.get(&between).is_ok());
+    }
+
+    #[test]
+    fn max_interpolation_gap() {
+        let t = Timestamp::now();
+        let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            max_interpolation_gap: Some(Duration::from_secs(1)),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+
+        // A sensor dropout of five seconds after the second transform
+        for ms in [0, 100, 5100] {
+            let mut transform = create_transform(at(ms));
+            transform.translation.x = ms as f64;
+            buffer.insert(transform);
+        }
+
+        assert_abs_diff_eq!(buffer.get(&at(50)).unwrap().translation.x, 50.);
+        assert!(matches!(
+            buffer.get(&at(2000)),
+            Err(BufferError::InterpolationGap(gap, max))
+                if gap == Duration::from_secs(5) && max == Duration::from_secs(1)
+        ));
+        for mode in [
+            InterpolationMode::Nearest,
+            InterpolationMode::Previous,
+            InterpolationMode::Cubic,
+        ] {
+            assert!(matches!(
+                buffer.get_with(&at(150), mode),
+                Err(BufferError::InterpolationGap(_, _))
+            ));
+        }
+        assert!(matches!(
+            buffer.get_with(&at(150), InterpolationMode::Exact),
+            Err(BufferError::NoTransformAvailable)
+        ));
+
+        // Transforms on either side of the gap are still served
+        assert_abs_diff_eq!(buffer.get(&at(100)).unwrap().translation.x, 100.);
+        assert_abs_diff_eq!(buffer.get(&at(5100)).unwrap().translation.x, 5100.);
+
+        // A gap of exactly the limit is answered across
+        let mut transform = create_transform(at(6100));
+        transform.translation.x = 6100.;
+        buffer.insert(transform);
+        assert_abs_diff_eq!(buffer.get(&at(5600)).unwrap().translation.x, 5600.);
+    }
+
+    #[test]
+    fn cubic_interpolation_is_smooth() {
+        let t = Timestamp::now();
+        let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            interpolation: InterpolationMode::Cubic,
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+
+        // x = s² and a yaw of s/2 radians, sampled every second
+        for s in 0..5_u64 {
+            let mut transform = create_transform(at(s * 1000));
+            let s = s as f64;
+            transform.translation.x = s * s;
+            transform.rotation = Quaternion {
+                w: (s / 4.).cos(),
+                x: 0.,
+                y: 0.,
+                z: (s / 4.).sin(),
+            };
+            buffer.insert(transform);
+        }
+        let x = |ms| buffer.get(&at(ms)).unwrap().translation.x;
+
+        // Between interior samples, the quadratic is reproduced, where linear cuts the corner
+        assert_abs_diff_eq!(x(1500), 2.25, epsilon = 1e-6);
+        assert_abs_diff_eq!(x(2500), 6.25, epsilon = 1e-6);
+        let linear = buffer
+            .get_with(&at(1500), InterpolationMode::Linear)
+            .unwrap();
+        assert_abs_diff_eq!(linear.translation.x, 2.5, epsilon = 1e-6);
+
+        // The velocity is continuous across a stored sample
+        let left = (x(2000) - x(1999)) / 0.001;
+        let right = (x(2001) - x(2000)) / 0.001;
+        assert_abs_diff_eq!(left, right, epsilon = 0.01);
+
+        // Constant angular velocity is followed exactly
+        let rotation = buffer.get(&at(2250)).unwrap().rotation;
+        assert_abs_diff_eq!(rotation.z, (2.25_f64 / 4.).sin(), epsilon = 1e-9);
+
+        // Stored samples are returned as stored, and the ends fall back to a one-sided
+        // tangent rather than failing
+        assert_eq!(x(3000), 9.);
+        assert!(x(500) > 0. && x(500) < 1.);
+        assert!(x(3500) > 9. && x(3500) < 16.);
+        assert!(buffer.get(&at(4001)).is_err());
+
+        // Two samples alone interpolate linearly
+        let mut pair = Buffer::with_config(BufferConfig {
+            interpolation: InterpolationMode::Cubic,
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        insert_pair(&mut pair, t);
+        assert_abs_diff_eq!(
+            pair.get(&at(300)).unwrap().translation.x,
+            3.,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn cubic_interpolation_at_the_end_of_time() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            interpolation: InterpolationMode::Cubic,
+            ..BufferConfig::new(Duration::from_secs(10))
+        });
+        let end = Timestamp {
+            nanoseconds: u128::MAX,
+        };
+        insert_pair(&mut buffer, (end - Duration::from_secs(1)).unwrap());
+
+        // No neighbour past the newest transform, which the clock cannot step beyond
+        let between = (end - Duration::from_millis(700)).unwrap();
+        assert_abs_diff_eq!(
+            buffer.get(&between).unwrap().translation.x,
+            3.,
+            epsilon = 1e-6
+        );
+    }
+
+    #[test]
+    fn motion_limits_reject_velocity_jumps() {
+        let t = Timestamp::now();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            motion_limits: Some(MotionLimits {
+                max_linear_velocity: Some(2.),
+                max_angular_velocity: Some(1.5),
+                ..MotionLimits::default()
+            }),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+
+        // The curve moves at up to 1.5 m/s and turns at 1 rad/s
+        assert_eq!(buffer.try_insert(curve_at(t, 0)).unwrap(), None);
+        assert_eq!(buffer.try_insert(curve_at(t, 1000)).unwrap(), None);
+
+        // 5 m/s more along x
+        let mut jump = curve_at(t, 2000);
+        jump.translation.x += 5.;
+        assert!(matches!(
+            buffer.try_insert(jump),
+            Err(BufferError::MotionAnomaly(anomaly))
+                if anomaly.quantity == MotionQuantity::LinearVelocity
+                    && anomaly.value > 6.
+                    && anomaly.limit == 2.
+        ));
+        assert_eq!(buffer.len(), 2);
+
+        // 2 rad/s, from a yaw of 1 rad to 3 rad
+        let mut spin = curve_at(t, 2000);
+        spin.rotation = Quaternion {
+            w: 1.5_f64.cos(),
+            x: 0.,
+            y: 0.,
+            z: 1.5_f64.sin(),
+        };
+        assert!(matches!(
+            buffer.try_insert(spin),
+            Err(BufferError::MotionAnomaly(anomaly))
+                if anomaly.quantity == MotionQuantity::AngularVelocity
+                    && (anomaly.value - 2.).abs() < 1e-9
+        ));
+
+        // Across the double cover, the curve turns at 1 rad/s still
+        let mut negated = curve_at(t, 2000);
+        negated.rotation = negated.rotation.scale(-1.);
+        assert_eq!(buffer.try_insert(negated).unwrap(), None);
+
+        // Plain inserts drop rejected transforms
+        let mut jump = curve_at(t, 3000);
+        jump.translation.x += 100.;
+        buffer.insert(jump);
+        assert_eq!(buffer.len(), 3);
+    }
+
+    #[test]
+    fn motion_limits_check_acceleration() {
+        let t = Timestamp::now();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            motion_limits: Some(MotionLimits {
+                max_linear_acceleration: Some(1.),
+                max_angular_acceleration: Some(1.),
+                ..MotionLimits::default()
+            }),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+
+        // The curve accelerates by at most 1 m/s² and turns at a constant rate
+        for ms in [0, 100, 200] {
+            assert_eq!(buffer.try_insert(curve_at(t, ms)).unwrap(), None);
+        }
+        assert_eq!(buffer.check_motion(&curve_at(t, 300)), None);
+
+        // 2 m/s faster along x over 0.1 s
+        let mut pushed = curve_at(t, 300);
+        pushed.translation.x += 0.2;
+        let anomaly = buffer.check_motion(&pushed).unwrap();
+        assert_eq!(anomaly.quantity, MotionQuantity::LinearAcceleration);
+        assert_abs_diff_eq!(anomaly.value, 20., epsilon = 1e-2);
+
+        // From 1 rad/s to 3 rad/s over 0.1 s
+        let mut spun = curve_at(t, 300);
+        spun.rotation = Quaternion {
+            w: 0.25_f64.cos(),
+            x: 0.,
+            y: 0.,
+            z: 0.25_f64.sin(),
+        };
+        let anomaly = buffer.check_motion(&spun).unwrap();
+        assert_eq!(anomaly.quantity, MotionQuantity::AngularAcceleration);
+        assert_abs_diff_eq!(anomaly.value, 20., epsilon = 1e-6);
+    }
+
+    #[test]
+    fn motion_limits_flag_and_skip() {
+        let t = Timestamp::now();
+        let limits = MotionLimits {
+            max_linear_velocity: Some(2.),
+            ..MotionLimits::default()
+        };
+        let mut buffer = Buffer::with_config(BufferConfig {
+            motion_limits: Some(MotionLimits {
+                action: AnomalyAction::Flag,
+                ..limits
+            }),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        let off_curve = |ms, dx| {
+            let mut transform = curve_at(t, ms);
+            transform.translation.x += dx;
+            transform
+        };
+
+        buffer.insert(curve_at(t, 0));
+        let flagged = buffer.try_insert(off_curve(1000, 10.)).unwrap();
+        assert_eq!(
+            flagged.map(|anomaly| anomaly.quantity),
+            Some(MotionQuantity::LinearVelocity)
+        );
+        assert_eq!(buffer.len(), 2);
+
+        // Out-of-order transforms are checked against the transform before them
+        assert!(buffer.check_motion(&curve_at(t, 500)).is_none());
+        assert!(buffer.check_motion(&off_curve(500, 8.)).is_some());
+
+        // A transform replacing a stored one is checked against the one before it
+        assert!(buffer.check_motion(&curve_at(t, 1000)).is_none());
+
+        // Transforms that change the parent are not checked
+        let mut reparented = off_curve(2000, 50.);
+        reparented.parent = "odom".into();
+        assert!(buffer.check_motion(&reparented).is_none());
+
+        // Static transforms are not checked
+        let mut fixed = Buffer::with_config(BufferConfig {
+            motion_limits: Some(limits),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        fixed.insert(create_transform(Timestamp::zero()));
+        let mut moved = create_transform(Timestamp::zero());
+        moved.translation.x = 100.;
+        assert_eq!(fixed.try_insert(moved).unwrap(), None);
+    }
+
+    #[test]
+    fn duplicate_policies() {
+        let t = Timestamp::now();
+        let first = create_transform(t);
+        let mut second = first;
+        second.translation.x = 2.;
+        let stored = |duplicates| {
+            let mut buffer = Buffer::with_config(BufferConfig {
+                duplicates,
+                ..BufferConfig::new(Duration::from_secs(60))
+            });
+            buffer.insert(first);
+            let result = buffer.try_insert(second);
+            let x = buffer.get(&first.timestamp).unwrap().translation.x;
+            (result, x, buffer.rejection_stats().duplicates)
+        };
+
+        let (result, x, rejected) = stored(DuplicatePolicy::Replace);
+        assert!(result.is_ok());
+        assert_eq!((x, rejected), (2., 0));
+
+        let (result, x, rejected) = stored(DuplicatePolicy::KeepFirst);
+        assert!(result.is_ok());
+        assert_eq!((x, rejected), (1., 1));
+
+        let (result, x, rejected) = stored(DuplicatePolicy::Reject);
+        assert!(matches!(
+            result,
+            Err(BufferError::DuplicateTimestamp(_, _, timestamp)) if timestamp == first.timestamp
+        ));
+        assert_eq!((x, rejected), (1., 1));
+
+        // Static transforms always replace
+        let mut buffer = Buffer::with_config(BufferConfig {
+            duplicates: DuplicatePolicy::Reject,
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        let mut fixed = create_transform(Timestamp::zero());
+        buffer.try_insert(fixed).unwrap();
+        fixed.translation.x = 5.;
+        buffer.try_insert(fixed).unwrap();
+        assert_eq!(buffer.latest().unwrap().translation.x, 5.);
+    }
+
+    #[test]
+    fn duplicates_in_the_archive() {
+        let mut buffer = Buffer::with_config(BufferConfig {
+            duplicates: DuplicatePolicy::Reject,
+            archive: Some(ArchivePolicy::new(Duration::from_secs(5))),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        let old = (Timestamp::now() - Duration::from_secs(30)).unwrap();
+        buffer.insert(create_transform(old));
+        buffer.insert(create_transform(Timestamp::now()));
+
+        assert!(matches!(
+            buffer.try_insert(create_transform(old)),
+            Err(BufferError::DuplicateTimestamp(..))
+        ));
+    }
+
+    #[test]
+    fn max_lateness() {
+        let t = Timestamp::now();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            max_lateness: Some(Duration::from_millis(500)),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        buffer.insert(curve_at(t, 1000));
+
+        // Within the window, late transforms fill in the history
+        buffer.try_insert(curve_at(t, 500)).unwrap();
+        assert_eq!(buffer.len(), 2);
+
+        let late = buffer.try_insert(curve_at(t, 499));
+        assert!(matches!(
+            late,
+            Err(BufferError::LateTransform(_, _, _, window))
+                if window == Duration::from_millis(500)
+        ));
+        assert_eq!(buffer.len(), 2);
+
+        // The window follows the newest transform
+        buffer.insert(curve_at(t, 2000));
+        assert!(buffer.try_insert(curve_at(t, 1400)).is_err());
+        assert_eq!(
+            buffer.rejection_stats(),
+            RejectionStats {
+                late: 2,
+                duplicates: 0,
+                anomalies: 0,
+            }
+        );
+    }
+
+    #[test]
+    fn rejection_stats_count_anomalies() {
+        let t = Timestamp::now();
+        let mut buffer = Buffer::with_config(BufferConfig {
+            motion_limits: Some(MotionLimits {
+                max_linear_velocity: Some(2.),
+                ..MotionLimits::default()
+            }),
+            ..BufferConfig::new(Duration::from_secs(60))
+        });
+        buffer.insert(curve_at(t, 0));
+        let mut jump = curve_at(t, 1000);
+        jump.translation.x += 10.;
+        buffer.insert(jump);
+        buffer.insert(curve_at(t, 1000));
+        assert_eq!(buffer.rejection_stats().anomalies, 1);
+        assert_eq!(buffer.len(), 2);
+    }
 }
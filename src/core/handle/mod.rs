@@ -0,0 +1,199 @@
+//! Precompiled lookups between a fixed pair of frames.
+//!
+//! This module provides the `LookupHandle` struct, which resolves the path between two
+//! frames once and then evaluates it at any number of timestamps. The handle remembers
+//! the topology the path was resolved against and only resolves it again once a frame in
+//! the registry changes parent, so repeated lookups skip the walk through the frame tree.
+//!
+//! Handles read from the snapshots a registry publishes, just like a `RegistryReader`,
+//! and never wait on the registry lock.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use std::time::Duration;
+//! use transforms::{
+//!     geometry::{Quaternion, Transform, Vector3},
+//!     time::Timestamp,
+//!     Registry,
+//! };
+//!
+//! let mut registry = Registry::new(Duration::from_secs(60));
+//! let mut handle = registry.lookup_handle("base", "camera");
+//!
+//! let timestamp = Timestamp::now();
+//! let transform = Transform {
+//!     translation: Vector3::new(1.0, 0.0, 0.0),
+//!     rotation: Quaternion::identity(),
+//!     timestamp,
+//!     parent: "base".into(),
+//!     child: "camera".into(),
+//! };
+//! registry.add_transform(transform).unwrap();
+//!
+//! assert!(handle.at(timestamp).is_ok());
+//! assert_eq!(handle.latest().unwrap().timestamp, timestamp);
+//! # }
+//! ```
+
+use crate::{
+    core::registry::{FramePath, FrameTree},
+    errors::TransformError,
+    frame::FrameId,
+    geometry::Transform,
+    time::Timestamp,
+    Registry,
+};
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+
+/// A lookup between a fixed pair of frames with a cached path.
+///
+/// Handles are obtained from `Registry::lookup_handle` or `RegistryReader::lookup_handle`.
+/// Each handle keeps its own resolved path, so lookups take `&mut self`; clone the handle
+/// to use it from several threads.
+#[derive(Clone)]
+pub struct LookupHandle {
+    published: Arc<ArcSwap<FrameTree>>,
+    from: FrameId,
+    to: FrameId,
+    path: Option<FramePath>,
+    generation: Option<u64>,
+}
+
+impl LookupHandle {
+    pub(crate) fn new(
+        published: Arc<ArcSwap<FrameTree>>,
+        from: FrameId,
+        to: FrameId,
+    ) -> Self {
+        Self {
+            published,
+            from,
+            to,
+            path: None,
+            generation: None,
+        }
+    }
+
+    /// Returns the source frame of the handle.
+    pub fn from(&self) -> FrameId {
+        self.from
+    }
+
+    /// Returns the destination frame of the handle.
+    pub fn to(&self) -> FrameId {
+        self.to
+    }
+
+    /// Retrieves the transform between the frames at the specified timestamp.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamp` - The timestamp for which the transform is requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the transform cannot be found.
+    pub fn at(
+        &mut self,
+        timestamp: Timestamp,
+    ) -> Result<Transform, TransformError> {
+        let data = self.published.load();
+        self.refresh(&data);
+        self.evaluate(timestamp, &data)
+    }
+
+    /// Retrieves the transform between the frames at the latest time every link between
+    /// them is known.
+    ///
+    /// Static links do not limit that time. If every link is static, the static transform
+    /// is returned.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the frames are not connected or the transform cannot
+    /// be found.
+    pub fn latest(&mut self) -> Result<Transform, TransformError> {
+        let data = self.published.load();
+        self.refresh(&data);
+
+        let timestamp = self
+            .path
+            .as_ref()
+            .and_then(|path| Self::latest_common_time(path, &data))
+            .ok_or(TransformError::NotFound(self.from, self.to))?;
+        self.evaluate(timestamp, &data)
+    }
+
+    /// Retrieves the transforms between the frames at each of the specified timestamps.
+    ///
+    /// All timestamps are evaluated against the same published snapshot, so the results
+    /// are consistent with each other even while the registry is being written to.
+    ///
+    /// # Arguments
+    ///
+    /// * `timestamps` - The timestamps for which transforms are requested.
+    ///
+    /// # Returns
+    ///
+    /// One result per timestamp, in the same order.
+    pub fn batch(
+        &mut self,
+        timestamps: &[Timestamp],
+    ) -> Vec<Result<Transform, TransformError>> {
+        let data = self.published.load();
+        self.refresh(&data);
+        timestamps
+            .iter()
+            .map(|timestamp| self.evaluate(*timestamp, &data))
+            .collect()
+    }
+
+    /// Resolves the path again if the topology changed since it was last resolved.
+    fn refresh(
+        &mut self,
+        data: &FrameTree,
+    ) {
+        if self.generation != Some(data.generation()) {
+            self.path = FramePath::resolve(self.from, self.to, data);
+            self.generation = Some(data.generation());
+        }
+    }
+
+    fn evaluate(
+        &self,
+        timestamp: Timestamp,
+        data: &FrameTree,
+    ) -> Result<Transform, TransformError> {
+        Registry::process_get_transform_along(
+            self.from,
+            self.to,
+            self.path.as_ref(),
+            timestamp,
+            None,
+            data,
+        )
+    }
+
+    /// Returns the newest timestamp at which every dynamic link along `path` is known.
+    fn latest_common_time(
+        path: &FramePath,
+        data: &FrameTree,
+    ) -> Option<Timestamp> {
+        let mut latest = Timestamp::zero();
+        for frame in path.from_links.iter().chain(&path.to_links) {
+            let timestamp = data.buffer(*frame)?.latest()?.timestamp;
+            if timestamp != Timestamp::zero() && (latest == Timestamp::zero() || timestamp < latest)
+            {
+                latest = timestamp;
+            }
+        }
+        Some(latest)
+    }
+}
+
+#[cfg(test)]
+mod tests;
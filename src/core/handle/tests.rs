@@ -0,0 +1,139 @@
+#[cfg(not(feature = "async"))]
+#[cfg(test)]
+mod handle_tests {
+    use crate::{
+        geometry::{Quaternion, Transform, Vector3},
+        time::Timestamp,
+        Registry,
+    };
+    use std::time::Duration;
+
+    fn create_transform(
+        parent: &str,
+        child: &str,
+        x: f64,
+        timestamp: Timestamp,
+    ) -> Transform {
+        Transform {
+            translation: Vector3 { x, y: 0., z: 0. },
+            rotation: Quaternion::identity(),
+            timestamp,
+            parent: parent.into(),
+            child: child.into(),
+        }
+    }
+
+    #[test]
+    fn handle_at_and_batch() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let mut handle = registry.lookup_handle("c", "a");
+        let t1 = Timestamp::now();
+        let t2 = (t1 + Duration::from_secs(2)).unwrap();
+        let t_mid = (t1 + Duration::from_secs(1)).unwrap();
+
+        for (t, x) in [(t1, 1.), (t2, 3.)] {
+            registry
+                .add_transform(create_transform("a", "b", x, t))
+                .unwrap();
+            registry
+                .add_transform(create_transform("b", "c", 1., t))
+                .unwrap();
+        }
+
+        let r = handle.at(t_mid).unwrap();
+        assert_eq!(r.translation, Vector3::new(-3., 0., 0.));
+        assert_eq!(r.parent, "c");
+        assert_eq!(r.child, "a");
+
+        let results = handle.batch(&[t1, t_mid, t2, (t2 + Duration::from_secs(1)).unwrap()]);
+        assert_eq!(results.len(), 4);
+        assert_eq!(results[0].as_ref().unwrap().translation.x, -2.);
+        assert_eq!(results[1].as_ref().unwrap().translation.x, -3.);
+        assert_eq!(results[2].as_ref().unwrap().translation.x, -4.);
+        assert!(results[3].is_err());
+    }
+
+    #[test]
+    fn handle_latest_uses_common_time() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let mut handle = registry.lookup_handle("a", "c");
+        assert!(handle.latest().is_err());
+
+        let t1 = Timestamp::now();
+        let t2 = (t1 + Duration::from_secs(1)).unwrap();
+        let t3 = (t1 + Duration::from_secs(2)).unwrap();
+
+        for (t, x) in [(t1, 1.), (t2, 2.), (t3, 3.)] {
+            registry
+                .add_transform(create_transform("a", "b", x, t))
+                .unwrap();
+        }
+        for t in [t1, t2] {
+            registry
+                .add_transform(create_transform("b", "c", 1., t))
+                .unwrap();
+        }
+
+        // B to C is only known up to t2
+        let r = handle.latest().unwrap();
+        assert_eq!(r.timestamp, t2);
+        assert_eq!(r.translation, Vector3::new(3., 0., 0.));
+    }
+
+    #[test]
+    fn handle_resolves_only_on_topology_change() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let mut handle = registry.lookup_handle("c", "a");
+        let t1 = Timestamp::now();
+        let t2 = (t1 + Duration::from_secs(1)).unwrap();
+
+        registry
+            .add_transform(create_transform("a", "b", 1., t1))
+            .unwrap();
+        registry
+            .add_transform(create_transform("b", "c", 1., t1))
+            .unwrap();
+
+        handle.at(t1).unwrap();
+        let generation = handle.generation;
+
+        // New samples on the same links keep the path
+        registry
+            .add_transform(create_transform("a", "b", 1., t2))
+            .unwrap();
+        registry
+            .add_transform(create_transform("b", "c", 1., t2))
+            .unwrap();
+        handle.at(t2).unwrap();
+        assert_eq!(handle.generation, generation);
+
+        // Re-parenting C onto A resolves it again
+        let t3 = (t1 + Duration::from_secs(2)).unwrap();
+        registry
+            .add_transform(create_transform("a", "c", 5., t3))
+            .unwrap();
+        let r = handle.at(t3).unwrap();
+        assert_ne!(handle.generation, generation);
+        assert_eq!(r.translation, Vector3::new(-5., 0., 0.));
+        assert_eq!(handle.path.as_ref().unwrap().from_links, vec!["c"]);
+
+        // Lookups in the past still follow the tree as it was
+        let r = handle.at(t1).unwrap();
+        assert_eq!(r.translation, Vector3::new(-2., 0., 0.));
+    }
+
+    #[test]
+    fn handle_from_reader() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let reader = registry.reader();
+        let t = Timestamp::now();
+        registry
+            .add_transform(create_transform("a", "b", 1., t))
+            .unwrap();
+
+        let mut handle = reader.lookup_handle("a", "b");
+        assert_eq!(handle.from(), "a");
+        assert_eq!(handle.to(), "b");
+        assert_eq!(handle.at(t).unwrap().translation, Vector3::new(1., 0., 0.));
+    }
+}
@@ -0,0 +1,88 @@
+//! Lookups that report how their transform was obtained.
+//!
+//! This module provides the `LookupResult` struct returned by `Registry::lookup`, which holds
+//! the transform between two frames together with the frames it passed through and, for
+//! every link along the way, the stored transforms it was derived from and how.
+
+use crate::{frame::FrameId, geometry::Transform, time::Timestamp};
+use std::time::Duration;
+
+/// How the transform of a single link was obtained from its buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LinkSource {
+    /// A transform stored at exactly the requested timestamp.
+    Exact,
+    /// Interpolated between stored transforms around the requested timestamp.
+    Interpolated,
+    /// A stored transform held over to the requested timestamp between stored transforms,
+    /// as `InterpolationMode::Nearest` and `InterpolationMode::Previous` do.
+    Held,
+    /// A static transform, which holds at any time.
+    Static,
+}
+
+/// How the transform of a single link along a lookup was obtained.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LinkLookup {
+    /// The parent frame of the link.
+    pub parent: FrameId,
+    /// The child frame of the link.
+    pub child: FrameId,
+    /// How the transform of the link was obtained.
+    pub source: LinkSource,
+    /// The timestamps of the stored transforms the link was derived from, oldest first.
+    /// Empty for static links.
+    pub samples: Vec<Timestamp>,
+    /// How far the requested timestamp is from the closest stored transform used.
+    pub staleness: Duration,
+}
+
+/// The transform between two frames, with the provenance of every link it combines.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(not(feature = "async"))]
+/// # {
+/// use std::time::Duration;
+/// use transforms::{
+///     core::LinkSource,
+///     geometry::Transform,
+///     time::Timestamp,
+///     Registry,
+/// };
+///
+/// let mut registry = Registry::new(Duration::from_secs(60));
+/// let t = Timestamp::now();
+///
+/// let mut transform = Transform::identity();
+/// transform.parent = "base".into();
+/// transform.child = "arm".into();
+/// registry.add_transform(transform).unwrap();
+///
+/// transform.parent = "arm".into();
+/// transform.child = "gripper".into();
+/// transform.timestamp = t;
+/// registry.add_transform(transform).unwrap();
+/// transform.timestamp = (t + Duration::from_secs(1)).unwrap();
+/// registry.add_transform(transform).unwrap();
+///
+/// let between = (t + Duration::from_millis(200)).unwrap();
+/// let result = registry.lookup("base", "gripper", between).unwrap();
+/// assert_eq!(result.frames, vec!["base", "arm", "gripper"]);
+/// assert_eq!(result.links[0].source, LinkSource::Static);
+/// assert_eq!(result.links[1].source, LinkSource::Interpolated);
+/// assert_eq!(result.max_staleness, Duration::from_millis(200));
+/// # }
+/// ```
+#[derive(Debug, Clone, PartialEq)]
+pub struct LookupResult {
+    /// The transform between the two frames, as `get_transform` returns it.
+    pub transform: Transform,
+    /// The frames the lookup passed through, from the source frame to the target frame.
+    pub frames: Vec<FrameId>,
+    /// The links between consecutive entries of `frames`, in the same order.
+    pub links: Vec<LinkLookup>,
+    /// The largest `staleness` of any link, zero if there are none.
+    pub max_staleness: Duration,
+}
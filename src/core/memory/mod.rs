@@ -0,0 +1,133 @@
+//! Memory accounting and caps for a registry.
+//!
+//! This module provides `MemoryUsage`, returned by `Registry::memory_usage`, which reports
+//! how much memory the buffers of a registry hold per frame, and `Limits`, which caps the
+//! number of frames, the samples per buffer and the total bytes a registry may hold.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use std::time::Duration;
+//! use transforms::{
+//!     core::{LimitPolicy, Limits},
+//!     geometry::Transform,
+//!     Registry,
+//! };
+//!
+//! let mut registry = Registry::new(Duration::from_secs(60));
+//! registry.set_limits(Limits {
+//!     max_frames: Some(1),
+//!     policy: LimitPolicy::Reject,
+//!     ..Limits::default()
+//! });
+//!
+//! let mut transform = Transform::identity();
+//! transform.parent = "a".into();
+//! transform.child = "b".into();
+//! registry.add_transform(transform).unwrap();
+//!
+//! transform.child = "c".into();
+//! assert!(registry.add_transform(transform).is_err());
+//!
+//! let usage = registry.memory_usage();
+//! assert_eq!(usage.frames.len(), 1);
+//! assert_eq!(usage.frames[&"b".into()].samples, 1);
+//! # }
+//! ```
+
+use crate::frame::FrameId;
+use std::{collections::HashMap, fmt};
+
+/// The memory held by the buffers of a registry.
+///
+/// Byte counts are estimates: they include the buffer itself and the storage of its
+/// transforms, such as the preallocated capacity of a ring, but not the allocator overhead
+/// or the index of the frame tree.
+///
+/// Frame names are interned once per process rather than per registry, so they are
+/// reported apart from `total_bytes` and bounded by `FrameId::set_max_names` rather than
+/// by `Limits`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct MemoryUsage {
+    /// The sum of the bytes held by every buffer.
+    pub total_bytes: usize,
+    /// The usage of the buffer of each child frame.
+    pub frames: HashMap<FrameId, FrameUsage>,
+    /// The number of frame names interned by the process, as `FrameId::interned_names`.
+    pub interned_names: usize,
+    /// The bytes held by the interned frame names, as `FrameId::interned_bytes`.
+    pub interned_bytes: usize,
+}
+
+/// The memory held by the buffer of one frame.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FrameUsage {
+    /// The number of transforms in the buffer.
+    pub samples: usize,
+    /// The estimated number of bytes the buffer holds.
+    pub bytes: usize,
+}
+
+/// Caps on the memory a registry may hold. Every cap is off by default.
+///
+/// Frames are counted by their buffers, so only frames that are the child of some
+/// transform count towards `max_frames`. Frame names are interned before a transform
+/// reaches the registry, so they are capped for the whole process by
+/// `FrameId::set_max_names`; transforms naming `FrameId::OVERFLOW` are rejected with
+/// `Limit::Names` under either policy.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Limits {
+    /// The maximum number of frames with a buffer.
+    pub max_frames: Option<usize>,
+    /// The maximum number of transforms in a single buffer.
+    pub max_samples: Option<usize>,
+    /// The maximum number of bytes all buffers together may hold, as in `MemoryUsage`.
+    pub max_bytes: Option<usize>,
+    /// What happens to a transform that would exceed a cap.
+    pub policy: LimitPolicy,
+}
+
+/// What a registry does with a transform that would exceed one of its `Limits`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum LimitPolicy {
+    /// The transform is rejected with `BufferError::LimitExceeded` and nothing changes.
+    #[default]
+    Reject,
+    /// The transform is inserted and older data is evicted to make room for it.
+    ///
+    /// A buffer over `max_samples` drops its oldest transforms. Over `max_frames` or
+    /// `max_bytes`, whole frames are removed, starting with the frame whose newest transform
+    /// is the oldest and keeping static frames until no dynamic frame is left. The frame
+    /// being inserted into is never removed; if it alone exceeds `max_bytes`, it drops its
+    /// oldest transforms instead.
+    Evict,
+}
+
+/// One of the caps in `Limits`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Limit {
+    /// `Limits::max_frames`
+    Frames,
+    /// `Limits::max_samples`
+    Samples,
+    /// `Limits::max_bytes`
+    Bytes,
+    /// The cap on interned frame names, set with `FrameId::set_max_names`.
+    Names,
+}
+
+impl fmt::Display for Limit {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        match self {
+            Self::Frames => write!(f, "frames"),
+            Self::Samples => write!(f, "samples per buffer"),
+            Self::Bytes => write!(f, "bytes"),
+            Self::Names => write!(f, "frame names"),
+        }
+    }
+}
@@ -1,5 +1,23 @@
 pub mod buffer;
+pub mod handle;
+pub mod lookup;
+pub mod memory;
+pub mod poses;
+pub mod reader;
 pub mod registry;
+pub mod snapshot;
+pub mod validation;
 
-pub use buffer::Buffer;
+pub use buffer::{
+    AnomalyAction, ArchivePolicy, Buffer, BufferConfig, Compression, Decimation, DecimationMode,
+    DecimationStats, DuplicatePolicy, InterpolationMode, MotionAnomaly, MotionLimits,
+    MotionQuantity, RejectionStats, Storage,
+};
+pub use handle::LookupHandle;
+pub use lookup::{LinkLookup, LinkSource, LookupResult};
+pub use memory::{FrameUsage, Limit, LimitPolicy, Limits, MemoryUsage};
+pub use poses::TreePoses;
+pub use reader::RegistryReader;
 pub use registry::Registry;
+pub use snapshot::Snapshot;
+pub use validation::ValidationPolicy;
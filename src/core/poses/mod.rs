@@ -0,0 +1,68 @@
+//! The poses of a whole frame tree at one point in time.
+//!
+//! This module provides the `TreePoses` struct returned by `Registry::tree_at`, which holds
+//! the transform from a root frame to every frame below it, together with the frames that
+//! could not be resolved.
+
+use crate::{errors::TransformError, frame::FrameId, geometry::Transform, time::Timestamp};
+use std::collections::HashMap;
+
+/// The transforms from a root frame to every frame below it at one timestamp.
+///
+/// Frames whose link to their parent is unavailable at the timestamp are listed in
+/// `unresolved`, and so are all frames below them. Every other frame below the root has
+/// an entry in `poses`.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(not(feature = "async"))]
+/// # {
+/// use std::time::Duration;
+/// use transforms::{geometry::Transform, time::Timestamp, Registry};
+///
+/// let mut registry = Registry::new(Duration::from_secs(60));
+/// for (parent, child) in [("base", "arm"), ("arm", "gripper")] {
+///     let mut transform = Transform::identity();
+///     transform.parent = parent.into();
+///     transform.child = child.into();
+///     registry.add_transform(transform).unwrap();
+/// }
+///
+/// let poses = registry.tree_at("base", Timestamp::zero());
+/// assert_eq!(poses.len(), 2);
+/// assert_eq!(poses.get("gripper").unwrap().parent, "base");
+/// assert!(poses.unresolved.is_empty());
+/// # }
+/// ```
+#[derive(Debug)]
+pub struct TreePoses {
+    /// The frame every pose is expressed in.
+    pub root: FrameId,
+    /// The timestamp the tree was evaluated at.
+    pub timestamp: Timestamp,
+    /// The transform from `root` to each resolved frame.
+    pub poses: HashMap<FrameId, Transform>,
+    /// The reason each unresolved frame could not be evaluated.
+    pub unresolved: HashMap<FrameId, TransformError>,
+}
+
+impl TreePoses {
+    /// Returns the transform from the root to `frame`, if it was resolved.
+    pub fn get(
+        &self,
+        frame: impl Into<FrameId>,
+    ) -> Option<&Transform> {
+        self.poses.get(&frame.into())
+    }
+
+    /// Returns the number of resolved frames.
+    pub fn len(&self) -> usize {
+        self.poses.len()
+    }
+
+    /// Returns whether no frame was resolved.
+    pub fn is_empty(&self) -> bool {
+        self.poses.is_empty()
+    }
+}
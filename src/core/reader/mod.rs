@@ -0,0 +1,173 @@
+//! A wait-free read handle on a registry in publishing mode.
+//!
+//! This module provides the `RegistryReader` struct, which performs transform lookups
+//! against the latest immutable snapshot published by a `Registry`. Writers replace the
+//! snapshot with an atomic pointer swap after every insert, so readers never wait on the
+//! lock that guards the registry and never observe a partially applied insert.
+//!
+//! This is aimed at real-time consumers, such as a control loop running at a fixed rate,
+//! that must not be delayed by a burst of writers.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use std::time::Duration;
+//! use transforms::{
+//!     geometry::{Quaternion, Transform, Vector3},
+//!     time::Timestamp,
+//!     Registry,
+//! };
+//!
+//! let mut registry = Registry::new(Duration::from_secs(60));
+//! let reader = registry.reader();
+//!
+//! let timestamp = Timestamp::now();
+//! let transform = Transform {
+//!     translation: Vector3::new(1.0, 0.0, 0.0),
+//!     rotation: Quaternion::identity(),
+//!     timestamp,
+//!     parent: "base".into(),
+//!     child: "sensor".into(),
+//! };
+//! registry.add_transform(transform).unwrap();
+//!
+//! // The control loop only ever touches the reader
+//! let control_loop = std::thread::spawn(move || reader.get_transform("base", "sensor", timestamp));
+//! assert!(control_loop.join().unwrap().is_ok());
+//! # }
+//! ```
+
+use crate::{
+    core::{registry::FrameTree, InterpolationMode, LookupHandle, LookupResult, Snapshot},
+    errors::TransformError,
+    frame::FrameId,
+    geometry::Transform,
+    time::Timestamp,
+    Registry,
+};
+use arc_swap::ArcSwap;
+use std::sync::Arc;
+
+/// A read handle that looks up transforms in the latest published registry snapshot.
+///
+/// Readers are obtained from `Registry::reader` and can be cloned cheaply and sent to
+/// other threads. Each lookup loads the current snapshot with a single atomic operation
+/// and then works entirely on immutable data.
+#[derive(Clone)]
+pub struct RegistryReader {
+    published: Arc<ArcSwap<FrameTree>>,
+}
+
+impl RegistryReader {
+    pub(crate) fn new(published: Arc<ArcSwap<FrameTree>>) -> Self {
+        Self { published }
+    }
+
+    /// Retrieves a transform from the latest published snapshot.
+    ///
+    /// A successful lookup between frames whose names are already interned does not
+    /// allocate, which makes the reader safe to call from a real-time loop.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source frame.
+    /// * `to` - The destination frame.
+    /// * `timestamp` - The timestamp for which the transform is requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the transform cannot be found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # #[cfg(not(feature = "async"))]
+    /// # {
+    /// use std::time::Duration;
+    /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+    ///
+    /// let mut registry = Registry::new(Duration::from_secs(60));
+    /// let reader = registry.reader();
+    ///
+    /// let mut transform = Transform::identity();
+    /// transform.parent = "a".into();
+    /// transform.child = "b".into();
+    /// registry.add_transform(transform).unwrap();
+    ///
+    /// let result = reader.get_transform("a", "b", Timestamp::zero());
+    /// assert!(result.is_ok());
+    /// # }
+    /// ```
+    pub fn get_transform(
+        &self,
+        from: impl Into<FrameId>,
+        to: impl Into<FrameId>,
+        timestamp: Timestamp,
+    ) -> Result<Transform, TransformError> {
+        let data = self.published.load();
+        Registry::process_get_transform(from.into(), to.into(), timestamp, None, &data, None)
+    }
+
+    /// Retrieves a transform from the latest published snapshot, answering every link in
+    /// `mode` rather than the `InterpolationMode` its buffer is configured with.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the transform cannot be found.
+    pub fn get_transform_with(
+        &self,
+        from: impl Into<FrameId>,
+        to: impl Into<FrameId>,
+        timestamp: Timestamp,
+        mode: InterpolationMode,
+    ) -> Result<Transform, TransformError> {
+        let data = self.published.load();
+        Registry::process_get_transform(from.into(), to.into(), timestamp, Some(mode), &data, None)
+    }
+
+    /// Retrieves a transform from the latest published snapshot, along with the frames it
+    /// passed through and how the transform of each link was obtained.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the transform cannot be found.
+    pub fn lookup(
+        &self,
+        from: impl Into<FrameId>,
+        to: impl Into<FrameId>,
+        timestamp: Timestamp,
+    ) -> Result<LookupResult, TransformError> {
+        let data = self.published.load();
+        Registry::process_lookup(from.into(), to.into(), timestamp, &data)
+    }
+
+    /// Creates a handle for repeated lookups between two frames.
+    ///
+    /// The handle resolves the path between the frames once and only resolves it again
+    /// when the topology of the registry changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source frame.
+    /// * `to` - The destination frame.
+    pub fn lookup_handle(
+        &self,
+        from: impl Into<FrameId>,
+        to: impl Into<FrameId>,
+    ) -> LookupHandle {
+        LookupHandle::new(Arc::clone(&self.published), from.into(), to.into())
+    }
+
+    /// Returns the latest published snapshot.
+    ///
+    /// Use this when several lookups must agree with each other: the returned snapshot
+    /// keeps reflecting the same state while the registry publishes newer ones.
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot::new(self.published.load_full())
+    }
+}
+
+#[cfg(test)]
+mod tests;
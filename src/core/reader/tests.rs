@@ -0,0 +1,109 @@
+#[cfg(not(feature = "async"))]
+#[cfg(test)]
+mod reader_tests {
+    use crate::{
+        geometry::{Quaternion, Transform, Vector3},
+        time::Timestamp,
+        Registry,
+    };
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc,
+        },
+        thread,
+        time::Duration,
+    };
+
+    fn create_transform(
+        parent: &str,
+        child: &str,
+        x: f64,
+        timestamp: Timestamp,
+    ) -> Transform {
+        Transform {
+            translation: Vector3 { x, y: 0., z: 0. },
+            rotation: Quaternion::identity(),
+            timestamp,
+            parent: parent.into(),
+            child: child.into(),
+        }
+    }
+
+    #[test]
+    fn reader_sees_existing_transforms() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        registry
+            .add_transform(create_transform("a", "b", 1., t))
+            .unwrap();
+
+        let reader = registry.reader();
+        let r = reader.get_transform("a", "b", t);
+
+        assert!(r.is_ok(), "Reader returned Error, expected Ok");
+        assert_eq!(r.unwrap().translation.x, 1.);
+    }
+
+    #[test]
+    fn reader_sees_published_transforms() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        let reader = registry.reader();
+
+        assert!(reader.get_transform("a", "c", t).is_err());
+
+        registry
+            .add_transform(create_transform("a", "b", 1., t))
+            .unwrap();
+        registry
+            .add_transform(create_transform("b", "c", 2., t))
+            .unwrap();
+
+        let r = reader.get_transform("a", "c", t);
+        assert!(r.is_ok(), "Reader returned Error, expected Ok");
+        assert_eq!(r.unwrap().translation.x, 3.);
+
+        // Clones observe the same snapshots
+        let clone = reader.clone();
+        registry
+            .add_transform(create_transform("a", "d", 4., t))
+            .unwrap();
+        assert!(clone.get_transform("a", "d", t).is_ok());
+        assert!(reader.get_transform("a", "d", t).is_ok());
+    }
+
+    #[test]
+    fn reader_under_concurrent_writer() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        registry
+            .add_transform(create_transform("a", "b", 0., t))
+            .unwrap();
+
+        let reader = registry.reader();
+        let done = Arc::new(AtomicBool::new(false));
+
+        let writer_done = done.clone();
+        let writer = thread::spawn(move || {
+            for i in 1..=1000 {
+                let timestamp = (t + Duration::from_micros(i)).unwrap();
+                registry
+                    .add_transform(create_transform("a", "b", i as f64, timestamp))
+                    .unwrap();
+            }
+            writer_done.store(true, Ordering::Release);
+        });
+
+        // Every lookup must resolve against a complete snapshot, never a torn one
+        while !done.load(Ordering::Acquire) {
+            assert!(reader.get_transform("a", "b", t).is_ok());
+        }
+        writer.join().unwrap();
+
+        let last = (t + Duration::from_micros(1000)).unwrap();
+        let r = reader.get_transform("a", "b", last);
+        assert!(r.is_ok(), "Reader returned Error, expected Ok");
+        assert_eq!(r.unwrap().translation.x, 1000.);
+    }
+}
@@ -0,0 +1,130 @@
+use crate::{core::registry::tree::FrameTree, frame::FrameId};
+use std::{collections::HashMap, sync::Arc};
+
+/// The frames a lookup has to traverse, resolved from the current topology of the tree.
+///
+/// A path runs from both ends of a lookup up to their common ancestor. Each entry names
+/// the child frame of a link, which is also the key of the buffer holding that link.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct FramePath {
+    /// Frames from the source frame up to, but excluding, the common ancestor.
+    pub from_links: Vec<FrameId>,
+    /// Frames from the target frame up to, but excluding, the common ancestor.
+    pub to_links: Vec<FrameId>,
+    /// The common ancestor both halves of the path end in.
+    pub ancestor: FrameId,
+}
+
+impl FramePath {
+    /// Resolves the path between two frames from the topology index of the tree.
+    ///
+    /// Returns `None` if either frame is unknown or the frames do not share an ancestor.
+    pub fn resolve(
+        from: FrameId,
+        to: FrameId,
+        tree: &FrameTree,
+    ) -> Option<Self> {
+        let span = PathSpan::resolve(from, to, tree)?;
+        Some(Self {
+            from_links: tree.ancestors(from).take(span.from_len).collect(),
+            to_links: tree.ancestors(to).take(span.to_len).collect(),
+            ancestor: span.ancestor,
+        })
+    }
+}
+
+/// The shape of the path between two frames, without the frames along it.
+///
+/// A span can be resolved without allocating; the links of either half are the first
+/// `from_len` or `to_len` frames returned by `FrameTree::ancestors`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) struct PathSpan {
+    /// The number of links between the source frame and the common ancestor.
+    pub from_len: usize,
+    /// The number of links between the target frame and the common ancestor.
+    pub to_len: usize,
+    /// The common ancestor both halves of the path end in.
+    pub ancestor: FrameId,
+}
+
+impl PathSpan {
+    /// Resolves the span between two frames from the topology index of the tree.
+    ///
+    /// The lowest common ancestor is found by first lifting the deeper frame to the depth
+    /// of the shallower one and then lifting both in lockstep until they meet, so only the
+    /// frames on the path itself are visited.
+    ///
+    /// Returns `None` if either frame is unknown or the frames do not share an ancestor.
+    pub fn resolve(
+        from: FrameId,
+        to: FrameId,
+        tree: &FrameTree,
+    ) -> Option<Self> {
+        let mut from_frame = from;
+        let mut to_frame = to;
+        let from_depth = tree.depth(from)?;
+        let to_depth = tree.depth(to)?;
+
+        let mut from_len = 0;
+        let mut to_len = 0;
+
+        while from_depth - from_len > to_depth - to_len {
+            from_frame = tree.parent(from_frame)?;
+            from_len += 1;
+        }
+        while to_depth - to_len > from_depth - from_len {
+            to_frame = tree.parent(to_frame)?;
+            to_len += 1;
+        }
+        while from_frame != to_frame {
+            from_frame = tree.parent(from_frame)?;
+            to_frame = tree.parent(to_frame)?;
+            from_len += 1;
+            to_len += 1;
+        }
+
+        Some(Self {
+            from_len,
+            to_len,
+            ancestor: from_frame,
+        })
+    }
+}
+
+/// A cache of resolved frame paths per (from, to) pair.
+///
+/// Paths only depend on the topology of the tree, so the cache is cleared whenever a link
+/// is added or a frame changes parent, and is otherwise left untouched by inserts.
+#[derive(Debug, Default)]
+pub(crate) struct ChainCache {
+    paths: HashMap<(FrameId, FrameId), Arc<FramePath>>,
+}
+
+impl ChainCache {
+    /// Returns the cached path between two frames, resolving and caching it if needed.
+    pub fn get_or_resolve(
+        &mut self,
+        from: FrameId,
+        to: FrameId,
+        tree: &FrameTree,
+    ) -> Option<Arc<FramePath>> {
+        if let Some(path) = self.paths.get(&(from, to)) {
+            return Some(path.clone());
+        }
+
+        let path = Arc::new(FramePath::resolve(from, to, tree)?);
+        self.paths.insert((from, to), path.clone());
+        Some(path)
+    }
+
+    /// Drops every cached path.
+    pub fn clear(&mut self) {
+        self.paths.clear();
+    }
+
+    /// Returns the number of cached paths.
+    #[cfg(all(test, not(feature = "async")))]
+    pub fn len(&self) -> usize {
+        self.paths.len()
+    }
+}
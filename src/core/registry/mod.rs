@@ -128,6 +128,12 @@
 //!   - **Returns**
 //!     - A new instance of `Registry`.
 //!
+//! - `with_config(config: BufferConfig) -> Self`
+//!   - Creates a new `Registry` whose buffers use the specified configuration, such as a ring buffer storage.
+//!
+//! - `set_frame_config(&self, frame: impl Into<FrameId>, config: BufferConfig)`
+//!   - Sets the buffer configuration for the transforms into a single frame.
+//!
 //! - `add_transform(&self, t: Transform) -> Result<(), BufferError>`
 //!   - Adds a transform to the registry asynchronously.
 //!   - **Arguments**
@@ -135,7 +141,10 @@
 //!   - **Errors**
 //!     - Returns a `BufferError` if the transform cannot be added.
 //!
-//! - `await_transform(&self, from: &str, to: &str, timestamp: Timestamp) -> Result<Transform, TransformError>` (async only)
+//! - `add_transforms(&self, transforms: impl IntoIterator<Item = Transform>) -> Result<(), BufferError>`
+//!   - Adds a batch of transforms, stopping at the first rejected one and publishing once.
+//!
+//! - `await_transform(&self, from: impl Into<FrameId>, to: impl Into<FrameId>, timestamp: Timestamp) -> Result<Transform, TransformError>` (async only)
 //!   - Awaits for a transform to become available in the registry.
 //!   - **Arguments**
 //!     - `from`: The source frame.
@@ -144,7 +153,7 @@
 //!   - **Returns**
 //!     - A `Result` containing the `Transform` if found, or an error if not found.
 //!
-//! - `get_transform(&self, from: &str, to: &str, timestamp: Timestamp) -> Result<Transform, TransformError>`
+//! - `get_transform(&self, from: impl Into<FrameId>, to: impl Into<FrameId>, timestamp: Timestamp) -> Result<Transform, TransformError>`
 //!   - Retrieves a transform from the registry asynchronously.
 //!   - **Arguments**
 //!     - `from`: The source frame.
@@ -152,18 +161,92 @@
 //!     - `timestamp`: The timestamp for which the transform is requested.
 //!   - **Errors**
 //!     - Returns a `TransformError` if the transform cannot be found.
+//!
+//! - `get_transform_with(&self, from: impl Into<FrameId>, to: impl Into<FrameId>, timestamp: Timestamp, mode: InterpolationMode) -> Result<Transform, TransformError>`
+//!   - Retrieves a transform like `get_transform`, answering every link in the given `InterpolationMode`.
+//!
+//! - `lookup(&self, from: impl Into<FrameId>, to: impl Into<FrameId>, timestamp: Timestamp) -> Result<LookupResult, TransformError>`
+//!   - Retrieves a transform like `get_transform`, along with the frames it passed through and how each link was obtained.
+//!
+//! - `reader(&self) -> RegistryReader`
+//!   - Returns a read handle that performs lookups against the latest published snapshot.
+//!   - Once a reader exists, every `add_transform` publishes a new immutable snapshot.
+//!   - Each publish costs O(frames + samples of the written buffer); `add_transforms` publishes once per batch.
+//!   - **Returns**
+//!     - A `RegistryReader` that never blocks on the registry lock.
+//!
+//! - `lookup_handle(&self, from: impl Into<FrameId>, to: impl Into<FrameId>) -> LookupHandle`
+//!   - Returns a handle for repeated lookups between a fixed pair of frames.
+//!   - The path between the frames is resolved once and only again when the topology changes.
+//!   - **Returns**
+//!     - A `LookupHandle` offering `at`, `latest` and `batch` lookups.
+//!
+//! - `snapshot(&self) -> Snapshot`
+//!   - Freezes the current state of the registry into an immutable, cheaply cloned view.
+//!   - **Returns**
+//!     - A `Snapshot` supporting the same lookups and introspection as the registry.
+//!
+//! - `frames(&self) -> Vec<FrameId>`
+//!   - Lists every frame known to the registry, both children and parents, in sorted order.
+//!
+//! - `parent(&self, frame: impl Into<FrameId>) -> Option<FrameId>`
+//!   - Returns the parent of a frame according to its most recent transform.
+//!
+//! - `time_range(&self, frame: impl Into<FrameId>) -> Option<(Timestamp, Timestamp)>`
+//!   - Returns the oldest and newest timestamps buffered for the transform into a frame.
+//!
+//! - `decimation_stats(&self, frame: impl Into<FrameId>) -> Option<DecimationStats>`
+//!   - Returns how many transforms into a frame its `Decimation` has dropped or merged.
+//!
+//! - `rejection_stats(&self, frame: impl Into<FrameId>) -> Option<RejectionStats>`
+//!   - Returns how many transforms into a frame were turned away as late, duplicate or implausible.
+//!
+//! - `tree_at(&self, root: impl Into<FrameId>, timestamp: Timestamp) -> TreePoses`
+//!   - Evaluates the transform from a root frame to every frame below it in a single top-down pass.
+//!   - Frames that cannot be resolved are reported in the result instead of failing the call.
+//!
+//! - `set_limits(&mut self, limits: Limits)`
+//!   - Caps the number of frames, the samples per buffer and the total bytes of the registry.
+//!   - Transforms over a cap are rejected or make room by evicting older data, per `LimitPolicy`.
+//!
+//! - `set_validation(&mut self, policy: ValidationPolicy)`
+//!   - Decides whether invalid transforms are rejected, repaired or inserted with a warning.
+//!   - Non-finite values, zero or non-unit rotations, empty names and self-parented frames are rejected by default.
+//!
+//! - `set_anomaly_hook(&mut self, hook: impl Fn(&MotionAnomaly))`
+//!   - Reports every transform that exceeds the `MotionLimits` of its frame, rejected or flagged.
+//!
+//! - `memory_usage(&self) -> MemoryUsage`
+//!   - Reports the estimated bytes and the number of samples held by the buffer of each frame.
 
 use crate::{
-    core::Buffer,
+    core::{
+        validation, Buffer, BufferConfig, DecimationStats, InterpolationMode, Limits, LinkLookup,
+        LookupHandle, LookupResult, MemoryUsage, MotionAnomaly, RegistryReader, RejectionStats,
+        Snapshot, TreePoses, ValidationPolicy,
+    },
     errors::{BufferError, TransformError},
+    frame::FrameId,
     geometry::Transform,
     time::Timestamp,
 };
+use arc_swap::ArcSwap;
 use std::{
-    collections::{hash_map::Entry, HashMap, HashSet, VecDeque},
+    collections::{HashMap, HashSet, VecDeque},
+    sync::{Arc, OnceLock},
     time::Duration,
 };
+mod chain;
 mod error;
+mod slot;
+mod tree;
+
+pub(crate) use chain::FramePath;
+use chain::{ChainCache, PathSpan};
+pub(crate) use tree::FrameTree;
+
+/// The transforms along one half of a traced path, with how each of them was obtained.
+type TracedLinks = (VecDeque<Transform>, Vec<LinkLookup>);
 
 #[cfg(feature = "async")]
 pub use async_impl::Registry;
@@ -174,7 +257,8 @@ pub use sync_impl::Registry;
 #[cfg(feature = "async")]
 pub mod async_impl {
     use super::*;
-    use tokio::sync::{Mutex, Notify};
+    use tokio::sync::{Mutex, Notify, RwLock};
+    use tree::Append;
 
     /// A registry for managing transforms between different frames.
     ///
@@ -182,6 +266,11 @@ pub mod async_impl {
     /// between frames, supporting both synchronous and asynchronous operations
     /// depending on the feature flags.
     ///
+    /// Buffers are sharded per child frame. Inserting into a known frame without changing
+    /// its parent only locks that frame's buffer, so writers into different frames do not
+    /// wait for each other, and lookups only lock the buffers on their chain. Transforms
+    /// that change the topology lock the whole tree.
+    ///
     /// # Examples
     ///
     /// ```
@@ -231,9 +320,11 @@ pub mod async_impl {
     /// # });
     /// ```
     pub struct Registry {
-        pub data: Mutex<HashMap<String, Buffer>>,
-        max_age: Duration,
+        pub(crate) data: RwLock<FrameTree>,
+        config: BufferConfig,
         notify: Notify,
+        publisher: OnceLock<Arc<ArcSwap<FrameTree>>>,
+        cache: Mutex<ChainCache>,
     }
 
     impl Registry {
@@ -256,13 +347,185 @@ pub mod async_impl {
         /// let mut registry = Registry::new(Duration::from_secs(60));
         /// ```
         pub fn new(max_age: std::time::Duration) -> Self {
+            Self::with_config(BufferConfig::new(max_age))
+        }
+
+        /// Creates a new `Registry` whose buffers use the specified configuration.
+        ///
+        /// # Arguments
+        ///
+        /// * `config` - The configuration every buffer is created with, unless its frame
+        ///   is given its own through `set_frame_config`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{
+        ///     core::{BufferConfig, Storage},
+        ///     Registry,
+        /// };
+        ///
+        /// let registry = Registry::with_config(BufferConfig {
+        ///     storage: Storage::Ring { capacity: 1024 },
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// });
+        /// ```
+        pub fn with_config(config: BufferConfig) -> Self {
             Self {
-                data: Mutex::new(HashMap::new()),
-                max_age,
+                data: RwLock::new(FrameTree::sharded()),
+                config,
                 notify: Notify::new(),
+                publisher: OnceLock::new(),
+                cache: Mutex::new(ChainCache::default()),
             }
         }
 
+        /// Sets the buffer configuration for the transforms into `frame`.
+        ///
+        /// If the frame already has a buffer, its transforms are moved into the new storage.
+        ///
+        /// # Arguments
+        ///
+        /// * `frame` - The child frame whose buffer is configured.
+        /// * `config` - The configuration to apply.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{
+        ///     core::{BufferConfig, Storage},
+        ///     geometry::Transform,
+        ///     Registry,
+        /// };
+        /// # use tokio_test::block_on;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let config = BufferConfig {
+        ///     storage: Storage::Ring { capacity: 1024 },
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// };
+        /// registry.set_frame_config("imu", config).await;
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "base".into();
+        /// transform.child = "imu".into();
+        /// registry.add_transform(transform).await.unwrap();
+        /// # });
+        /// ```
+        pub async fn set_frame_config(
+            &self,
+            frame: impl Into<FrameId>,
+            config: BufferConfig,
+        ) {
+            let mut data = self.data.write().await;
+            Self::process_set_frame_config(frame.into(), config, &mut data);
+            Self::publish(&self.publisher, &data);
+        }
+
+        /// Sets the caps on the frames, samples and bytes the registry holds.
+        ///
+        /// With `LimitPolicy::Evict`, data over the new caps is evicted right away. With
+        /// `LimitPolicy::Reject`, data already held is kept and only new transforms that
+        /// would exceed a cap are rejected.
+        ///
+        /// # Arguments
+        ///
+        /// * `limits` - The caps to enforce.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// # use tokio_test::block_on;
+        /// use transforms::{
+        ///     core::{LimitPolicy, Limits},
+        ///     Registry,
+        /// };
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// registry
+        ///     .set_limits(Limits {
+        ///         max_frames: Some(256),
+        ///         max_bytes: Some(64 << 20),
+        ///         policy: LimitPolicy::Evict,
+        ///         ..Limits::default()
+        ///     })
+        ///     .await;
+        /// # });
+        /// ```
+        pub async fn set_limits(
+            &self,
+            limits: Limits,
+        ) {
+            let mut data = self.data.write().await;
+            let mut cache = self.cache.lock().await;
+            Self::process_set_limits(limits, &mut data, &mut cache);
+            Self::publish(&self.publisher, &data);
+        }
+
+        /// Sets a callback that receives every transform exceeding the `MotionLimits` of its
+        /// frame, whether the transform is rejected or flagged.
+        ///
+        /// The callback runs inside `add_transform`, while the registry is locked, so it
+        /// should return quickly and must not call back into the registry.
+        ///
+        /// # Arguments
+        ///
+        /// * `hook` - The callback, replacing any set before.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// # use tokio_test::block_on;
+        /// use transforms::Registry;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// registry
+        ///     .set_anomaly_hook(|anomaly| log::warn!("{anomaly}"))
+        ///     .await;
+        /// # });
+        /// ```
+        pub async fn set_anomaly_hook(
+            &self,
+            hook: impl Fn(&MotionAnomaly) + Send + Sync + 'static,
+        ) {
+            self.data.write().await.set_anomaly_hook(Arc::new(hook));
+        }
+
+        /// Sets what happens to transforms that fail validation in `add_transform`.
+        ///
+        /// Transforms with a non-finite translation, a zero or non-unit rotation, an empty
+        /// frame name or a frame parented to itself are rejected by default.
+        ///
+        /// # Arguments
+        ///
+        /// * `policy` - Whether to reject, repair or only warn about invalid transforms.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// # use tokio_test::block_on;
+        /// use transforms::{core::ValidationPolicy, Registry};
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// registry.set_validation(ValidationPolicy::Normalize).await;
+        /// # });
+        /// ```
+        pub async fn set_validation(
+            &self,
+            policy: ValidationPolicy,
+        ) {
+            self.data.write().await.set_validation(policy);
+        }
+
         /// Adds a transform to the registry asynchronously.
         ///
         /// # Arguments
@@ -271,7 +534,13 @@ pub mod async_impl {
         ///
         /// # Errors
         ///
-        /// Returns a `BufferError` if the transform cannot be added.
+        /// Returns a `BufferError` if the transform cannot be added, such as
+        /// `BufferError::FrameCycle` if it would make a frame its own ancestor,
+        /// `BufferError::LimitExceeded` if it would exceed a cap set with `set_limits`,
+        /// one of the validation errors such as `BufferError::NonUnitRotation` if the
+        /// transform is invalid and `set_validation` does not accept it, or
+        /// `BufferError::LateTransform`, `BufferError::DuplicateTimestamp` or
+        /// `BufferError::MotionAnomaly` if the `BufferConfig` of the frame rejects it.
         ///
         /// # Examples
         ///
@@ -282,7 +551,9 @@ pub mod async_impl {
         ///
         /// # block_on(async {
         /// let mut registry = Registry::new(Duration::from_secs(60));
-        /// let transform = Transform::identity();
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
         ///
         /// let result = registry.add_transform(transform).await;
         /// assert!(result.is_ok());
@@ -292,14 +563,84 @@ pub mod async_impl {
             &self,
             t: Transform,
         ) -> Result<(), BufferError> {
-            {
-                let mut data = self.data.lock().await;
-                Self::process_add_transform(t, &mut data, self.max_age)?;
+            // Transforms that keep the topology only lock the buffer of their frame
+            let (t, append) = {
+                let data = self.data.read().await;
+                let t = validation::validate(t, data.validation())?;
+                let append = data.try_append(t, |buffer| {
+                    Self::publish_buffer(&self.publisher, t.child, buffer);
+                });
+                (t, append)
+            };
+
+            if append != Append::Appended {
+                let mut data = self.data.write().await;
+                let mut cache = self.cache.lock().await;
+                if append == Append::Reindex {
+                    if data.reindex(t.child, false) {
+                        cache.clear();
+                    }
+                } else {
+                    Self::process_add_transform(t, &mut data, &mut cache, &self.config)?;
+                }
+                Self::publish(&self.publisher, &data);
             }
             self.notify.notify_waiters();
             Ok(())
         }
 
+        /// Adds a batch of transforms to the registry, publishing once for the whole batch.
+        ///
+        /// The batch holds the registry lock throughout, so no reader sees part of it.
+        /// Transforms are added in order and the batch stops at the first one that is
+        /// rejected. The transforms added before it are kept and published.
+        ///
+        /// # Arguments
+        ///
+        /// * `transforms` - The transforms to add.
+        ///
+        /// # Errors
+        ///
+        /// Returns the `BufferError` of the first rejected transform, as `add_transform`
+        /// would.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let reader = registry.reader().await;
+        ///
+        /// let links = [("a", "b"), ("b", "c")].map(|(parent, child)| {
+        ///     let mut transform = Transform::identity();
+        ///     transform.parent = parent.into();
+        ///     transform.child = child.into();
+        ///     transform
+        /// });
+        /// registry.add_transforms(links).await.unwrap();
+        ///
+        /// assert!(reader.get_transform("a", "c", Timestamp::zero()).is_ok());
+        /// # });
+        /// ```
+        pub async fn add_transforms(
+            &self,
+            transforms: impl IntoIterator<Item = Transform>,
+        ) -> Result<(), BufferError> {
+            let mut data = self.data.write().await;
+            let mut cache = self.cache.lock().await;
+            let result = transforms.into_iter().try_for_each(|t| {
+                let t = validation::validate(t, data.validation())?;
+                Self::process_add_transform(t, &mut data, &mut cache, &self.config)
+            });
+            Self::publish(&self.publisher, &data);
+            self.notify.notify_waiters();
+            result
+        }
+
         /// Awaits for a transform to become available in the registry.
         ///
         /// This method will (indefinitely) wait until the requested transform becomes available.
@@ -360,10 +701,11 @@ pub mod async_impl {
         /// ```
         pub async fn await_transform(
             &self,
-            from: &str,
-            to: &str,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
             timestamp: Timestamp,
         ) -> Result<Transform, TransformError> {
+            let (from, to) = (from.into(), to.into());
             loop {
                 if let Ok(transform) = self.get_transform(from, to, timestamp).await {
                     return Ok(transform);
@@ -382,7 +724,12 @@ pub mod async_impl {
         ///
         /// # Errors
         ///
-        /// Returns a `TransformError` if the transform cannot be found.
+        /// Returns a `TransformError` naming why the transform cannot be found:
+        /// `UnknownFrame` if a frame was never added, `Disconnected` if no chain of
+        /// transforms connects the frames, `TooOld` or `TooNew` with the available range if
+        /// a link along the chain holds no transforms that far back or forward, and
+        /// `LinkUnavailable` if a link cannot answer inside its range. A known frame looked
+        /// up in itself always yields the identity.
         ///
         /// # Examples
         ///
@@ -430,145 +777,865 @@ pub mod async_impl {
         /// ```
         pub async fn get_transform(
             &self,
-            from: &str,
-            to: &str,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
             timestamp: Timestamp,
         ) -> Result<Transform, TransformError> {
-            let mut d = self.data.lock().await;
-            Self::process_get_transform(from, to, timestamp, &mut d)
+            let d = self.data.read().await;
+            // Lookups never wait for each other; while the cache is busy, resolve without it
+            let mut cache = self.cache.try_lock().ok();
+            Self::process_get_transform(
+                from.into(),
+                to.into(),
+                timestamp,
+                None,
+                &d,
+                cache.as_deref_mut(),
+            )
         }
-    }
-}
-
-#[cfg(not(feature = "async"))]
-pub mod sync_impl {
-    use super::*;
-
-    /// A registry for managing transforms between different frames. It can
-    /// traverse the parent-child tree and calculate the final transform.
-    /// It will interpolate between two entries if a time is requested that
-    /// lies in between.
-    ///
-    /// The `Registry` struct provides methods to add and retrieve transforms
-    /// between frames, supporting both synchronous and asynchronous operations
-    /// depending on the feature flags.
-    ///
-    /// # Examples
-    ///
-    /// ```
-    /// use std::time::Duration;
-    /// use transforms::{
-    ///     geometry::{Quaternion, Transform, Vector3},
-    ///     time::Timestamp,
-    ///     Registry,
-    /// };
-    ///
-    /// // Create a new registry with a max_age duration
-    /// let mut registry = Registry::new(Duration::from_secs(60));
-    /// let t1 = Timestamp::now();
-    /// let t2 = t1.clone();
-    ///
-    /// // Define a transform from frame "a" to frame "b"
-    /// let t_a_b_1 = Transform {
-    ///     translation: Vector3 {
-    ///         x: 1.0,
-    ///         y: 0.0,
-    ///         z: 0.0,
-    ///     },
-    ///     rotation: Quaternion {
-    ///         w: 1.0,
-    ///         x: 0.0,
-    ///         y: 0.0,
-    ///         z: 0.0,
-    ///     },
-    ///     timestamp: t1,
-    ///     parent: "a".into(),
-    ///     child: "b".into(),
-    /// };
-    ///
-    /// // For validation
-    /// let t_a_b_2 = t_a_b_1.clone();
-    ///
-    /// // Add the transform to the registry
-    /// let result = registry.add_transform(t_a_b_1);
-    /// assert!(result.is_ok());
-    ///
-    /// // Retrieve the transform from "a" to "b"
-    /// let result = registry.get_transform("a", "b", t2);
-    /// assert!(result.is_ok());
-    /// assert_eq!(result.unwrap(), t_a_b_2);
-    /// ```
-    pub struct Registry {
-        pub data: HashMap<String, Buffer>,
-        max_age: Duration,
-    }
 
-    impl Registry {
-        /// Creates a new `Registry` with the specified max_age duration.
+        /// Retrieves the transform between two frames at a timestamp, answering every link in
+        /// `mode` rather than the `InterpolationMode` its buffer is configured with.
         ///
         /// # Arguments
         ///
-        /// * `max_age` - The duration for which transforms are considered valid.
+        /// * `from` - The source frame.
+        /// * `to` - The destination frame.
+        /// * `timestamp` - The timestamp for which the transform is requested.
+        /// * `mode` - How each link answers between its stored transforms.
         ///
-        /// # Returns
+        /// # Errors
         ///
-        /// A new instance of `Registry`.
+        /// Same as `get_transform`, and `TransformError::LinkUnavailable` if a link holds no
+        /// transform at `timestamp` in `InterpolationMode::Exact`.
         ///
         /// # Examples
         ///
         /// ```
         /// use std::time::Duration;
-        /// use transforms::Registry;
+        /// # use tokio_test::block_on;
+        /// use transforms::{core::InterpolationMode, geometry::Transform, time::Timestamp, Registry};
         ///
-        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let t = Timestamp::now();
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = t;
+        /// registry.add_transform(transform).await.unwrap();
+        /// transform.timestamp = (t + Duration::from_secs(1)).unwrap();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// let between = (t + Duration::from_millis(500)).unwrap();
+        /// let exact = registry
+        ///     .get_transform_with("a", "b", between, InterpolationMode::Exact)
+        ///     .await;
+        /// assert!(exact.is_err());
+        /// # });
         /// ```
-        pub fn new(max_age: std::time::Duration) -> Self {
-            Self {
-                data: HashMap::new(),
-                max_age,
-            }
+        pub async fn get_transform_with(
+            &self,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
+            timestamp: Timestamp,
+            mode: InterpolationMode,
+        ) -> Result<Transform, TransformError> {
+            let d = self.data.read().await;
+            let mut cache = self.cache.try_lock().ok();
+            Self::process_get_transform(
+                from.into(),
+                to.into(),
+                timestamp,
+                Some(mode),
+                &d,
+                cache.as_deref_mut(),
+            )
         }
 
-        /// Adds a transform to the registry.
+        /// Retrieves the transform between two frames at a timestamp, along with the frames
+        /// it passed through and how the transform of each link was obtained.
+        ///
+        /// This allocates and looks each link up twice, so prefer `get_transform` where the
+        /// provenance is not needed.
         ///
         /// # Arguments
         ///
-        /// * `t` - The transform to add.
+        /// * `from` - The source frame.
+        /// * `to` - The destination frame.
+        /// * `timestamp` - The timestamp for which the transform is requested.
         ///
         /// # Errors
         ///
-        /// Returns a `BufferError` if the transform cannot be added.
+        /// Same as `get_transform`.
         ///
         /// # Examples
         ///
         /// ```
         /// use std::time::Duration;
-        /// use transforms::{geometry::Transform, Registry};
+        /// # use tokio_test::block_on;
+        /// use transforms::{core::LinkSource, geometry::Transform, time::Timestamp, Registry};
         ///
-        /// let mut registry = Registry::new(Duration::from_secs(60));
-        /// let transform = Transform::identity();
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = Timestamp::now();
+        /// registry.add_transform(transform).await.unwrap();
         ///
-        /// let result = registry.add_transform(transform);
-        /// assert!(result.is_ok());
+        /// let result = registry.lookup("a", "b", transform.timestamp).await.unwrap();
+        /// assert_eq!(result.links[0].source, LinkSource::Exact);
+        /// # });
         /// ```
-        pub fn add_transform(
-            &mut self,
-            t: Transform,
-        ) -> Result<(), BufferError> {
-            Self::process_add_transform(t, &mut self.data, self.max_age)
+        pub async fn lookup(
+            &self,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
+            timestamp: Timestamp,
+        ) -> Result<LookupResult, TransformError> {
+            let d = self.data.read().await;
+            Self::process_lookup(from.into(), to.into(), timestamp, &d)
         }
 
-        /// Retrieves a transform from the registry.
+        /// Returns a read handle that looks up transforms without taking the registry lock.
         ///
-        /// # Arguments
-        ///
-        /// * `from` - The source frame.
-        /// * `to` - The destination frame.
-        /// * `timestamp` - The timestamp for which the transform is requested.
-        ///
-        /// # Errors
+        /// The first call switches the registry into publishing mode: from then on every
+        /// `add_transform` publishes an immutable snapshot of the frame tree, which readers
+        /// pick up with a single atomic load. This is intended for real-time threads that
+        /// must never wait on a writer.
         ///
-        /// Returns a `TransformError` if the transform cannot be found.
+        /// Writers pay for readers. Each publish clones the frame tree, which takes time
+        /// linear in the number of frames, and the next insert into a buffer the published
+        /// tree still shares copies that buffer, which takes time linear in its samples. So
+        /// every `add_transform` costs O(frames + samples of the written buffer) once
+        /// publishing is on; `add_transforms` pays this once per batch instead. Registries
+        /// that never create a reader, handle or snapshot do not pay this cost.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let reader = registry.reader().await;
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// let result = reader.get_transform("a", "b", Timestamp::zero());
+        /// assert!(result.is_ok());
+        /// # });
+        /// ```
+        pub async fn reader(&self) -> RegistryReader {
+            // Exclusive, so no insert into a single frame slips past the first snapshot
+            let data = self.data.write().await;
+            Self::process_reader(&self.publisher, &data)
+        }
+
+        /// Creates a handle for repeated lookups between two frames.
+        ///
+        /// The handle resolves the path between the frames once and only resolves it again
+        /// when the topology of the registry changes. Like a `RegistryReader`, it switches
+        /// the registry into publishing mode and never waits on the registry lock.
+        ///
+        /// Publishing mode stays on for the lifetime of the registry and makes every
+        /// `add_transform` cost O(frames + samples of the written buffer), as described on
+        /// `reader`. Prefer `add_transforms` for bursts of transforms.
+        ///
+        /// # Arguments
+        ///
+        /// * `from` - The source frame.
+        /// * `to` - The destination frame.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let mut handle = registry.lookup_handle("a", "b").await;
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// assert!(handle.at(Timestamp::zero()).is_ok());
+        /// # });
+        /// ```
+        pub async fn lookup_handle(
+            &self,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
+        ) -> LookupHandle {
+            self.reader().await.lookup_handle(from, to)
+        }
+
+        /// Freezes the current state of the registry into an immutable `Snapshot`.
+        ///
+        /// The snapshot shares its buffers with the registry and is cheap to take and to
+        /// clone. Transforms added afterwards are not visible in it, so every lookup made
+        /// against one snapshot reflects the same registry state.
+        ///
+        /// Taking a snapshot clones the frame tree, which takes time linear in the number of
+        /// frames. While the snapshot is alive, the first insert into each buffer it shares
+        /// copies that buffer, which takes time linear in its samples, so drop snapshots
+        /// once they are no longer needed.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let snapshot = registry.snapshot().await;
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// assert!(snapshot.get_transform("a", "b", Timestamp::zero()).is_err());
+        /// # });
+        /// ```
+        pub async fn snapshot(&self) -> Snapshot {
+            // Exclusive, as appends into a single frame only hold a read lock and would
+            // otherwise land in some buffers of the copy but not in others
+            let data = self.data.write().await;
+            Snapshot::new(Arc::new(data.clone()))
+        }
+
+        /// Lists every frame known to the registry, in sorted order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// assert_eq!(registry.frames().await, vec!["a", "b"]);
+        /// # });
+        /// ```
+        pub async fn frames(&self) -> Vec<FrameId> {
+            let data = self.data.read().await;
+            Self::process_frames(&data)
+        }
+
+        /// Returns the parent of a frame according to its most recent transform.
+        ///
+        /// Returns `None` for root frames and frames the registry does not know about.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// assert_eq!(registry.parent("b").await, Some("a".into()));
+        /// assert_eq!(registry.parent("a").await, None);
+        /// # });
+        /// ```
+        pub async fn parent(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<FrameId> {
+            let data = self.data.read().await;
+            Self::process_parent(frame.into(), &data)
+        }
+
+        /// Returns the oldest and newest timestamps buffered for the transform into `frame`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// assert_eq!(
+        ///     registry.time_range("b").await,
+        ///     Some((Timestamp::zero(), Timestamp::zero()))
+        /// );
+        /// # });
+        /// ```
+        pub async fn time_range(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<(Timestamp, Timestamp)> {
+            let data = self.data.read().await;
+            Self::process_time_range(frame.into(), &data)
+        }
+
+        /// Returns how many transforms into `frame` its `Decimation` has thinned out.
+        ///
+        /// Returns `None` if the registry holds no transforms into `frame`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// # use tokio_test::block_on;
+        /// use transforms::{
+        ///     core::{BufferConfig, Decimation},
+        ///     geometry::Transform,
+        ///     time::Timestamp,
+        ///     Registry,
+        /// };
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let config = BufferConfig {
+        ///     decimation: Some(Decimation::rate(100.)),
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// };
+        /// registry.set_frame_config("b", config).await;
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = Timestamp::now();
+        /// registry.add_transform(transform).await.unwrap();
+        /// transform.timestamp = (transform.timestamp + Duration::from_millis(1)).unwrap();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// let stats = registry.decimation_stats("b").await.unwrap();
+        /// assert_eq!(stats.dropped, 1);
+        /// # });
+        /// ```
+        pub async fn decimation_stats(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<DecimationStats> {
+            let data = self.data.read().await;
+            Self::process_decimation_stats(frame.into(), &data)
+        }
+
+        /// Returns how many transforms into `frame` its `BufferConfig` has turned away, as
+        /// late, duplicate or implausible.
+        ///
+        /// Returns `None` if the registry holds no transforms into `frame`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// # use tokio_test::block_on;
+        /// use transforms::{
+        ///     core::{BufferConfig, DuplicatePolicy},
+        ///     geometry::Transform,
+        ///     time::Timestamp,
+        ///     Registry,
+        /// };
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let config = BufferConfig {
+        ///     duplicates: DuplicatePolicy::Reject,
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// };
+        /// registry.set_frame_config("b", config).await;
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = Timestamp::now();
+        /// registry.add_transform(transform).await.unwrap();
+        /// assert!(registry.add_transform(transform).await.is_err());
+        ///
+        /// let stats = registry.rejection_stats("b").await.unwrap();
+        /// assert_eq!(stats.duplicates, 1);
+        /// # });
+        /// ```
+        pub async fn rejection_stats(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<RejectionStats> {
+            let data = self.data.read().await;
+            Self::process_rejection_stats(frame.into(), &data)
+        }
+
+        /// Evaluates the transform from `root` to every frame below it at one timestamp.
+        ///
+        /// Each link is looked up once in a top-down traversal, which is cheaper than one
+        /// `get_transform` call per frame. Frames that cannot be resolved, and the frames
+        /// below them, are reported in `TreePoses::unresolved` instead of failing the call.
+        ///
+        /// # Arguments
+        ///
+        /// * `root` - The frame every pose is expressed in.
+        /// * `timestamp` - The timestamp for which the poses are requested.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        /// # use tokio_test::block_on;
+        /// use std::time::Duration;
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// let poses = registry.tree_at("a", Timestamp::zero()).await;
+        /// assert!(poses.get("b").is_some());
+        /// # });
+        /// ```
+        pub async fn tree_at(
+            &self,
+            root: impl Into<FrameId>,
+            timestamp: Timestamp,
+        ) -> TreePoses {
+            let data = self.data.read().await;
+            Self::process_tree_at(root.into(), timestamp, &data)
+        }
+
+        /// Reports the memory held by the buffers of the registry, per frame.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// # use tokio_test::block_on;
+        /// use transforms::{geometry::Transform, Registry};
+        ///
+        /// # block_on(async {
+        /// let registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).await.unwrap();
+        ///
+        /// let usage = registry.memory_usage().await;
+        /// assert_eq!(usage.total_bytes, usage.frames[&"b".into()].bytes);
+        /// # });
+        /// ```
+        pub async fn memory_usage(&self) -> MemoryUsage {
+            let data = self.data.read().await;
+            Self::process_memory_usage(&data)
+        }
+    }
+}
+
+#[cfg(not(feature = "async"))]
+pub mod sync_impl {
+    use super::*;
+
+    /// A registry for managing transforms between different frames. It can
+    /// traverse the parent-child tree and calculate the final transform.
+    /// It will interpolate between two entries if a time is requested that
+    /// lies in between.
+    ///
+    /// The `Registry` struct provides methods to add and retrieve transforms
+    /// between frames, supporting both synchronous and asynchronous operations
+    /// depending on the feature flags.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use transforms::{
+    ///     geometry::{Quaternion, Transform, Vector3},
+    ///     time::Timestamp,
+    ///     Registry,
+    /// };
+    ///
+    /// // Create a new registry with a max_age duration
+    /// let mut registry = Registry::new(Duration::from_secs(60));
+    /// let t1 = Timestamp::now();
+    /// let t2 = t1.clone();
+    ///
+    /// // Define a transform from frame "a" to frame "b"
+    /// let t_a_b_1 = Transform {
+    ///     translation: Vector3 {
+    ///         x: 1.0,
+    ///         y: 0.0,
+    ///         z: 0.0,
+    ///     },
+    ///     rotation: Quaternion {
+    ///         w: 1.0,
+    ///         x: 0.0,
+    ///         y: 0.0,
+    ///         z: 0.0,
+    ///     },
+    ///     timestamp: t1,
+    ///     parent: "a".into(),
+    ///     child: "b".into(),
+    /// };
+    ///
+    /// // For validation
+    /// let t_a_b_2 = t_a_b_1.clone();
+    ///
+    /// // Add the transform to the registry
+    /// let result = registry.add_transform(t_a_b_1);
+    /// assert!(result.is_ok());
+    ///
+    /// // Retrieve the transform from "a" to "b"
+    /// let result = registry.get_transform("a", "b", t2);
+    /// assert!(result.is_ok());
+    /// assert_eq!(result.unwrap(), t_a_b_2);
+    /// ```
+    pub struct Registry {
+        pub(crate) data: FrameTree,
+        config: BufferConfig,
+        publisher: OnceLock<Arc<ArcSwap<FrameTree>>>,
+        cache: ChainCache,
+    }
+
+    impl Registry {
+        /// Creates a new `Registry` with the specified max_age duration.
+        ///
+        /// # Arguments
+        ///
+        /// * `max_age` - The duration for which transforms are considered valid.
+        ///
+        /// # Returns
+        ///
+        /// A new instance of `Registry`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::Registry;
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// ```
+        pub fn new(max_age: std::time::Duration) -> Self {
+            Self::with_config(BufferConfig::new(max_age))
+        }
+
+        /// Creates a new `Registry` whose buffers use the specified configuration.
+        ///
+        /// # Arguments
+        ///
+        /// * `config` - The configuration every buffer is created with, unless its frame
+        ///   is given its own through `set_frame_config`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{
+        ///     core::{BufferConfig, Storage},
+        ///     Registry,
+        /// };
+        ///
+        /// let mut registry = Registry::with_config(BufferConfig {
+        ///     storage: Storage::Ring { capacity: 1024 },
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// });
+        /// ```
+        pub fn with_config(config: BufferConfig) -> Self {
+            Self {
+                data: FrameTree::default(),
+                config,
+                publisher: OnceLock::new(),
+                cache: ChainCache::default(),
+            }
+        }
+
+        /// Sets the buffer configuration for the transforms into `frame`.
+        ///
+        /// If the frame already has a buffer, its transforms are moved into the new storage.
+        ///
+        /// # Arguments
+        ///
+        /// * `frame` - The child frame whose buffer is configured.
+        /// * `config` - The configuration to apply.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{
+        ///     core::{BufferConfig, Storage},
+        ///     geometry::Transform,
+        ///     Registry,
+        /// };
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let config = BufferConfig {
+        ///     storage: Storage::Ring { capacity: 1024 },
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// };
+        /// registry.set_frame_config("imu", config);
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "base".into();
+        /// transform.child = "imu".into();
+        /// registry.add_transform(transform).unwrap();
+        /// ```
+        pub fn set_frame_config(
+            &mut self,
+            frame: impl Into<FrameId>,
+            config: BufferConfig,
+        ) {
+            Self::process_set_frame_config(frame.into(), config, &mut self.data);
+            Self::publish(&self.publisher, &self.data);
+        }
+
+        /// Sets the caps on the frames, samples and bytes the registry holds.
+        ///
+        /// With `LimitPolicy::Evict`, data over the new caps is evicted right away. With
+        /// `LimitPolicy::Reject`, data already held is kept and only new transforms that
+        /// would exceed a cap are rejected.
+        ///
+        /// # Arguments
+        ///
+        /// * `limits` - The caps to enforce.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{
+        ///     core::{LimitPolicy, Limits},
+        ///     Registry,
+        /// };
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// registry.set_limits(Limits {
+        ///     max_frames: Some(256),
+        ///     max_bytes: Some(64 << 20),
+        ///     policy: LimitPolicy::Evict,
+        ///     ..Limits::default()
+        /// });
+        /// ```
+        pub fn set_limits(
+            &mut self,
+            limits: Limits,
+        ) {
+            Self::process_set_limits(limits, &mut self.data, &mut self.cache);
+            Self::publish(&self.publisher, &self.data);
+        }
+
+        /// Sets a callback that receives every transform exceeding the `MotionLimits` of its
+        /// frame, whether the transform is rejected or flagged.
+        ///
+        /// The callback runs inside `add_transform`, so it should return quickly.
+        ///
+        /// # Arguments
+        ///
+        /// * `hook` - The callback, replacing any set before.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::{
+        ///     sync::{Arc, Mutex},
+        ///     time::Duration,
+        /// };
+        /// use transforms::{
+        ///     core::{AnomalyAction, BufferConfig, MotionLimits},
+        ///     geometry::Transform,
+        ///     time::Timestamp,
+        ///     Registry,
+        /// };
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// registry.set_frame_config(
+        ///     "odom",
+        ///     BufferConfig {
+        ///         motion_limits: Some(MotionLimits {
+        ///             max_linear_velocity: Some(5.),
+        ///             action: AnomalyAction::Flag,
+        ///             ..MotionLimits::default()
+        ///         }),
+        ///         ..BufferConfig::new(Duration::from_secs(60))
+        ///     },
+        /// );
+        /// let jumps = Arc::new(Mutex::new(Vec::new()));
+        /// let seen = Arc::clone(&jumps);
+        /// registry.set_anomaly_hook(move |anomaly| seen.lock().unwrap().push(*anomaly));
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "map".into();
+        /// transform.child = "odom".into();
+        /// transform.timestamp = Timestamp::now();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// // A relocalization teleports odom by 20 metres within a second; it is still inserted
+        /// transform.timestamp = (transform.timestamp + Duration::from_secs(1)).unwrap();
+        /// transform.translation.x = 20.;
+        /// registry.add_transform(transform).unwrap();
+        /// assert_eq!(jumps.lock().unwrap().len(), 1);
+        /// assert_eq!(jumps.lock().unwrap()[0].value, 20.);
+        /// ```
+        pub fn set_anomaly_hook(
+            &mut self,
+            hook: impl Fn(&MotionAnomaly) + Send + Sync + 'static,
+        ) {
+            self.data.set_anomaly_hook(Arc::new(hook));
+        }
+
+        /// Sets what happens to transforms that fail validation in `add_transform`.
+        ///
+        /// Transforms with a non-finite translation, a zero or non-unit rotation, an empty
+        /// frame name or a frame parented to itself are rejected by default.
+        ///
+        /// # Arguments
+        ///
+        /// * `policy` - Whether to reject, repair or only warn about invalid transforms.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{core::ValidationPolicy, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// registry.set_validation(ValidationPolicy::Normalize);
+        /// ```
+        pub fn set_validation(
+            &mut self,
+            policy: ValidationPolicy,
+        ) {
+            self.data.set_validation(policy);
+        }
+
+        /// Adds a transform to the registry.
+        ///
+        /// # Arguments
+        ///
+        /// * `t` - The transform to add.
+        ///
+        /// # Errors
+        ///
+        /// Returns a `BufferError` if the transform cannot be added, such as
+        /// `BufferError::FrameCycle` if it would make a frame its own ancestor,
+        /// `BufferError::LimitExceeded` if it would exceed a cap set with `set_limits`,
+        /// one of the validation errors such as `BufferError::NonUnitRotation` if the
+        /// transform is invalid and `set_validation` does not accept it, or
+        /// `BufferError::LateTransform`, `BufferError::DuplicateTimestamp` or
+        /// `BufferError::MotionAnomaly` if the `BufferConfig` of the frame rejects it.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        ///
+        /// let result = registry.add_transform(transform);
+        /// assert!(result.is_ok());
+        /// ```
+        pub fn add_transform(
+            &mut self,
+            t: Transform,
+        ) -> Result<(), BufferError> {
+            let t = validation::validate(t, self.data.validation())?;
+            Self::process_add_transform(t, &mut self.data, &mut self.cache, &self.config)?;
+            Self::publish(&self.publisher, &self.data);
+            Ok(())
+        }
+
+        /// Adds a batch of transforms to the registry, publishing once for the whole batch.
+        ///
+        /// Transforms are added in order and the batch stops at the first one that is
+        /// rejected. The transforms added before it are kept and published.
+        ///
+        /// # Arguments
+        ///
+        /// * `transforms` - The transforms to add.
+        ///
+        /// # Errors
+        ///
+        /// Returns the `BufferError` of the first rejected transform, as `add_transform`
+        /// would.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let reader = registry.reader();
+        ///
+        /// let links = [("a", "b"), ("b", "c")].map(|(parent, child)| {
+        ///     let mut transform = Transform::identity();
+        ///     transform.parent = parent.into();
+        ///     transform.child = child.into();
+        ///     transform
+        /// });
+        /// registry.add_transforms(links).unwrap();
+        ///
+        /// assert!(reader.get_transform("a", "c", Timestamp::zero()).is_ok());
+        /// ```
+        pub fn add_transforms(
+            &mut self,
+            transforms: impl IntoIterator<Item = Transform>,
+        ) -> Result<(), BufferError> {
+            let result = transforms.into_iter().try_for_each(|t| {
+                let t = validation::validate(t, self.data.validation())?;
+                Self::process_add_transform(t, &mut self.data, &mut self.cache, &self.config)
+            });
+            Self::publish(&self.publisher, &self.data);
+            result
+        }
+
+        /// Retrieves a transform from the registry.
+        ///
+        /// Once the path between two frames is cached and their names are interned, a
+        /// successful lookup does not allocate. `Transform` holds no heap data, so the
+        /// result can be handed to a real-time thread as is.
+        ///
+        /// # Arguments
+        ///
+        /// * `from` - The source frame.
+        /// * `to` - The destination frame.
+        /// * `timestamp` - The timestamp for which the transform is requested.
+        ///
+        /// # Errors
+        ///
+        /// Returns a `TransformError` naming why the transform cannot be found:
+        /// `UnknownFrame` if a frame was never added, `Disconnected` if no chain of
+        /// transforms connects the frames, `TooOld` or `TooNew` with the available range if
+        /// a link along the chain holds no transforms that far back or forward, and
+        /// `LinkUnavailable` if a link cannot answer inside its range. A known frame looked
+        /// up in itself always yields the identity.
         ///
         /// # Examples
         ///
@@ -613,43 +1680,700 @@ pub mod sync_impl {
         /// ```
         pub fn get_transform(
             &mut self,
-            from: &str,
-            to: &str,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
+            timestamp: Timestamp,
+        ) -> Result<Transform, TransformError> {
+            Self::process_get_transform(
+                from.into(),
+                to.into(),
+                timestamp,
+                None,
+                &self.data,
+                Some(&mut self.cache),
+            )
+        }
+
+        /// Retrieves the transform between two frames at a timestamp, answering every link in
+        /// `mode` rather than the `InterpolationMode` its buffer is configured with.
+        ///
+        /// # Arguments
+        ///
+        /// * `from` - The source frame.
+        /// * `to` - The destination frame.
+        /// * `timestamp` - The timestamp for which the transform is requested.
+        /// * `mode` - How each link answers between its stored transforms.
+        ///
+        /// # Errors
+        ///
+        /// Same as `get_transform`, and `TransformError::LinkUnavailable` if a link holds no
+        /// transform at `timestamp` in `InterpolationMode::Exact`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{core::InterpolationMode, geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let t = Timestamp::now();
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = t;
+        /// registry.add_transform(transform).unwrap();
+        /// transform.timestamp = (t + Duration::from_secs(1)).unwrap();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// let between = (t + Duration::from_millis(500)).unwrap();
+        /// let exact = registry.get_transform_with("a", "b", between, InterpolationMode::Exact);
+        /// assert!(exact.is_err());
+        /// ```
+        pub fn get_transform_with(
+            &mut self,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
             timestamp: Timestamp,
+            mode: InterpolationMode,
         ) -> Result<Transform, TransformError> {
-            Self::process_get_transform(from, to, timestamp, &mut self.data)
+            Self::process_get_transform(
+                from.into(),
+                to.into(),
+                timestamp,
+                Some(mode),
+                &self.data,
+                Some(&mut self.cache),
+            )
+        }
+
+        /// Retrieves the transform between two frames at a timestamp, along with the frames
+        /// it passed through and how the transform of each link was obtained.
+        ///
+        /// This allocates and looks each link up twice, so prefer `get_transform` where the
+        /// provenance is not needed.
+        ///
+        /// # Arguments
+        ///
+        /// * `from` - The source frame.
+        /// * `to` - The destination frame.
+        /// * `timestamp` - The timestamp for which the transform is requested.
+        ///
+        /// # Errors
+        ///
+        /// Same as `get_transform`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{core::LinkSource, geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = Timestamp::now();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// let result = registry.lookup("a", "b", transform.timestamp).unwrap();
+        /// assert_eq!(result.links[0].source, LinkSource::Exact);
+        /// assert_eq!(result.max_staleness, Duration::ZERO);
+        /// ```
+        pub fn lookup(
+            &self,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
+            timestamp: Timestamp,
+        ) -> Result<LookupResult, TransformError> {
+            Self::process_lookup(from.into(), to.into(), timestamp, &self.data)
+        }
+
+        /// Returns a read handle that looks up transforms without touching the registry.
+        ///
+        /// The first call switches the registry into publishing mode: from then on every
+        /// `add_transform` publishes an immutable snapshot of the frame tree, which readers
+        /// pick up with a single atomic load. The reader can be cloned and moved to other
+        /// threads, so a real-time thread never has to wait on the lock guarding the writer.
+        ///
+        /// Writers pay for readers. Each publish clones the frame tree, which takes time
+        /// linear in the number of frames, and the next insert into a buffer the published
+        /// tree still shares copies that buffer, which takes time linear in its samples. So
+        /// every `add_transform` costs O(frames + samples of the written buffer) once
+        /// publishing is on; `add_transforms` pays this once per batch instead. Registries
+        /// that never create a reader, handle or snapshot do not pay this cost.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let reader = registry.reader();
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// let handle = std::thread::spawn(move || reader.get_transform("a", "b", Timestamp::zero()));
+        /// assert!(handle.join().unwrap().is_ok());
+        /// ```
+        pub fn reader(&self) -> RegistryReader {
+            Self::process_reader(&self.publisher, &self.data)
+        }
+
+        /// Creates a handle for repeated lookups between two frames.
+        ///
+        /// The handle resolves the path between the frames once and only resolves it again
+        /// when the topology of the registry changes. Like a `RegistryReader`, it switches
+        /// the registry into publishing mode and does not borrow the registry.
+        ///
+        /// Publishing mode stays on for the lifetime of the registry and makes every
+        /// `add_transform` cost O(frames + samples of the written buffer), as described on
+        /// `reader`. Prefer `add_transforms` for bursts of transforms.
+        ///
+        /// # Arguments
+        ///
+        /// * `from` - The source frame.
+        /// * `to` - The destination frame.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut handle = registry.lookup_handle("a", "b");
+        ///
+        /// let t1 = Timestamp::zero();
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// assert!(handle.at(t1).is_ok());
+        /// assert_eq!(handle.batch(&[t1, t1]).len(), 2);
+        /// ```
+        pub fn lookup_handle(
+            &self,
+            from: impl Into<FrameId>,
+            to: impl Into<FrameId>,
+        ) -> LookupHandle {
+            self.reader().lookup_handle(from, to)
+        }
+
+        /// Freezes the current state of the registry into an immutable `Snapshot`.
+        ///
+        /// The snapshot shares its buffers with the registry and is cheap to take and to
+        /// clone. Transforms added afterwards are not visible in it, so every lookup made
+        /// against one snapshot reflects the same registry state.
+        ///
+        /// Taking a snapshot clones the frame tree, which takes time linear in the number of
+        /// frames. While the snapshot is alive, the first insert into each buffer it shares
+        /// copies that buffer, which takes time linear in its samples, so drop snapshots
+        /// once they are no longer needed.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let snapshot = registry.snapshot();
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// assert!(snapshot.get_transform("a", "b", Timestamp::zero()).is_err());
+        /// assert!(registry.snapshot().get_transform("a", "b", Timestamp::zero()).is_ok());
+        /// ```
+        pub fn snapshot(&self) -> Snapshot {
+            Snapshot::new(Arc::new(self.data.clone()))
+        }
+
+        /// Lists every frame known to the registry, in sorted order.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// assert_eq!(registry.frames(), vec!["a", "b"]);
+        /// ```
+        pub fn frames(&self) -> Vec<FrameId> {
+            Self::process_frames(&self.data)
+        }
+
+        /// Returns the parent of a frame according to its most recent transform.
+        ///
+        /// Returns `None` for root frames and frames the registry does not know about.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// assert_eq!(registry.parent("b"), Some("a".into()));
+        /// assert_eq!(registry.parent("a"), None);
+        /// ```
+        pub fn parent(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<FrameId> {
+            Self::process_parent(frame.into(), &self.data)
+        }
+
+        /// Returns the oldest and newest timestamps buffered for the transform into `frame`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// assert_eq!(
+        ///     registry.time_range("b"),
+        ///     Some((Timestamp::zero(), Timestamp::zero()))
+        /// );
+        /// ```
+        pub fn time_range(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<(Timestamp, Timestamp)> {
+            Self::process_time_range(frame.into(), &self.data)
+        }
+
+        /// Returns how many transforms into `frame` its `Decimation` has thinned out.
+        ///
+        /// Returns `None` if the registry holds no transforms into `frame`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{
+        ///     core::{BufferConfig, Decimation},
+        ///     geometry::Transform,
+        ///     time::Timestamp,
+        ///     Registry,
+        /// };
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let config = BufferConfig {
+        ///     decimation: Some(Decimation::rate(100.)),
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// };
+        /// registry.set_frame_config("b", config);
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = Timestamp::now();
+        /// registry.add_transform(transform).unwrap();
+        /// transform.timestamp = (transform.timestamp + Duration::from_millis(1)).unwrap();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// let stats = registry.decimation_stats("b").unwrap();
+        /// assert_eq!(stats.dropped, 1);
+        /// ```
+        pub fn decimation_stats(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<DecimationStats> {
+            Self::process_decimation_stats(frame.into(), &self.data)
+        }
+
+        /// Returns how many transforms into `frame` its `BufferConfig` has turned away, as
+        /// late, duplicate or implausible.
+        ///
+        /// Returns `None` if the registry holds no transforms into `frame`.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{
+        ///     core::{BufferConfig, DuplicatePolicy},
+        ///     geometry::Transform,
+        ///     time::Timestamp,
+        ///     Registry,
+        /// };
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let config = BufferConfig {
+        ///     duplicates: DuplicatePolicy::Reject,
+        ///     ..BufferConfig::new(Duration::from_secs(60))
+        /// };
+        /// registry.set_frame_config("b", config);
+        ///
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// transform.timestamp = Timestamp::now();
+        /// registry.add_transform(transform).unwrap();
+        /// assert!(registry.add_transform(transform).is_err());
+        ///
+        /// let stats = registry.rejection_stats("b").unwrap();
+        /// assert_eq!(stats.duplicates, 1);
+        /// ```
+        pub fn rejection_stats(
+            &self,
+            frame: impl Into<FrameId>,
+        ) -> Option<RejectionStats> {
+            Self::process_rejection_stats(frame.into(), &self.data)
+        }
+
+        /// Evaluates the transform from `root` to every frame below it at one timestamp.
+        ///
+        /// Each link is looked up once in a top-down traversal, which is cheaper than one
+        /// `get_transform` call per frame. Frames that cannot be resolved, and the frames
+        /// below them, are reported in `TreePoses::unresolved` instead of failing the call.
+        ///
+        /// # Arguments
+        ///
+        /// * `root` - The frame every pose is expressed in.
+        /// * `timestamp` - The timestamp for which the poses are requested.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, time::Timestamp, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// let poses = registry.tree_at("a", Timestamp::zero());
+        /// assert!(poses.get("b").is_some());
+        /// ```
+        pub fn tree_at(
+            &self,
+            root: impl Into<FrameId>,
+            timestamp: Timestamp,
+        ) -> TreePoses {
+            Self::process_tree_at(root.into(), timestamp, &self.data)
+        }
+
+        /// Reports the memory held by the buffers of the registry, per frame.
+        ///
+        /// # Examples
+        ///
+        /// ```
+        /// use std::time::Duration;
+        /// use transforms::{geometry::Transform, Registry};
+        ///
+        /// let mut registry = Registry::new(Duration::from_secs(60));
+        /// let mut transform = Transform::identity();
+        /// transform.parent = "a".into();
+        /// transform.child = "b".into();
+        /// registry.add_transform(transform).unwrap();
+        ///
+        /// let usage = registry.memory_usage();
+        /// assert_eq!(usage.total_bytes, usage.frames[&"b".into()].bytes);
+        /// ```
+        pub fn memory_usage(&self) -> MemoryUsage {
+            Self::process_memory_usage(&self.data)
+        }
+    }
+}
+
+impl Registry {
+    /// Adds a transform to the data buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The transform to be added to the registry
+    /// * `data` - Mutable reference to the data buffer where transforms are stored
+    /// * `cache` - Mutable reference to the path cache, cleared if the topology changes
+    /// * `config` - The configuration for buffers of frames without their own
+    ///
+    /// # Errors
+    ///
+    /// Returns `BufferError` if there is an issue adding the transform to the buffer
+    fn process_add_transform(
+        t: Transform,
+        data: &mut FrameTree,
+        cache: &mut ChainCache,
+        config: &BufferConfig,
+    ) -> Result<(), BufferError> {
+        if data.insert(t, config)? {
+            cache.clear();
+        }
+        Ok(())
+    }
+
+    /// Sets the buffer configuration for a frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The child frame whose buffer is configured
+    /// * `config` - The configuration to apply
+    /// * `data` - Mutable reference to the data buffer holding the frame
+    fn process_set_frame_config(
+        frame: FrameId,
+        config: BufferConfig,
+        data: &mut FrameTree,
+    ) {
+        data.configure(frame, config);
+    }
+
+    /// Sets the limits of the data buffer, clearing the cache if frames were evicted.
+    ///
+    /// # Arguments
+    ///
+    /// * `limits` - The caps to enforce
+    /// * `data` - Mutable reference to the data buffer
+    /// * `cache` - The cache of resolved paths between frames
+    fn process_set_limits(
+        limits: Limits,
+        data: &mut FrameTree,
+        cache: &mut ChainCache,
+    ) {
+        if data.set_limits(limits) {
+            cache.clear();
+        }
+    }
+
+    /// Reports the memory held by the buffers in the data buffer.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Reference to the data buffer containing transforms
+    pub(crate) fn process_memory_usage(data: &FrameTree) -> MemoryUsage {
+        data.memory_usage()
+    }
+
+    /// Publishes a snapshot of the data buffer if the registry is in publishing mode.
+    ///
+    /// # Arguments
+    ///
+    /// * `publisher` - The slot readers load snapshots from, initialized once a reader exists
+    /// * `data` - Reference to the data buffer to publish
+    fn publish(
+        publisher: &OnceLock<Arc<ArcSwap<FrameTree>>>,
+        data: &FrameTree,
+    ) {
+        if let Some(published) = publisher.get() {
+            published.store(Arc::new(data.clone()));
+        }
+    }
+
+    /// Publishes the buffer of a single frame into the latest snapshot.
+    ///
+    /// Writers into different frames publish concurrently, so each replaces only its own
+    /// buffer and retries if another writer published in the meantime.
+    ///
+    /// # Arguments
+    ///
+    /// * `publisher` - The slot readers load snapshots from, initialized once a reader exists
+    /// * `frame` - The child frame whose buffer changed
+    /// * `buffer` - The buffer to publish
+    #[cfg(feature = "async")]
+    fn publish_buffer(
+        publisher: &OnceLock<Arc<ArcSwap<FrameTree>>>,
+        frame: FrameId,
+        buffer: &Arc<crate::core::Buffer>,
+    ) {
+        if let Some(published) = publisher.get() {
+            published.rcu(|tree| tree.with_buffer(frame, Arc::clone(buffer)));
+        }
+    }
+
+    /// Creates a reader, switching the registry into publishing mode if needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `publisher` - The slot readers load snapshots from
+    /// * `data` - Reference to the data buffer used as the initial snapshot
+    fn process_reader(
+        publisher: &OnceLock<Arc<ArcSwap<FrameTree>>>,
+        data: &FrameTree,
+    ) -> RegistryReader {
+        let published = publisher.get_or_init(|| Arc::new(ArcSwap::from_pointee(data.clone())));
+        RegistryReader::new(Arc::clone(published))
+    }
+
+    /// Lists every frame that appears in the data buffer, either as a child or as a parent.
+    ///
+    /// # Arguments
+    ///
+    /// * `data` - Reference to the data buffer containing transforms
+    pub(crate) fn process_frames(data: &FrameTree) -> Vec<FrameId> {
+        let mut frames: Vec<FrameId> = data.frames().collect();
+        frames.sort();
+        frames
+    }
+
+    /// Returns the parent of a frame according to its most recent transform.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The child frame identifier
+    /// * `data` - Reference to the data buffer containing transforms
+    pub(crate) fn process_parent(
+        frame: FrameId,
+        data: &FrameTree,
+    ) -> Option<FrameId> {
+        data.parent(frame)
+    }
+
+    /// Returns the oldest and newest timestamps buffered for the transform into a frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The child frame identifier
+    /// * `data` - Reference to the data buffer containing transforms
+    pub(crate) fn process_time_range(
+        frame: FrameId,
+        data: &FrameTree,
+    ) -> Option<(Timestamp, Timestamp)> {
+        data.buffer(frame).and_then(|buffer| buffer.time_range())
+    }
+
+    /// Returns how many transforms into a frame its decimation has thinned out.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The child frame identifier
+    /// * `data` - Reference to the data buffer containing transforms
+    pub(crate) fn process_decimation_stats(
+        frame: FrameId,
+        data: &FrameTree,
+    ) -> Option<DecimationStats> {
+        data.buffer(frame).map(|buffer| buffer.decimation_stats())
+    }
+
+    /// Returns how many transforms into a frame its duplicate, lateness and motion checks
+    /// have rejected.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The child frame identifier
+    /// * `data` - Reference to the data buffer containing transforms
+    pub(crate) fn process_rejection_stats(
+        frame: FrameId,
+        data: &FrameTree,
+    ) -> Option<RejectionStats> {
+        data.buffer(frame).map(|buffer| buffer.rejection_stats())
+    }
+
+    /// Evaluates the transform from a root frame to every frame below it at one timestamp.
+    ///
+    /// The tree is traversed top-down, so every link is looked up once and each pose is
+    /// the pose of its parent times that link. A frame whose link is unavailable is
+    /// reported as unresolved together with the frames below it.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The frame every pose is expressed in
+    /// * `timestamp` - The time for which the poses are requested
+    /// * `data` - Reference to the data buffer containing transforms
+    pub(crate) fn process_tree_at(
+        root: FrameId,
+        timestamp: Timestamp,
+        data: &FrameTree,
+    ) -> TreePoses {
+        let mut poses: HashMap<FrameId, Transform> = HashMap::new();
+        let mut unresolved = HashMap::new();
+        let mut queue = VecDeque::from([root]);
+
+        while let Some(frame) = queue.pop_front() {
+            let parent_resolved = frame == root || poses.contains_key(&frame);
+
+            for &child in data.children(frame) {
+                queue.push_back(child);
+
+                if !parent_resolved {
+                    unresolved.insert(child, TransformError::NotFound(root, child));
+                    continue;
+                }
+
+                match Self::evaluate_child(root, frame, child, timestamp, poses.get(&frame), data) {
+                    Ok(pose) => {
+                        poses.insert(child, pose);
+                    }
+                    Err(error) => {
+                        unresolved.insert(child, error);
+                    }
+                }
+            }
+        }
+
+        TreePoses {
+            root,
+            timestamp,
+            poses,
+            unresolved,
         }
     }
-}
 
-impl Registry {
-    /// Adds a transform to the data buffer.
+    /// Evaluates the transform from the root to a child frame from the pose of its parent.
     ///
     /// # Arguments
     ///
-    /// * `t` - The transform to be added to the registry
-    /// * `data` - Mutable reference to the data buffer where transforms are stored
-    /// * `max_age` - The maximum duration for which transforms are considered valid
+    /// * `root` - The frame every pose is expressed in
+    /// * `parent` - The current parent of `child`
+    /// * `child` - The frame to evaluate
+    /// * `timestamp` - The time for which the pose is requested
+    /// * `parent_pose` - The pose of `parent`, or `None` if `parent` is the root
+    /// * `data` - Reference to the data buffer containing transforms
     ///
     /// # Errors
     ///
-    /// Returns `BufferError` if there is an issue adding the transform to the buffer
-    fn process_add_transform(
-        t: Transform,
-        data: &mut HashMap<String, Buffer>,
-        max_age: Duration,
-    ) -> Result<(), BufferError> {
-        match data.entry(t.child.clone()) {
-            Entry::Occupied(mut entry) => {
-                entry.get_mut().insert(t);
-            }
-            Entry::Vacant(entry) => {
-                let buffer = Buffer::new(max_age);
-                let buffer = entry.insert(buffer);
-                buffer.insert(t);
-            }
+    /// Returns `TransformError` if the link into `child` is unavailable at `timestamp` or
+    /// cannot be combined with the pose of its parent
+    fn evaluate_child(
+        root: FrameId,
+        parent: FrameId,
+        child: FrameId,
+        timestamp: Timestamp,
+        parent_pose: Option<&Transform>,
+        data: &FrameTree,
+    ) -> Result<Transform, TransformError> {
+        let link = match data.buffer(child) {
+            Some(buffer) => buffer
+                .lookup(&timestamp, None)
+                .map_err(|_| Self::link_error(parent, child, timestamp, &buffer))?,
+            None => return Err(TransformError::NotFound(parent, child)),
+        };
+
+        // The frame was parented elsewhere at `timestamp`, so resolve it on its own
+        if link.parent != parent {
+            return Self::process_get_transform(root, child, timestamp, None, data, None);
+        }
+
+        match parent_pose {
+            Some(parent_pose) => Self::compose(*parent_pose, link),
+            None => Ok(link),
         }
-        Ok(())
     }
 
     /// Retrieves and computes the transform between two frames at a specific timestamp.
@@ -659,35 +2383,555 @@ impl Registry {
     /// * `from` - The source frame identifier
     /// * `to` - The target frame identifier
     /// * `timestamp` - The time for which the transform is requested
-    /// * `data` - Mutable reference to the data buffer containing transforms
+    /// * `mode` - The `InterpolationMode` chosen for this query, or `None` for the one each buffer is configured with
+    /// * `data` - Reference to the data buffer containing transforms
+    /// * `cache` - Optional path cache; without one the path is resolved on every call
     ///
     /// # Errors
     ///
     /// * `TransformError::NotFound` - If no valid transform chain is found between the specified frames
     /// * `TransformError::TransformTreeEmpty` - If the combined transform chain is empty after processing
     /// * Other variants of `TransformError` resulting from transform operations
-    fn process_get_transform(
-        from: &str,
-        to: &str,
+    pub(crate) fn process_get_transform(
+        from: FrameId,
+        to: FrameId,
+        timestamp: Timestamp,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
+        cache: Option<&mut ChainCache>,
+    ) -> Result<Transform, TransformError> {
+        if from == to {
+            return Self::process_identity(from, timestamp, data);
+        }
+        let transform = match cache {
+            Some(cache) => match cache.get_or_resolve(from, to, data) {
+                Some(path) => Self::evaluate_resolved_path(&path, timestamp, mode, data)?,
+                None => None,
+            },
+            None => match PathSpan::resolve(from, to, data) {
+                Some(span) => Self::evaluate_path(
+                    data.ancestors(from).take(span.from_len),
+                    data.ancestors(to).take(span.to_len),
+                    span.ancestor,
+                    timestamp,
+                    mode,
+                    data,
+                )?,
+                None => None,
+            },
+        };
+
+        match transform {
+            Some(transform) => Ok(transform),
+            None => Self::process_get_transform_fallback(from, to, timestamp, mode, data),
+        }
+    }
+
+    /// Retrieves the transform between two frames along a path resolved beforehand.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source frame identifier
+    /// * `to` - The target frame identifier
+    /// * `path` - The path between the frames, if they are connected
+    /// * `timestamp` - The time for which the transform is requested
+    /// * `mode` - The `InterpolationMode` chosen for this query, or `None` for the one each buffer is configured with
+    /// * `data` - Reference to the data buffer containing transforms
+    ///
+    /// # Errors
+    ///
+    /// Same as `process_get_transform`.
+    pub(crate) fn process_get_transform_along(
+        from: FrameId,
+        to: FrameId,
+        path: Option<&FramePath>,
+        timestamp: Timestamp,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
+    ) -> Result<Transform, TransformError> {
+        if from == to {
+            return Self::process_identity(from, timestamp, data);
+        }
+        let transform = match path {
+            Some(path) => Self::evaluate_resolved_path(path, timestamp, mode, data)?,
+            None => None,
+        };
+
+        match transform {
+            Some(transform) => Ok(transform),
+            None => Self::process_get_transform_fallback(from, to, timestamp, mode, data),
+        }
+    }
+
+    /// Returns the transform of a frame into itself, which is the identity for any known frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `frame` - The frame identifier
+    /// * `timestamp` - The time for which the transform is requested
+    /// * `data` - Reference to the data buffer containing transforms
+    ///
+    /// # Errors
+    ///
+    /// * `TransformError::UnknownFrame` - If the frame was never added
+    fn process_identity(
+        frame: FrameId,
+        timestamp: Timestamp,
+        data: &FrameTree,
+    ) -> Result<Transform, TransformError> {
+        if data.depth(frame).is_none() {
+            return Err(TransformError::UnknownFrame(frame));
+        }
+        Ok(Transform {
+            timestamp,
+            parent: frame,
+            child: frame,
+            ..Transform::identity()
+        })
+    }
+
+    /// Retrieves the transform between two frames by walking the tree as it was at `timestamp`.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source frame identifier
+    /// * `to` - The target frame identifier
+    /// * `timestamp` - The time for which the transform is requested
+    /// * `mode` - The `InterpolationMode` chosen for this query, or `None` for the one each buffer is configured with
+    /// * `data` - Reference to the data buffer containing transforms
+    ///
+    /// # Errors
+    ///
+    /// Same as `process_get_transform`.
+    fn process_get_transform_fallback(
+        from: FrameId,
+        to: FrameId,
         timestamp: Timestamp,
-        data: &mut HashMap<String, Buffer>,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
     ) -> Result<Transform, TransformError> {
-        let from_chain = Self::get_transform_chain(from, to, timestamp, data);
-        let to_chain = Self::get_transform_chain(to, from, timestamp, data);
-
-        match (from_chain, to_chain) {
-            (Ok(mut from_chain), Ok(mut to_chain)) => {
-                Self::truncate_at_common_parent(&mut from_chain, &mut to_chain);
-                Self::reverse_and_invert_transforms(&mut to_chain)?;
-                Self::combine_transforms(from_chain, to_chain)
+        // The current topology does not connect the frames at this timestamp, for example
+        // because a frame was re-parented since. Walk the tree as it was at `timestamp`.
+        let mut from_chain =
+            Self::get_transform_chain(from, to, timestamp, mode, data).unwrap_or_default();
+        let mut to_chain =
+            Self::get_transform_chain(to, from, timestamp, mode, data).unwrap_or_default();
+        Self::truncate_at_common_parent(&mut from_chain, &mut to_chain);
+
+        // The chains only connect the frames if they end in the same frame; a chain cut
+        // short by a failed lookup must not be mistaken for the whole path
+        let from_end = from_chain.back().map_or(from, |tf| tf.parent);
+        let to_end = to_chain.back().map_or(to, |tf| tf.parent);
+        if (from_chain.is_empty() && to_chain.is_empty()) || from_end != to_end {
+            return Err(Self::diagnose(from, to, timestamp, mode, data));
+        }
+
+        Self::reverse_and_invert_transforms(&mut to_chain)?;
+        Self::combine_transforms(from_chain, to_chain)
+    }
+
+    /// Works out why no transform between two frames is available at a timestamp.
+    ///
+    /// Names the first link along the current path between the frames that cannot answer
+    /// at `timestamp`, and whether the timestamp is before, after or inside the range of
+    /// transforms it holds.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source frame identifier
+    /// * `to` - The target frame identifier
+    /// * `timestamp` - The time for which the transform was requested
+    /// * `mode` - The `InterpolationMode` chosen for this query, or `None` for the one each buffer is configured with
+    /// * `data` - Reference to the data buffer containing transforms
+    fn diagnose(
+        from: FrameId,
+        to: FrameId,
+        timestamp: Timestamp,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
+    ) -> TransformError {
+        for frame in [from, to] {
+            if data.depth(frame).is_none() {
+                return TransformError::UnknownFrame(frame);
+            }
+        }
+        let Some(span) = PathSpan::resolve(from, to, data) else {
+            return TransformError::Disconnected(from, to);
+        };
+
+        let links = data
+            .ancestors(from)
+            .take(span.from_len)
+            .chain(data.ancestors(to).take(span.to_len));
+        for child in links {
+            let (Some(buffer), Some(parent)) = (data.buffer(child), data.parent(child)) else {
+                continue;
+            };
+            if buffer.lookup(&timestamp, mode).is_err() {
+                return Self::link_error(parent, child, timestamp, &buffer);
+            }
+        }
+
+        // Every link answers, but was parented differently at `timestamp`
+        TransformError::Disconnected(from, to)
+    }
+
+    /// Describes why the link from `parent` into `child` cannot answer at a timestamp.
+    fn link_error(
+        parent: FrameId,
+        child: FrameId,
+        timestamp: Timestamp,
+        buffer: &Buffer,
+    ) -> TransformError {
+        match buffer.time_range() {
+            Some((oldest, newest)) if timestamp < oldest => {
+                TransformError::TooOld(parent, child, timestamp, oldest, newest)
+            }
+            Some((oldest, newest)) if timestamp > newest => {
+                TransformError::TooNew(parent, child, timestamp, oldest, newest)
+            }
+            Some((oldest, newest)) => {
+                TransformError::LinkUnavailable(parent, child, timestamp, oldest, newest)
+            }
+            None => TransformError::NotFound(parent, child),
+        }
+    }
+
+    /// Retrieves the transform between two frames along with the provenance of each link.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source frame identifier
+    /// * `to` - The target frame identifier
+    /// * `timestamp` - The time for which the transform is requested
+    /// * `data` - Reference to the data buffer containing transforms
+    ///
+    /// # Errors
+    ///
+    /// Same as `process_get_transform`.
+    pub(crate) fn process_lookup(
+        from: FrameId,
+        to: FrameId,
+        timestamp: Timestamp,
+        data: &FrameTree,
+    ) -> Result<LookupResult, TransformError> {
+        if from == to {
+            return Ok(LookupResult {
+                transform: Self::process_identity(from, timestamp, data)?,
+                frames: vec![from],
+                links: Vec::new(),
+                max_staleness: Duration::ZERO,
+            });
+        }
+        let Some(((up_chain, up), (down_chain, down))) =
+            Self::trace_path(from, to, timestamp, data)
+        else {
+            return Err(Self::diagnose(from, to, timestamp, None, data));
+        };
+
+        // Each half folds upwards from its end, as `fold_links` folds it
+        let fold = |chain: VecDeque<Transform>| {
+            chain.into_iter().try_fold(None, |folded, tf| match folded {
+                Some(folded) => Self::compose(tf, folded).map(Some),
+                None => Ok(Some(tf)),
+            })
+        };
+        let transform = match Self::join_halves(fold(up_chain)?, fold(down_chain)?)? {
+            Some(transform) => transform,
+            None => Self::process_get_transform(from, to, timestamp, None, data, None)?,
+        };
+
+        let mut frames = vec![from];
+        frames.extend(up.iter().map(|link| link.parent));
+        frames.extend(down.iter().rev().map(|link| link.child));
+        let mut links = up;
+        links.extend(down.into_iter().rev());
+        let max_staleness = links
+            .iter()
+            .map(|link| link.staleness)
+            .max()
+            .unwrap_or_default();
+
+        Ok(LookupResult {
+            transform,
+            frames,
+            links,
+            max_staleness,
+        })
+    }
+
+    /// Traces the links `process_get_transform` combines between two frames.
+    ///
+    /// Follows the current topology if it describes the tree at `timestamp`, and otherwise
+    /// the tree as it was at `timestamp`, as the fallback lookup does.
+    ///
+    /// # Returns
+    ///
+    /// The links from the source frame up to the common ancestor and from the target frame
+    /// up to it, or `None` if the frames are not connected at `timestamp`.
+    fn trace_path(
+        from: FrameId,
+        to: FrameId,
+        timestamp: Timestamp,
+        data: &FrameTree,
+    ) -> Option<(TracedLinks, TracedLinks)> {
+        let topology = PathSpan::resolve(from, to, data).and_then(|span| {
+            let up = Self::trace_links(data.ancestors(from).take(span.from_len), timestamp, data)?;
+            let down = Self::trace_links(data.ancestors(to).take(span.to_len), timestamp, data)?;
+            let ends = |(_, links): &TracedLinks| {
+                links.last().map_or(span.ancestor, |l| l.parent) == span.ancestor
+            };
+            (ends(&up) && ends(&down)).then_some((up, down))
+        });
+        if topology.is_some() {
+            return topology;
+        }
+
+        let (mut from_chain, mut up) = Self::trace_chain(from, to, timestamp, data);
+        let (mut to_chain, mut down) = Self::trace_chain(to, from, timestamp, data);
+        Self::truncate_at_common_parent(&mut from_chain, &mut to_chain);
+        up.truncate(from_chain.len());
+        down.truncate(to_chain.len());
+
+        // As in the fallback lookup, both chains must end in the same frame
+        let from_end = from_chain.back().map_or(from, |tf| tf.parent);
+        let to_end = to_chain.back().map_or(to, |tf| tf.parent);
+        if (from_chain.is_empty() && to_chain.is_empty()) || from_end != to_end {
+            return None;
+        }
+        Some(((from_chain, up), (to_chain, down)))
+    }
+
+    /// Traces a run of links, each parented to the child frame of the next.
+    ///
+    /// Returns `None` if a link is unavailable at `timestamp` or the links do not connect.
+    fn trace_links(
+        children: impl Iterator<Item = FrameId>,
+        timestamp: Timestamp,
+        data: &FrameTree,
+    ) -> Option<TracedLinks> {
+        let (mut chain, mut links) = TracedLinks::default();
+        for child in children {
+            if links
+                .last()
+                .is_some_and(|link: &LinkLookup| link.parent != child)
+            {
+                return None;
+            }
+            let (transform, link) = data.buffer(child)?.trace(&timestamp, None).ok()?;
+            chain.push_back(transform);
+            links.push(link);
+        }
+        Some((chain, links))
+    }
+
+    /// Traces the links from `from` up through its parents at `timestamp`, like
+    /// `get_transform_chain`, stopping at `to` or at the first unavailable link.
+    fn trace_chain(
+        from: FrameId,
+        to: FrameId,
+        timestamp: Timestamp,
+        data: &FrameTree,
+    ) -> TracedLinks {
+        let (mut chain, mut links) = TracedLinks::default();
+        let mut current_frame = from;
+
+        while let Some(Ok((transform, link))) = data
+            .buffer(current_frame)
+            .map(|buffer| buffer.trace(&timestamp, None))
+        {
+            current_frame = transform.parent;
+            chain.push_back(transform);
+            links.push(link);
+            if current_frame == to {
+                break;
             }
-            (Ok(from_chain), Err(_)) => Self::combine_transforms(from_chain, VecDeque::new()),
-            (Err(_), Ok(mut to_chain)) => {
-                Self::reverse_and_invert_transforms(&mut to_chain)?;
-                Self::combine_transforms(VecDeque::new(), to_chain)
+        }
+        (chain, links)
+    }
+
+    /// Evaluates a path resolved into a `FramePath` at a given timestamp.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransformError` if the links along the path cannot be combined
+    fn evaluate_resolved_path(
+        path: &FramePath,
+        timestamp: Timestamp,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
+    ) -> Result<Option<Transform>, TransformError> {
+        Self::evaluate_path(
+            path.from_links.iter().copied(),
+            path.to_links.iter().copied(),
+            path.ancestor,
+            timestamp,
+            mode,
+            data,
+        )
+    }
+
+    /// Evaluates a resolved frame path at a given timestamp.
+    ///
+    /// Each half of the path is folded into a single transform while its links are looked
+    /// up, so evaluating a path does not allocate.
+    ///
+    /// # Arguments
+    ///
+    /// * `from_links` - The child frames of the links between the source frame and `ancestor`
+    /// * `to_links` - The child frames of the links between the target frame and `ancestor`
+    /// * `ancestor` - The common ancestor both halves of the path end in
+    /// * `timestamp` - The time for which the transform is requested
+    /// * `mode` - The `InterpolationMode` chosen for this query, or `None` for the one each buffer is configured with
+    /// * `data` - Reference to the data buffer containing transforms
+    ///
+    /// # Returns
+    ///
+    /// `None` if the path is empty or does not describe the tree at `timestamp`, otherwise
+    /// the transform between the two frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransformError` if the links along the path cannot be combined
+    fn evaluate_path(
+        from_links: impl Iterator<Item = FrameId>,
+        to_links: impl Iterator<Item = FrameId>,
+        ancestor: FrameId,
+        timestamp: Timestamp,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
+    ) -> Result<Option<Transform>, TransformError> {
+        let Some(from_link) = Self::fold_links(from_links, ancestor, timestamp, mode, data)? else {
+            return Ok(None);
+        };
+        let Some(to_link) = Self::fold_links(to_links, ancestor, timestamp, mode, data)? else {
+            return Ok(None);
+        };
+
+        Self::join_halves(from_link, to_link)
+    }
+
+    /// Joins the two halves of a path, each folded into the transform from the common
+    /// ancestor to its end, into the transform between the two ends.
+    ///
+    /// # Returns
+    ///
+    /// `None` if both halves are empty, otherwise the transform between the two frames.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransformError` if the halves cannot be combined
+    fn join_halves(
+        from_link: Option<Transform>,
+        to_link: Option<Transform>,
+    ) -> Result<Option<Transform>, TransformError> {
+        match (from_link, to_link) {
+            (Some(from_link), Some(to_link)) => {
+                Self::compose(from_link.inverse()?, to_link).map(Some)
             }
-            (Err(_), Err(_)) => Err(TransformError::NotFound(from.into(), to.into())),
+            (Some(from_link), None) => from_link.inverse().map(Some),
+            (None, Some(to_link)) => Ok(Some(to_link)),
+            (None, None) => Ok(None),
+        }
+    }
+
+    /// Folds one half of a frame path into the transform from its ancestor to its end.
+    ///
+    /// Runs of static links are taken from the static run index of the tree, so crossing
+    /// them costs a single multiplication however long they are.
+    ///
+    /// # Arguments
+    ///
+    /// * `links` - The child frames of the links, ordered from the end of the path upwards
+    /// * `ancestor` - The frame the last link is expected to be parented to
+    /// * `timestamp` - The time for which the transforms are requested
+    /// * `mode` - The `InterpolationMode` chosen for this query, or `None` for the one each buffer is configured with
+    /// * `data` - Reference to the data buffer containing transforms
+    ///
+    /// # Returns
+    ///
+    /// `None` if a link is unavailable at `timestamp` or is parented differently than the
+    /// path expects, `Some(None)` if there are no links, and the folded transform otherwise.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransformError` if two consecutive links cannot be combined
+    fn fold_links(
+        links: impl Iterator<Item = FrameId>,
+        ancestor: FrameId,
+        timestamp: Timestamp,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
+    ) -> Result<Option<Option<Transform>>, TransformError> {
+        let mut links = links.peekable();
+        let mut folded: Option<Transform> = None;
+
+        while let Some(child) = links.next() {
+            let tf = match data.static_run(child) {
+                Some(run) => {
+                    // The path follows the same parents as the run, so skip the links it covers
+                    let mut len = 1;
+                    while len < run.len && links.next().is_some() {
+                        len += 1;
+                    }
+
+                    if len == run.len {
+                        if links.peek().copied().unwrap_or(ancestor) != run.top {
+                            return Ok(None);
+                        }
+                        run.transform
+                    } else {
+                        // The path ends at an ancestor inside the run, so take off the part above it
+                        match data.static_run(ancestor) {
+                            Some(above) if above.top == run.top && above.len + len == run.len => {
+                                (above.transform.inverse()? * run.transform)?
+                            }
+                            _ => return Ok(None),
+                        }
+                    }
+                }
+                None => {
+                    let parent = links.peek().copied().unwrap_or(ancestor);
+                    let Some(tf) = data
+                        .buffer(child)
+                        .and_then(|buffer| buffer.lookup(&timestamp, mode).ok())
+                    else {
+                        return Ok(None);
+                    };
+                    if tf.parent != parent {
+                        return Ok(None);
+                    }
+                    tf
+                }
+            };
+
+            folded = Some(match folded {
+                Some(folded) => Self::compose(tf, folded)?,
+                None => tf,
+            });
+        }
+
+        Ok(Some(folded))
+    }
+
+    /// Multiplies two transforms, letting a static transform take the timestamp of the other.
+    ///
+    /// Static transforms are stored at timestamp zero but hold at any time, so they must not
+    /// be rejected for a timestamp mismatch when combined with a dynamic transform.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransformError` if the transforms cannot be multiplied
+    fn compose(
+        mut lhs: Transform,
+        mut rhs: Transform,
+    ) -> Result<Transform, TransformError> {
+        if lhs.timestamp == Timestamp::zero() {
+            lhs.timestamp = rhs.timestamp;
+        } else if rhs.timestamp == Timestamp::zero() {
+            rhs.timestamp = lhs.timestamp;
         }
+        lhs * rhs
     }
 
     /// Constructs a chain of transforms from a starting frame to a target frame at a given timestamp.
@@ -697,25 +2941,27 @@ impl Registry {
     /// * `from` - The starting frame identifier
     /// * `to` - The target frame identifier
     /// * `timestamp` - The time for which the transforms are requested
+    /// * `mode` - The `InterpolationMode` chosen for this query, or `None` for the one each buffer is configured with
     /// * `data` - Reference to the data buffer containing transforms
     ///
     /// # Errors
     ///
     /// Returns `TransformError::NotFound` if no transform chain can be found from the starting frame to the target frame
     fn get_transform_chain(
-        from: &str,
-        to: &str,
+        from: FrameId,
+        to: FrameId,
         timestamp: Timestamp,
-        data: &HashMap<String, Buffer>,
+        mode: Option<InterpolationMode>,
+        data: &FrameTree,
     ) -> Result<VecDeque<Transform>, TransformError> {
         let mut transforms = VecDeque::new();
-        let mut current_frame = from.into();
+        let mut current_frame = from;
 
-        while let Some(frame_buffer) = data.get(&current_frame) {
-            match frame_buffer.get(&timestamp) {
+        while let Some(frame_buffer) = data.buffer(current_frame) {
+            match frame_buffer.lookup(&timestamp, mode) {
                 Ok(tf) => {
-                    transforms.push_back(tf.clone());
-                    current_frame = tf.parent.clone();
+                    current_frame = tf.parent;
+                    transforms.push_back(tf);
                     if current_frame == to {
                         return Ok(transforms);
                     }
@@ -725,7 +2971,7 @@ impl Registry {
         }
 
         if transforms.is_empty() {
-            Err(TransformError::NotFound(from.into(), to.into()))
+            Err(TransformError::NotFound(from, to))
         } else {
             Ok(transforms)
         }
@@ -0,0 +1,97 @@
+use crate::core::Buffer;
+#[cfg(feature = "async")]
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::{ops::Deref, sync::Arc};
+
+/// The place a `FrameTree` keeps the buffer of one frame.
+///
+/// A frozen slot is an immutable, shared buffer that is only written through `&mut` access
+/// to the tree, copying it if a snapshot still holds it. A sharded slot puts the buffer
+/// behind its own lock, so writers into different frames can insert through a shared
+/// reference to the tree without waiting for each other.
+///
+/// Cloning a slot always yields a frozen slot holding the buffer as it is at that moment,
+/// which is what snapshots and published trees are made of.
+pub(crate) enum Slot {
+    Frozen(Arc<Buffer>),
+    #[cfg(feature = "async")]
+    Sharded(Mutex<Arc<Buffer>>),
+}
+
+/// A borrowed buffer, locked for as long as the reference is held if its slot is sharded.
+pub(crate) enum SlotRef<'a> {
+    Frozen(&'a Buffer),
+    #[cfg(feature = "async")]
+    Locked(MutexGuard<'a, Arc<Buffer>>),
+}
+
+impl Slot {
+    /// Creates a frozen slot.
+    pub fn frozen(buffer: Buffer) -> Self {
+        Self::Frozen(Arc::new(buffer))
+    }
+
+    /// Creates a slot behind its own lock.
+    #[cfg(feature = "async")]
+    pub fn sharded(buffer: Buffer) -> Self {
+        Self::Sharded(Mutex::new(Arc::new(buffer)))
+    }
+
+    /// Borrows the buffer, locking it if the slot is sharded.
+    pub fn load(&self) -> SlotRef<'_> {
+        match self {
+            Self::Frozen(buffer) => SlotRef::Frozen(buffer),
+            #[cfg(feature = "async")]
+            Self::Sharded(buffer) => SlotRef::Locked(lock(buffer)),
+        }
+    }
+
+    /// Locks a sharded slot for writing. Returns `None` for a frozen slot.
+    #[cfg(feature = "async")]
+    pub fn lock(&self) -> Option<MutexGuard<'_, Arc<Buffer>>> {
+        match self {
+            Self::Frozen(_) => None,
+            Self::Sharded(buffer) => Some(lock(buffer)),
+        }
+    }
+
+    /// Returns the buffer for writing, copying it first if a snapshot still holds it.
+    pub fn get_mut(&mut self) -> &mut Buffer {
+        match self {
+            Self::Frozen(buffer) => Arc::make_mut(buffer),
+            #[cfg(feature = "async")]
+            Self::Sharded(buffer) => {
+                Arc::make_mut(buffer.get_mut().unwrap_or_else(PoisonError::into_inner))
+            }
+        }
+    }
+}
+
+/// Locks a sharded buffer.
+#[cfg(feature = "async")]
+fn lock(buffer: &Mutex<Arc<Buffer>>) -> MutexGuard<'_, Arc<Buffer>> {
+    // A panic while inserting leaves the buffer valid, so a poisoned lock is still usable
+    buffer.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Clone for Slot {
+    fn clone(&self) -> Self {
+        match self {
+            Self::Frozen(buffer) => Self::Frozen(Arc::clone(buffer)),
+            #[cfg(feature = "async")]
+            Self::Sharded(buffer) => Self::Frozen(Arc::clone(&lock(buffer))),
+        }
+    }
+}
+
+impl Deref for SlotRef<'_> {
+    type Target = Buffer;
+
+    fn deref(&self) -> &Buffer {
+        match self {
+            Self::Frozen(buffer) => buffer,
+            #[cfg(feature = "async")]
+            Self::Locked(buffer) => buffer,
+        }
+    }
+}
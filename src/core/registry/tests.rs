@@ -2,12 +2,20 @@
 #[cfg(test)]
 mod registry_tests {
     use crate::{
+        core::{
+            registry::chain::{ChainCache, FramePath},
+            AnomalyAction, ArchivePolicy, BufferConfig, Decimation, DuplicatePolicy,
+            InterpolationMode, Limit, LimitPolicy, Limits, LinkSource, MemoryUsage, MotionLimits,
+            MotionQuantity, Storage, ValidationPolicy,
+        },
+        errors::{BufferError, TransformError},
         geometry::{Quaternion, Transform, Vector3},
         time::Timestamp,
         Registry,
     };
+    use approx::assert_abs_diff_eq;
     use log::debug;
-    use std::time::Duration;
+    use std::{sync::Arc, time::Duration};
 
     mod sync_tests {
         use super::*;
@@ -54,8 +62,8 @@ mod registry_tests {
                 child: "c".into(),
             };
 
-            registry.add_transform(t_a_b.clone()).unwrap();
-            registry.add_transform(t_b_c.clone()).unwrap();
+            registry.add_transform(t_a_b).unwrap();
+            registry.add_transform(t_b_c).unwrap();
 
             let t_a_c = Transform {
                 translation: Vector3 {
@@ -129,8 +137,8 @@ mod registry_tests {
                 child: "c".into(),
             };
 
-            registry.add_transform(t_a_b.clone()).unwrap();
-            registry.add_transform(t_b_c.clone()).unwrap();
+            registry.add_transform(t_a_b).unwrap();
+            registry.add_transform(t_b_c).unwrap();
 
             let t_c_a = Transform {
                 translation: Vector3 {
@@ -222,9 +230,9 @@ mod registry_tests {
                 child: "d".into(),
             };
 
-            registry.add_transform(t_a_b.clone()).unwrap();
-            registry.add_transform(t_b_c.clone()).unwrap();
-            registry.add_transform(t_c_d.clone()).unwrap();
+            registry.add_transform(t_a_b).unwrap();
+            registry.add_transform(t_b_c).unwrap();
+            registry.add_transform(t_c_d).unwrap();
 
             let t_a_d = Transform {
                 translation: Vector3 {
@@ -297,8 +305,8 @@ mod registry_tests {
                 child: "c".into(),
             };
 
-            registry.add_transform(t_a_b.clone()).unwrap();
-            registry.add_transform(t_a_c.clone()).unwrap();
+            registry.add_transform(t_a_b).unwrap();
+            registry.add_transform(t_a_c).unwrap();
 
             let r = registry.get_transform("a", "b", t_a_b.timestamp);
 
@@ -366,8 +374,8 @@ mod registry_tests {
                 child: "b".into(),
             };
 
-            registry.add_transform(t_a_b_0.clone()).unwrap();
-            registry.add_transform(t_a_b_1.clone()).unwrap();
+            registry.add_transform(t_a_b_0).unwrap();
+            registry.add_transform(t_a_b_1).unwrap();
 
             let middle_timestamp = Timestamp {
                 nanoseconds: (t_a_b_0.timestamp.nanoseconds + t_a_b_1.timestamp.nanoseconds) / 2,
@@ -471,10 +479,10 @@ mod registry_tests {
                 child: "c".into(),
             };
 
-            registry.add_transform(t_a_b_0.clone()).unwrap();
-            registry.add_transform(t_a_b_1.clone()).unwrap();
-            registry.add_transform(t_b_c_0.clone()).unwrap();
-            registry.add_transform(t_b_c_1.clone()).unwrap();
+            registry.add_transform(t_a_b_0).unwrap();
+            registry.add_transform(t_a_b_1).unwrap();
+            registry.add_transform(t_b_c_0).unwrap();
+            registry.add_transform(t_b_c_1).unwrap();
 
             let middle_timestamp = Timestamp {
                 nanoseconds: (t_a_b_0.timestamp.nanoseconds + t_a_b_1.timestamp.nanoseconds) / 2,
@@ -665,8 +673,10 @@ mod registry_tests {
             registry.add_transform(t_b_c).unwrap();
             registry.add_transform(t_b_d).unwrap();
 
-            let from_chain = Registry::get_transform_chain("d", "a", t, &registry.data);
-            let mut to_chain = Registry::get_transform_chain("c", "a", t, &registry.data);
+            let from_chain =
+                Registry::get_transform_chain("d".into(), "a".into(), t, None, &registry.data);
+            let mut to_chain =
+                Registry::get_transform_chain("c".into(), "a".into(), t, None, &registry.data);
 
             if let Ok(chain) = to_chain.as_mut() {
                 Registry::reverse_and_invert_transforms(chain).unwrap();
@@ -683,5 +693,1261 @@ mod registry_tests {
 
             debug!("{:?}", result);
         }
+
+        #[test]
+        fn resolve_frame_path() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+
+            for (parent, child) in [("a", "b"), ("b", "c"), ("c", "d"), ("b", "e")] {
+                let mut transform = Transform::identity();
+                transform.parent = parent.into();
+                transform.child = child.into();
+                registry.add_transform(transform).unwrap();
+            }
+
+            let path = FramePath::resolve("d".into(), "e".into(), &registry.data).unwrap();
+            assert_eq!(path.from_links, vec!["d", "c"]);
+            assert_eq!(path.to_links, vec!["e"]);
+            assert_eq!(path.ancestor, "b");
+
+            let path = FramePath::resolve("a".into(), "d".into(), &registry.data).unwrap();
+            assert!(path.from_links.is_empty());
+            assert_eq!(path.to_links, vec!["d", "c", "b"]);
+            assert_eq!(path.ancestor, "a");
+
+            assert!(FramePath::resolve("d".into(), "x".into(), &registry.data).is_none());
+        }
+
+        #[test]
+        fn frame_depth_index() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t1 = Timestamp::now();
+            let t2 = (t1 + Duration::from_secs(1)).unwrap();
+
+            for (parent, child) in [("a", "b"), ("b", "c"), ("c", "d"), ("x", "y")] {
+                let mut transform = Transform::identity();
+                transform.timestamp = t1;
+                transform.parent = parent.into();
+                transform.child = child.into();
+                registry.add_transform(transform).unwrap();
+            }
+
+            assert_eq!(registry.data.depth("a".into()), Some(0));
+            assert_eq!(registry.data.depth("d".into()), Some(3));
+            assert_eq!(registry.data.depth("y".into()), Some(1));
+            assert_eq!(registry.data.depth("z".into()), None);
+
+            // Re-parenting C onto Y moves its whole subtree
+            let mut transform = Transform::identity();
+            transform.timestamp = t2;
+            transform.parent = "y".into();
+            transform.child = "c".into();
+            registry.add_transform(transform).unwrap();
+
+            assert_eq!(registry.data.parent("c".into()), Some("y".into()));
+            assert_eq!(registry.data.depth("c".into()), Some(2));
+            assert_eq!(registry.data.depth("d".into()), Some(3));
+
+            let path = FramePath::resolve("d".into(), "b".into(), &registry.data);
+            assert!(path.is_none(), "B and D are no longer in the same tree");
+        }
+
+        #[test]
+        fn frame_cycle_rejected() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+
+            for (parent, child) in [("a", "b"), ("b", "c")] {
+                let mut transform = Transform::identity();
+                transform.parent = parent.into();
+                transform.child = child.into();
+                registry.add_transform(transform).unwrap();
+            }
+
+            let mut transform = Transform::identity();
+            transform.parent = "c".into();
+            transform.child = "a".into();
+            let r = registry.add_transform(transform);
+            assert!(matches!(r, Err(BufferError::FrameCycle(_, _))));
+
+            assert_eq!(registry.data.parent("a".into()), None);
+        }
+
+        #[test]
+        fn lookup_ignores_links_above_common_ancestor() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+
+            // The link above B is only known in the past
+            let mut t_a_b = Transform::identity();
+            t_a_b.timestamp = (t - Duration::from_secs(5)).unwrap();
+            t_a_b.parent = "a".into();
+            t_a_b.child = "b".into();
+            registry.add_transform(t_a_b).unwrap();
+
+            for (parent, child, x) in [("b", "c", 1.), ("b", "d", 3.)] {
+                let transform = Transform {
+                    translation: Vector3::new(x, 0., 0.),
+                    rotation: Quaternion::identity(),
+                    timestamp: t,
+                    parent: parent.into(),
+                    child: child.into(),
+                };
+                registry.add_transform(transform).unwrap();
+            }
+
+            let path = FramePath::resolve("c".into(), "d".into(), &registry.data).unwrap();
+            assert_eq!(path.ancestor, "b");
+
+            let r = registry.get_transform("c", "d", t);
+            assert!(r.is_ok(), "Registry returned Error, expected Ok");
+            assert_eq!(r.unwrap().translation, Vector3::new(2., 0., 0.));
+        }
+
+        #[test]
+        fn chain_cache_resolves_once() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+
+            let mut transform = Transform::identity();
+            transform.parent = "a".into();
+            transform.child = "b".into();
+            registry.add_transform(transform).unwrap();
+
+            let mut cache = ChainCache::default();
+            let first = cache
+                .get_or_resolve("a".into(), "b".into(), &registry.data)
+                .unwrap();
+            let second = cache
+                .get_or_resolve("a".into(), "b".into(), &registry.data)
+                .unwrap();
+
+            assert!(Arc::ptr_eq(&first, &second));
+            assert_eq!(cache.len(), 1);
+
+            cache.clear();
+            assert_eq!(cache.len(), 0);
+        }
+
+        #[test]
+        fn reparented_frame_invalidates_cache() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t1 = Timestamp::now();
+            let t2 = (t1 + Duration::from_secs(1)).unwrap();
+
+            let t_a_b = Transform {
+                translation: Vector3::new(1., 0., 0.),
+                rotation: Quaternion::identity(),
+                timestamp: t1,
+                parent: "a".into(),
+                child: "b".into(),
+            };
+            let t_b_c_1 = Transform {
+                translation: Vector3::new(0., 1., 0.),
+                rotation: Quaternion::identity(),
+                timestamp: t1,
+                parent: "b".into(),
+                child: "c".into(),
+            };
+
+            registry.add_transform(t_a_b).unwrap();
+            registry.add_transform(t_b_c_1).unwrap();
+
+            let r = registry.get_transform("a", "c", t1).unwrap();
+            assert_eq!(r.translation, Vector3::new(1., 1., 0.));
+
+            // Frame C is re-parented directly onto A
+            let t_a_c_2 = Transform {
+                translation: Vector3::new(0., 0., 1.),
+                rotation: Quaternion::identity(),
+                timestamp: t2,
+                parent: "a".into(),
+                child: "c".into(),
+            };
+            registry.add_transform(t_a_c_2).unwrap();
+
+            let r = registry.get_transform("a", "c", t2).unwrap();
+            assert_eq!(r, t_a_c_2);
+
+            // Lookups in the past still follow the topology at that time
+            let r = registry.get_transform("a", "c", t1).unwrap();
+            assert_eq!(r.translation, Vector3::new(1., 1., 0.));
+        }
+
+        #[test]
+        fn frame_config_selects_storage() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+
+            let ring = BufferConfig {
+                storage: Storage::Ring { capacity: 2 },
+                ..BufferConfig::new(Duration::from_secs(10))
+            };
+            registry.set_frame_config("b", ring);
+
+            for i in 0..3 {
+                for child in ["b", "c"] {
+                    let mut transform = Transform::identity();
+                    transform.timestamp = (t + Duration::from_secs(i)).unwrap();
+                    transform.parent = "a".into();
+                    transform.child = child.into();
+                    registry.add_transform(transform).unwrap();
+                }
+            }
+
+            let b = registry.data.buffer("b".into()).unwrap();
+            let c = registry.data.buffer("c".into()).unwrap();
+            assert_eq!(b.config(), ring);
+            assert_eq!(c.config().storage, Storage::BTree);
+
+            // The ring only holds the two newest transforms
+            assert!(registry.get_transform("a", "b", t).is_err());
+            assert!(registry.get_transform("a", "c", t).is_ok());
+
+            let t_new = (t + Duration::from_secs(2)).unwrap();
+            assert!(registry.get_transform("b", "c", t_new).is_ok());
+
+            // Reconfiguring an existing buffer keeps what it holds
+            registry.set_frame_config("c", ring);
+            let c = registry.data.buffer("c".into()).unwrap();
+            assert_eq!(c.config(), ring);
+            assert_eq!(
+                c.time_range(),
+                Some(((t + Duration::from_secs(1)).unwrap(), t_new))
+            );
+        }
+
+        fn static_link(
+            parent: &str,
+            child: &str,
+            x: f64,
+            yaw: f64,
+        ) -> Transform {
+            Transform {
+                translation: Vector3::new(x, 0., 0.),
+                rotation: Quaternion {
+                    w: (yaw / 2.).cos(),
+                    x: 0.,
+                    y: 0.,
+                    z: (yaw / 2.).sin(),
+                },
+                timestamp: Timestamp::zero(),
+                parent: parent.into(),
+                child: child.into(),
+            }
+        }
+
+        #[test]
+        fn static_links_are_folded() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+
+            let links = [
+                static_link("base", "mount", 1., std::f64::consts::FRAC_PI_2),
+                static_link("mount", "bracket", 2., 0.),
+                static_link("bracket", "camera", 3., 0.),
+                static_link("camera", "optical", 4., 0.),
+            ];
+            for link in &links {
+                registry.add_transform(*link).unwrap();
+            }
+            let mut odom = static_link("odom", "base", 10., 0.);
+            odom.timestamp = t;
+            registry.add_transform(odom).unwrap();
+
+            let run = registry.data.static_run("optical".into()).unwrap();
+            assert_eq!(run.len, 4);
+            assert_eq!(run.top, "base");
+            assert!(registry.data.static_run("base".into()).is_none());
+
+            // Base to optical: X=1 then rotated by 90 degrees, so the rest goes along Y
+            let r = registry.get_transform("base", "optical", t).unwrap();
+            assert_abs_diff_eq!(r.translation, Vector3::new(1., 9., 0.), epsilon = 1e-9);
+
+            // Through the dynamic link, the static run takes the lookup timestamp
+            let r = registry.get_transform("odom", "optical", t).unwrap();
+            assert_abs_diff_eq!(r.translation, Vector3::new(11., 9., 0.), epsilon = 1e-9);
+            assert_eq!(r.timestamp, t);
+
+            // A path that ends inside the run
+            let r = registry.get_transform("mount", "optical", t).unwrap();
+            assert_abs_diff_eq!(r.translation, Vector3::new(9., 0., 0.), epsilon = 1e-9);
+            let r = registry.get_transform("optical", "bracket", t).unwrap();
+            assert_abs_diff_eq!(r.translation, Vector3::new(-7., 0., 0.), epsilon = 1e-9);
+        }
+
+        #[test]
+        fn static_update_refolds_runs() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+
+            for (parent, child) in [
+                ("base", "mount"),
+                ("mount", "camera"),
+                ("camera", "optical"),
+            ] {
+                registry
+                    .add_transform(static_link(parent, child, 1., 0.))
+                    .unwrap();
+            }
+            let r = registry.get_transform("base", "optical", t).unwrap();
+            assert_eq!(r.translation, Vector3::new(3., 0., 0.));
+
+            // Updating a static link in the middle refolds the runs below it
+            registry
+                .add_transform(static_link("mount", "camera", 5., 0.))
+                .unwrap();
+            let r = registry.get_transform("base", "optical", t).unwrap();
+            assert_eq!(r.translation, Vector3::new(7., 0., 0.));
+            assert_eq!(registry.data.static_run("optical".into()).unwrap().len, 3);
+
+            // A link turning dynamic splits the run
+            let mut dynamic = static_link("mount", "camera", 2., 0.);
+            dynamic.timestamp = t;
+            registry.add_transform(dynamic).unwrap();
+            assert!(registry.data.static_run("camera".into()).is_none());
+            let run = registry.data.static_run("optical".into()).unwrap();
+            assert_eq!((run.top, run.len), ("camera".into(), 1));
+
+            let r = registry.get_transform("base", "optical", t).unwrap();
+            assert_eq!(r.translation, Vector3::new(4., 0., 0.));
+        }
+
+        #[test]
+        fn tree_at_matches_lookups() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+
+            registry
+                .add_transform(static_link("base", "arm", 1., std::f64::consts::FRAC_PI_2))
+                .unwrap();
+            registry
+                .add_transform(static_link("arm", "gripper", 2., 0.))
+                .unwrap();
+            let mut wheel = static_link("base", "wheel", 3., 0.);
+            wheel.timestamp = t;
+            registry.add_transform(wheel).unwrap();
+            let mut odom = static_link("odom", "base", 4., 0.);
+            odom.timestamp = t;
+            registry.add_transform(odom).unwrap();
+
+            let poses = registry.tree_at("base", t);
+            assert_eq!(poses.root, "base");
+            assert_eq!(poses.timestamp, t);
+            assert_eq!(poses.len(), 3);
+            assert!(poses.unresolved.is_empty());
+            assert!(poses.get("odom").is_none());
+
+            for frame in ["arm", "gripper", "wheel"] {
+                let pose = poses.get(frame).unwrap();
+                let expected = registry.get_transform("base", frame, t).unwrap();
+                assert_eq!(pose.parent, "base");
+                assert_eq!(pose.child, frame);
+                assert_abs_diff_eq!(pose.translation, expected.translation, epsilon = 1e-9);
+                assert_abs_diff_eq!(pose.rotation, expected.rotation, epsilon = 1e-9);
+            }
+        }
+
+        #[test]
+        fn tree_at_follows_interpolation_modes() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+            let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+            for (frame, interpolation) in [
+                ("b", InterpolationMode::Nearest),
+                ("c", InterpolationMode::Previous),
+            ] {
+                registry.set_frame_config(
+                    frame,
+                    BufferConfig {
+                        interpolation,
+                        ..BufferConfig::new(Duration::from_secs(10))
+                    },
+                );
+            }
+
+            // The samples of the two links are offset in time, so no stored timestamp agrees
+            for (parent, child, ms) in [("a", "b", 0), ("a", "b", 1000), ("b", "c", 100)] {
+                let mut link = link_at(parent, child, at(ms));
+                link.translation.x = ms as f64;
+                registry.add_transform(link).unwrap();
+            }
+            registry.add_transform(link_at("b", "c", at(1100))).unwrap();
+
+            let poses = registry.tree_at("a", at(300));
+            assert!(poses.unresolved.is_empty());
+            for frame in ["b", "c"] {
+                let pose = poses.get(frame).unwrap();
+                let expected = registry.get_transform("a", frame, at(300)).unwrap();
+                assert_eq!(pose.timestamp, at(300));
+                assert_abs_diff_eq!(pose.translation, expected.translation, epsilon = 1e-9);
+                assert_abs_diff_eq!(pose.rotation, expected.rotation, epsilon = 1e-9);
+            }
+            assert_eq!(poses.get("c").unwrap().translation.x, 100.);
+        }
+
+        #[test]
+        fn tree_at_reports_unresolved_subtrees() {
+            let _ = env_logger::try_init();
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t1 = Timestamp::now();
+            let t2 = (t1 + Duration::from_secs(1)).unwrap();
+
+            for (parent, child, t) in [
+                ("base", "arm", t2),
+                ("arm", "gripper", t2),
+                ("base", "wheel", t1),
+                ("wheel", "tire", t2),
+            ] {
+                let mut link = static_link(parent, child, 1., 0.);
+                link.timestamp = t;
+                registry.add_transform(link).unwrap();
+            }
+
+            // Base to wheel is only known at t1, so the wheel and the tire below it fail
+            let poses = registry.tree_at("base", t2);
+            assert_eq!(poses.len(), 2);
+            assert_eq!(
+                poses.get("gripper").unwrap().translation,
+                Vector3::new(2., 0., 0.)
+            );
+            assert_eq!(poses.unresolved.len(), 2);
+            assert!(poses.unresolved.contains_key(&"wheel".into()));
+            assert!(poses.unresolved.contains_key(&"tire".into()));
+
+            // An unknown root resolves nothing
+            let poses = registry.tree_at("unknown", t2);
+            assert!(poses.is_empty());
+            assert!(poses.unresolved.is_empty());
+        }
+
+        fn link_at(
+            parent: &str,
+            child: &str,
+            timestamp: Timestamp,
+        ) -> Transform {
+            let mut link = static_link(parent, child, 1., 0.);
+            link.timestamp = timestamp;
+            link
+        }
+
+        fn assert_accounted(usage: &MemoryUsage) {
+            let sum: usize = usage.frames.values().map(|frame| frame.bytes).sum();
+            assert_eq!(usage.total_bytes, sum);
+        }
+
+        #[test]
+        fn memory_usage_per_frame() {
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+            // The interned names are shared by the process, so only the buffers are empty
+            let empty = registry.memory_usage();
+            assert_eq!(empty.total_bytes, 0);
+            assert!(empty.frames.is_empty());
+            assert!(empty.interned_names > 0);
+
+            for i in 0..3 {
+                let t = (t + Duration::from_millis(i)).unwrap();
+                registry.add_transform(link_at("a", "b", t)).unwrap();
+            }
+            registry.add_transform(link_at("b", "c", t)).unwrap();
+
+            let usage = registry.memory_usage();
+            assert_eq!(usage.frames.len(), 2);
+            assert_eq!(usage.frames[&"b".into()].samples, 3);
+            assert_eq!(usage.frames[&"c".into()].samples, 1);
+            assert!(usage.frames[&"b".into()].bytes > usage.frames[&"c".into()].bytes);
+            assert_accounted(&usage);
+
+            // Replacing a transform, reconfiguring and expiring keep the total in step
+            registry.add_transform(link_at("b", "c", t)).unwrap();
+            let ring = BufferConfig {
+                storage: Storage::Ring { capacity: 64 },
+                ..BufferConfig::new(Duration::from_secs(10))
+            };
+            registry.set_frame_config("c", ring);
+            let old = (t - Duration::from_secs(20)).unwrap();
+            registry.add_transform(link_at("a", "b", old)).unwrap();
+
+            let usage = registry.memory_usage();
+            assert_eq!(usage.frames[&"b".into()].samples, 3);
+            assert_accounted(&usage);
+            assert_eq!(registry.snapshot().memory_usage(), usage);
+        }
+
+        #[test]
+        fn limits_reject() {
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+            let t2 = (t + Duration::from_millis(1)).unwrap();
+            registry.set_limits(Limits {
+                max_frames: Some(2),
+                max_samples: Some(2),
+                ..Limits::default()
+            });
+
+            registry.add_transform(link_at("a", "b", t)).unwrap();
+            registry.add_transform(link_at("b", "c", t)).unwrap();
+            let r = registry.add_transform(link_at("c", "d", t));
+            assert!(matches!(
+                r,
+                Err(BufferError::LimitExceeded(Limit::Frames, 2))
+            ));
+            assert_eq!(registry.frames(), vec!["a", "b", "c"]);
+
+            registry.add_transform(link_at("a", "b", t2)).unwrap();
+            let t3 = (t + Duration::from_millis(2)).unwrap();
+            let r = registry.add_transform(link_at("a", "b", t3));
+            assert!(matches!(
+                r,
+                Err(BufferError::LimitExceeded(Limit::Samples, 2))
+            ));
+            // Replacing a transform does not add a sample
+            registry.add_transform(link_at("a", "b", t2)).unwrap();
+
+            let total = registry.memory_usage().total_bytes;
+            registry.set_limits(Limits {
+                max_bytes: Some(total),
+                ..Limits::default()
+            });
+            let r = registry.add_transform(link_at("b", "c", t2));
+            assert!(
+                matches!(r, Err(BufferError::LimitExceeded(Limit::Bytes, max)) if max == total)
+            );
+            let usage = registry.memory_usage();
+            assert_eq!(usage.total_bytes, total);
+            assert_eq!(usage.frames[&"c".into()].samples, 1);
+        }
+
+        #[test]
+        fn limits_evict_least_recently_updated() {
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+            registry.set_limits(Limits {
+                max_frames: Some(3),
+                policy: LimitPolicy::Evict,
+                ..Limits::default()
+            });
+
+            registry
+                .add_transform(static_link("base", "camera", 1., 0.))
+                .unwrap();
+            registry.add_transform(link_at("odom", "base", t)).unwrap();
+            let t2 = (t + Duration::from_millis(1)).unwrap();
+            registry.add_transform(link_at("map", "odom", t2)).unwrap();
+            assert!(registry.get_transform("odom", "camera", t).is_ok());
+
+            // Base was updated least recently, the static camera is kept
+            let t3 = (t + Duration::from_millis(2)).unwrap();
+            registry.add_transform(link_at("map", "gps", t3)).unwrap();
+            let usage = registry.memory_usage();
+            assert_eq!(usage.frames.len(), 3);
+            assert!(!usage.frames.contains_key(&"base".into()));
+            assert_accounted(&usage);
+            assert_eq!(registry.parent("base"), None);
+            assert!(registry.get_transform("base", "camera", t).is_ok());
+
+            // Lowering the cap evicts right away, static frames last
+            registry.set_limits(Limits {
+                max_frames: Some(1),
+                policy: LimitPolicy::Evict,
+                ..Limits::default()
+            });
+            assert_eq!(registry.frames(), vec!["base", "camera"]);
+        }
+
+        #[test]
+        fn limits_evict_samples_and_bytes() {
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+            registry.set_limits(Limits {
+                max_samples: Some(2),
+                policy: LimitPolicy::Evict,
+                ..Limits::default()
+            });
+
+            for i in 0..4 {
+                let t = (t + Duration::from_millis(i)).unwrap();
+                registry.add_transform(link_at("a", "b", t)).unwrap();
+            }
+            let t_last = (t + Duration::from_millis(3)).unwrap();
+            assert_eq!(
+                registry.time_range("b"),
+                Some(((t + Duration::from_millis(2)).unwrap(), t_last))
+            );
+
+            registry.add_transform(link_at("a", "c", t)).unwrap();
+            let total = registry.memory_usage().total_bytes;
+            registry.set_limits(Limits {
+                max_bytes: Some(total),
+                policy: LimitPolicy::Evict,
+                ..Limits::default()
+            });
+
+            // C is newer than B's newest transform once it is updated, so B goes
+            registry
+                .add_transform(link_at(
+                    "a",
+                    "c",
+                    (t_last + Duration::from_millis(1)).unwrap(),
+                ))
+                .unwrap();
+            let usage = registry.memory_usage();
+            assert!(usage.total_bytes <= total);
+            assert_eq!(usage.frames.len(), 1);
+            assert_accounted(&usage);
+
+            // Alone over the cap, a frame drops its own oldest transforms
+            for i in 2..10 {
+                let t = (t_last + Duration::from_millis(i)).unwrap();
+                registry.add_transform(link_at("a", "c", t)).unwrap();
+            }
+            let usage = registry.memory_usage();
+            assert!(usage.total_bytes <= total);
+            assert_eq!(
+                registry.time_range("c").map(|(_, newest)| newest),
+                Some((t_last + Duration::from_millis(9)).unwrap())
+            );
+            assert_accounted(&usage);
+        }
+
+        #[test]
+        fn decimation_per_frame() {
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+            registry.set_frame_config(
+                "b",
+                BufferConfig {
+                    decimation: Some(Decimation::rate(100.)),
+                    ..BufferConfig::new(Duration::from_secs(10))
+                },
+            );
+            registry.set_limits(Limits {
+                max_samples: Some(2),
+                ..Limits::default()
+            });
+
+            for i in 0..15 {
+                let t = (t + Duration::from_millis(i)).unwrap();
+                registry.add_transform(link_at("a", "b", t)).unwrap();
+                let _ = registry.add_transform(link_at("b", "c", t));
+            }
+
+            // Thinned out transforms never count against the sample cap
+            let stats = registry.decimation_stats("b").unwrap();
+            assert_eq!(stats.dropped, 13);
+            assert_eq!(registry.memory_usage().frames[&"b".into()].samples, 2);
+            assert_eq!(registry.decimation_stats("c").unwrap().dropped, 0);
+            assert_eq!(registry.decimation_stats("a"), None);
+            assert_eq!(registry.snapshot().decimation_stats("b"), Some(stats));
+        }
+
+        #[test]
+        fn archive_is_transparent_to_lookups() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            registry.set_frame_config(
+                "b",
+                BufferConfig {
+                    archive: Some(ArchivePolicy {
+                        decimation: Some(Decimation::rate(10.)),
+                        ..ArchivePolicy::new(Duration::from_secs(1))
+                    }),
+                    ..BufferConfig::new(Duration::from_secs(60))
+                },
+            );
+
+            let start = (Timestamp::now() - Duration::from_secs(5)).unwrap();
+            for i in 0..500 {
+                let t = (start + Duration::from_millis(i * 10)).unwrap();
+                let mut link = link_at("a", "b", t);
+                link.translation.x = i as f64;
+                registry.add_transform(link).unwrap();
+                registry.add_transform(link_at("b", "c", t)).unwrap();
+            }
+
+            let usage = registry.memory_usage();
+            assert!(usage.frames[&"b".into()].samples < usage.frames[&"c".into()].samples / 2);
+            assert_accounted(&usage);
+            for i in [0, 5, 250, 499] {
+                let t = (start + Duration::from_millis(i * 10)).unwrap();
+                let r = registry.get_transform("a", "c", t).unwrap();
+                assert_abs_diff_eq!(r.translation.x, i as f64 + 1., epsilon = 1e-6);
+            }
+        }
+
+        #[test]
+        fn interpolation_mode_per_frame_and_query() {
+            let mut registry = Registry::new(Duration::from_secs(10));
+            let t = Timestamp::now();
+            let t2 = (t + Duration::from_secs(1)).unwrap();
+            let between = (t + Duration::from_millis(250)).unwrap();
+            registry.set_frame_config(
+                "c",
+                BufferConfig {
+                    interpolation: InterpolationMode::Previous,
+                    ..BufferConfig::new(Duration::from_secs(10))
+                },
+            );
+
+            for (parent, child) in [("a", "b"), ("b", "c")] {
+                registry.add_transform(link_at(parent, child, t)).unwrap();
+                let mut link = link_at(parent, child, t2);
+                link.translation.x = 5.;
+                registry.add_transform(link).unwrap();
+            }
+
+            // B interpolates linearly, C holds its previous transform
+            let r = registry.get_transform("a", "c", between).unwrap();
+            assert_abs_diff_eq!(r.translation.x, 3.);
+
+            let r = registry
+                .get_transform_with("a", "c", between, InterpolationMode::Nearest)
+                .unwrap();
+            assert_abs_diff_eq!(r.translation.x, 2.);
+            let r = registry
+                .get_transform_with("c", "a", between, InterpolationMode::Linear)
+                .unwrap();
+            assert_abs_diff_eq!(r.translation.x, -4.);
+
+            // Off-sample queries fail in exact mode, through every lookup
+            let exact = InterpolationMode::Exact;
+            assert!(registry
+                .get_transform_with("a", "c", between, exact)
+                .is_err());
+            assert!(registry.get_transform_with("a", "c", t2, exact).is_ok());
+            let snapshot = registry.snapshot();
+            assert!(snapshot
+                .get_transform_with("a", "c", between, exact)
+                .is_err());
+            let reader = registry.reader();
+            assert!(reader.get_transform_with("a", "c", between, exact).is_err());
+            assert_abs_diff_eq!(
+                reader
+                    .get_transform_with("a", "c", t2, exact)
+                    .unwrap()
+                    .translation
+                    .x,
+                10.
+            );
+        }
+
+        #[test]
+        fn max_interpolation_gap_fails_lookups() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            registry.set_frame_config(
+                "b",
+                BufferConfig {
+                    max_interpolation_gap: Some(Duration::from_millis(500)),
+                    ..BufferConfig::new(Duration::from_secs(60))
+                },
+            );
+
+            for (parent, child) in [("a", "b"), ("b", "c")] {
+                registry.add_transform(link_at(parent, child, t)).unwrap();
+                let later = (t + Duration::from_secs(5)).unwrap();
+                registry
+                    .add_transform(link_at(parent, child, later))
+                    .unwrap();
+            }
+
+            // B dropped out between its transforms, so no chain through it is answered
+            let inside = (t + Duration::from_secs(2)).unwrap();
+            assert!(registry.get_transform("a", "b", inside).is_err());
+            assert!(registry.get_transform("a", "c", inside).is_err());
+            assert!(registry.get_transform("b", "c", inside).is_ok());
+            assert!(registry.get_transform("a", "c", t).is_ok());
+        }
+
+        #[test]
+        fn frame_in_itself_is_identity() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            registry.add_transform(link_at("a", "b", t)).unwrap();
+
+            // The root has no buffer of its own, and the child has no transform this early
+            let before = (t - Duration::from_secs(10)).unwrap();
+            for frame in ["a", "b"] {
+                for timestamp in [t, before] {
+                    let result = registry.get_transform(frame, frame, timestamp).unwrap();
+                    assert_eq!(result.translation.x, 0.);
+                    assert_eq!(result.rotation.w, 1.);
+                    assert_eq!(result.timestamp, timestamp);
+                    assert_eq!((result.parent, result.child), (frame.into(), frame.into()));
+
+                    let lookup = registry.lookup(frame, frame, timestamp).unwrap();
+                    assert_eq!(lookup.transform, result);
+                    assert!(registry
+                        .reader()
+                        .get_transform(frame, frame, timestamp)
+                        .is_ok());
+                    assert!(registry
+                        .snapshot()
+                        .get_transform(frame, frame, timestamp)
+                        .is_ok());
+                    assert!(registry.lookup_handle(frame, frame).at(timestamp).is_ok());
+                }
+            }
+
+            assert!(matches!(
+                registry.get_transform("c", "c", t),
+                Err(TransformError::UnknownFrame(frame)) if frame == "c"
+            ));
+            assert!(matches!(
+                registry.lookup("c", "c", t),
+                Err(TransformError::UnknownFrame(_))
+            ));
+        }
+
+        #[test]
+        fn add_transforms_stops_at_first_rejection() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let reader = registry.reader();
+            let t = Timestamp::now();
+
+            let result = registry.add_transforms([
+                link_at("a", "b", t),
+                link_at("b", "c", t),
+                link_at("c", "a", t),
+                link_at("c", "d", t),
+            ]);
+            assert!(matches!(result, Err(BufferError::FrameCycle(_, _))));
+
+            // Transforms before the rejected one are kept and published, later ones are not
+            assert!(reader.get_transform("a", "c", t).is_ok());
+            assert!(registry.get_transform("a", "c", t).is_ok());
+            assert!(matches!(
+                reader.get_transform("a", "d", t),
+                Err(TransformError::UnknownFrame(_))
+            ));
+        }
+
+        #[test]
+        fn lookup_reports_provenance() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+            registry.set_frame_config(
+                "camera",
+                BufferConfig {
+                    interpolation: InterpolationMode::Previous,
+                    ..BufferConfig::new(Duration::from_secs(60))
+                },
+            );
+
+            registry
+                .add_transform(static_link("base", "arm", 1., 0.))
+                .unwrap();
+            for ms in [0, 1000] {
+                registry
+                    .add_transform(link_at("arm", "gripper", at(ms)))
+                    .unwrap();
+                registry
+                    .add_transform(link_at("base", "camera", at(ms)))
+                    .unwrap();
+            }
+            registry
+                .add_transform(link_at("base", "lidar", at(300)))
+                .unwrap();
+
+            let result = registry.lookup("gripper", "camera", at(300)).unwrap();
+            assert_eq!(
+                result.transform,
+                registry
+                    .get_transform("gripper", "camera", at(300))
+                    .unwrap()
+            );
+            assert_eq!(result.frames, vec!["gripper", "arm", "base", "camera"]);
+
+            let links: Vec<_> = result
+                .links
+                .iter()
+                .map(|link| (link.parent, link.child, link.source))
+                .collect();
+            assert_eq!(
+                links,
+                vec![
+                    ("arm".into(), "gripper".into(), LinkSource::Interpolated),
+                    ("base".into(), "arm".into(), LinkSource::Static),
+                    ("base".into(), "camera".into(), LinkSource::Held),
+                ]
+            );
+            assert_eq!(result.links[0].samples, vec![at(0), at(1000)]);
+            assert_eq!(result.links[0].staleness, Duration::from_millis(300));
+            assert!(result.links[1].samples.is_empty());
+            assert_eq!(result.links[1].staleness, Duration::ZERO);
+            assert_eq!(result.links[2].samples, vec![at(0)]);
+            assert_eq!(result.max_staleness, Duration::from_millis(300));
+
+            // A sample at exactly the timestamp is reported as such
+            let result = registry.lookup("base", "lidar", at(300)).unwrap();
+            assert_eq!(result.frames, vec!["base", "lidar"]);
+            assert_eq!(result.links[0].source, LinkSource::Exact);
+            assert_eq!(result.links[0].samples, vec![at(300)]);
+            assert_eq!(result.max_staleness, Duration::ZERO);
+
+            // Looking a frame up in itself passes through no links
+            let result = registry.lookup("arm", "arm", at(300)).unwrap();
+            assert_eq!(result.frames, vec!["arm"]);
+            assert!(result.links.is_empty());
+            assert_eq!(result.max_staleness, Duration::ZERO);
+
+            // Every lookup surface reports the same provenance
+            let expected = registry.lookup("gripper", "camera", at(700)).unwrap();
+            assert_eq!(
+                registry
+                    .snapshot()
+                    .lookup("gripper", "camera", at(700))
+                    .unwrap(),
+                expected
+            );
+            assert_eq!(
+                registry
+                    .reader()
+                    .lookup("gripper", "camera", at(700))
+                    .unwrap(),
+                expected
+            );
+
+            assert!(registry.lookup("gripper", "nowhere", at(300)).is_err());
+            assert!(registry.lookup("gripper", "camera", at(1001)).is_err());
+        }
+
+        #[test]
+        fn lookup_follows_the_tree_at_the_timestamp() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            let later = (t + Duration::from_secs(1)).unwrap();
+
+            // C is re-parented from A to B
+            registry.add_transform(link_at("a", "c", t)).unwrap();
+            registry.add_transform(link_at("b", "c", later)).unwrap();
+
+            let result = registry.lookup("a", "c", t).unwrap();
+            assert_eq!(
+                result.transform,
+                registry.get_transform("a", "c", t).unwrap()
+            );
+            assert_eq!(result.frames, vec!["a", "c"]);
+            assert_eq!(result.links.len(), 1);
+            assert_eq!(result.links[0].parent, "a");
+            assert_eq!(result.links[0].source, LinkSource::Exact);
+        }
+
+        #[test]
+        fn lookup_errors_name_the_failing_link() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+            let before = (t - Duration::from_secs(1)).unwrap();
+
+            registry
+                .add_transform(static_link("map", "odom", 0., 0.))
+                .unwrap();
+            for ms in [0, 1000] {
+                registry
+                    .add_transform(link_at("odom", "base", at(ms)))
+                    .unwrap();
+            }
+            registry
+                .add_transform(link_at("world", "satellite", t))
+                .unwrap();
+
+            assert!(matches!(
+                registry.get_transform("map", "nowhere", t),
+                Err(TransformError::UnknownFrame(frame)) if frame == "nowhere"
+            ));
+            assert!(matches!(
+                registry.get_transform("base", "satellite", t),
+                Err(TransformError::Disconnected(from, to)) if from == "base" && to == "satellite"
+            ));
+
+            // The dynamic link is named with the requested time and the range it holds
+            match registry.get_transform("map", "base", before) {
+                Err(TransformError::TooOld(parent, child, requested, oldest, newest)) => {
+                    assert_eq!((parent, child), ("odom".into(), "base".into()));
+                    assert_eq!(requested, before);
+                    assert_eq!((oldest, newest), (t, at(1000)));
+                }
+                other => panic!("Expected TooOld, got {other:?}"),
+            }
+            match registry.get_transform("base", "map", at(1500)) {
+                Err(TransformError::TooNew(parent, child, requested, oldest, newest)) => {
+                    assert_eq!((parent, child), ("odom".into(), "base".into()));
+                    assert_eq!(requested, at(1500));
+                    assert_eq!((oldest, newest), (t, at(1000)));
+                }
+                other => panic!("Expected TooNew, got {other:?}"),
+            }
+            assert!(matches!(
+                registry.get_transform_with("map", "base", at(500), InterpolationMode::Exact),
+                Err(TransformError::LinkUnavailable(_, child, _, _, _)) if child == "base"
+            ));
+
+            // Epoch timestamps are reported exactly, rather than failing to convert
+            let message = registry
+                .get_transform("map", "base", at(1500))
+                .unwrap_err()
+                .to_string();
+            assert!(message.contains(&at(1500).to_string()), "{message}");
+            assert!(message.contains(&t.to_string()), "{message}");
+
+            // Every lookup surface reports the same error
+            assert!(matches!(
+                registry.snapshot().get_transform("map", "base", before),
+                Err(TransformError::TooOld(..))
+            ));
+            assert!(matches!(
+                registry.reader().get_transform("map", "base", before),
+                Err(TransformError::TooOld(..))
+            ));
+            assert!(matches!(
+                registry.lookup_handle("map", "base").at(before),
+                Err(TransformError::TooOld(..))
+            ));
+            let poses = registry.tree_at("map", before);
+            assert!(matches!(
+                poses.unresolved.get(&"base".into()),
+                Some(TransformError::TooOld(..))
+            ));
+        }
+
+        #[test]
+        fn invalid_transforms_are_rejected() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            let valid = link_at("a", "b", t);
+
+            let mut empty = valid;
+            empty.child = "".into();
+            let mut looped = valid;
+            looped.child = "a".into();
+            let mut nan_translation = valid;
+            nan_translation.translation.y = f64::NAN;
+            let mut infinite_rotation = valid;
+            infinite_rotation.rotation.z = f64::INFINITY;
+            let mut zero_rotation = valid;
+            zero_rotation.rotation = Quaternion {
+                w: 0.,
+                x: 0.,
+                y: 0.,
+                z: 0.,
+            };
+            let mut scaled_rotation = valid;
+            scaled_rotation.rotation.w = 2.;
+
+            assert!(matches!(
+                registry.add_transform(empty),
+                Err(BufferError::EmptyFrameName(..))
+            ));
+            assert!(matches!(
+                registry.add_transform(looped),
+                Err(BufferError::SelfParented(frame)) if frame == "a"
+            ));
+            assert!(matches!(
+                registry.add_transform(nan_translation),
+                Err(BufferError::NonFiniteTranslation(..))
+            ));
+            assert!(matches!(
+                registry.add_transform(infinite_rotation),
+                Err(BufferError::NonFiniteRotation(..))
+            ));
+            assert!(matches!(
+                registry.add_transform(zero_rotation),
+                Err(BufferError::ZeroRotation(..))
+            ));
+            assert!(matches!(
+                registry.add_transform(scaled_rotation),
+                Err(BufferError::NonUnitRotation(parent, child, norm))
+                    if parent == "a" && child == "b" && norm == 2.
+            ));
+
+            // Nothing was inserted, and a valid transform still is
+            assert!(registry.frames().is_empty());
+            registry.add_transform(valid).unwrap();
+            assert!(registry.get_transform("a", "b", t).is_ok());
+        }
+
+        #[test]
+        fn validation_rejects_previously_accepted_transforms_by_default() {
+            let t = Timestamp::now();
+            let unnamed = Transform::identity();
+            let looped = link_at("a", "a", t);
+            let mut scaled = link_at("a", "b", t);
+            scaled.rotation.w = 2.;
+
+            // Inserted as they were before validation existed, and rejected now
+            let mut registry = Registry::new(Duration::from_secs(60));
+            for transform in [unnamed, looped, scaled] {
+                assert!(registry.add_transform(transform).is_err());
+            }
+            assert!(registry.frames().is_empty());
+
+            // Warn restores the old behaviour
+            registry.set_validation(ValidationPolicy::Warn);
+            for transform in [unnamed, looped, scaled] {
+                registry.add_transform(transform).unwrap();
+            }
+            assert!(registry.get_transform("", "", Timestamp::zero()).is_ok());
+            assert!(registry.get_transform("a", "b", t).is_ok());
+        }
+
+        #[test]
+        fn validation_policy_normalize_repairs_rotations() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            registry.set_validation(ValidationPolicy::Normalize);
+            let t = Timestamp::now();
+
+            let mut scaled = link_at("a", "b", t);
+            scaled.rotation = Quaternion {
+                w: 0.,
+                x: 0.,
+                y: 0.,
+                z: 3.,
+            };
+            registry.add_transform(scaled).unwrap();
+            let stored = registry.get_transform("a", "b", t).unwrap();
+            assert_abs_diff_eq!(stored.rotation.norm(), 1., epsilon = 1e-12);
+            assert_abs_diff_eq!(stored.rotation.z, 1., epsilon = 1e-12);
+
+            // Rotations within the tolerance are stored as they are
+            let mut close = link_at("a", "c", t);
+            close.rotation.w = 1. + 1e-9;
+            registry.add_transform(close).unwrap();
+            let stored = registry.get_transform("a", "c", t).unwrap();
+            assert_eq!(stored.rotation.w, 1. + 1e-9);
+
+            // Problems that cannot be repaired are still rejected
+            let mut nan = link_at("a", "d", t);
+            nan.translation.x = f64::NAN;
+            assert!(matches!(
+                registry.add_transform(nan),
+                Err(BufferError::NonFiniteTranslation(..))
+            ));
+            let mut zero = link_at("a", "d", t);
+            zero.rotation.w = 0.;
+            assert!(matches!(
+                registry.add_transform(zero),
+                Err(BufferError::ZeroRotation(..))
+            ));
+        }
+
+        #[test]
+        fn validation_policy_warn_inserts_as_is() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            registry.set_validation(ValidationPolicy::Warn);
+            let t = Timestamp::now();
+
+            let mut scaled = link_at("a", "b", t);
+            scaled.rotation.w = 2.;
+            registry.add_transform(scaled).unwrap();
+            let stored = registry.get_transform("a", "b", t).unwrap();
+            assert_eq!(stored.rotation.w, 2.);
+        }
+
+        #[test]
+        fn motion_limits_report_anomalies() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+            let limited = |action| BufferConfig {
+                motion_limits: Some(MotionLimits {
+                    max_linear_velocity: Some(5.),
+                    action,
+                    ..MotionLimits::default()
+                }),
+                ..BufferConfig::new(Duration::from_secs(60))
+            };
+            registry.set_frame_config("odom", limited(AnomalyAction::Reject));
+            registry.set_frame_config("base", limited(AnomalyAction::Flag));
+            let anomalies = Arc::new(std::sync::Mutex::new(Vec::new()));
+            let seen = Arc::clone(&anomalies);
+            registry.set_anomaly_hook(move |anomaly| seen.lock().unwrap().push(*anomaly));
+
+            for (parent, child) in [("map", "odom"), ("odom", "base")] {
+                registry.add_transform(link_at(parent, child, t)).unwrap();
+            }
+
+            // A relocalization teleports odom by 20 metres and is rejected
+            let mut teleport = link_at("map", "odom", at(1000));
+            teleport.translation.x = 21.;
+            assert!(matches!(
+                registry.add_transform(teleport),
+                Err(BufferError::MotionAnomaly(anomaly)) if anomaly.child == "odom"
+            ));
+            assert!(registry.get_transform("map", "odom", at(1000)).is_err());
+
+            // A glitch in base is inserted and flagged
+            let mut glitch = link_at("odom", "base", at(1000));
+            glitch.translation.x = 11.;
+            registry.add_transform(glitch).unwrap();
+            assert_eq!(
+                registry
+                    .get_transform("odom", "base", at(1000))
+                    .unwrap()
+                    .translation
+                    .x,
+                11.
+            );
+
+            // Plausible motion passes
+            let mut drive = link_at("map", "odom", at(1000));
+            drive.translation.x = 3.;
+            registry.add_transform(drive).unwrap();
+
+            let anomalies = anomalies.lock().unwrap();
+            assert_eq!(anomalies.len(), 2);
+            assert_eq!(anomalies[0].child, "odom");
+            assert_eq!(anomalies[0].value, 20.);
+            assert_eq!(anomalies[1].child, "base");
+            assert_eq!(anomalies[1].quantity, MotionQuantity::LinearVelocity);
+        }
+
+        #[test]
+        fn late_and_duplicate_transforms_are_counted() {
+            let mut registry = Registry::new(Duration::from_secs(60));
+            let t = Timestamp::now();
+            let at = |ms| (t + Duration::from_millis(ms)).unwrap();
+            registry.set_frame_config(
+                "b",
+                BufferConfig {
+                    duplicates: DuplicatePolicy::Reject,
+                    max_lateness: Some(Duration::from_millis(200)),
+                    ..BufferConfig::new(Duration::from_secs(60))
+                },
+            );
+
+            registry.add_transform(link_at("a", "b", at(1000))).unwrap();
+            assert!(matches!(
+                registry.add_transform(link_at("a", "b", at(1000))),
+                Err(BufferError::DuplicateTimestamp(..))
+            ));
+            assert!(matches!(
+                registry.add_transform(link_at("a", "b", at(500))),
+                Err(BufferError::LateTransform(..))
+            ));
+            registry.add_transform(link_at("a", "b", at(900))).unwrap();
+
+            let stats = registry.rejection_stats("b").unwrap();
+            assert_eq!((stats.late, stats.duplicates), (1, 1));
+            assert_eq!(registry.time_range("b"), Some((at(900), at(1000))));
+            assert_eq!(registry.rejection_stats("a"), None);
+
+            // Other frames keep the default of replacing and accepting any late transform
+            registry.add_transform(link_at("b", "c", at(1000))).unwrap();
+            registry.add_transform(link_at("b", "c", at(1000))).unwrap();
+            registry.add_transform(link_at("b", "c", at(0))).unwrap();
+            assert_eq!(registry.rejection_stats("c").unwrap(), Default::default());
+            assert_eq!(registry.snapshot().rejection_stats("b"), Some(stats));
+        }
     }
 }
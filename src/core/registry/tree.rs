@@ -0,0 +1,732 @@
+use crate::{
+    core::{
+        registry::slot::{Slot, SlotRef},
+        Buffer, BufferConfig, FrameUsage, Limit, LimitPolicy, Limits, MemoryUsage, MotionAnomaly,
+        ValidationPolicy,
+    },
+    errors::BufferError,
+    frame::FrameId,
+    geometry::Transform,
+};
+use std::{
+    collections::{HashMap, VecDeque},
+    iter,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+/// A frame as seen by the topology index.
+#[derive(Debug, Clone, Default)]
+struct FrameNode {
+    parent: Option<FrameId>,
+    depth: usize,
+    children: Vec<FrameId>,
+}
+
+/// The buffers of a registry together with an index of its current topology.
+///
+/// Buffers are keyed by child frame and shared behind an `Arc`, so cloning the tree only
+/// copies handles and writes copy a buffer only while a snapshot still holds it. The index
+/// records the parent of every frame, according to its most recent transform, and its
+/// depth below the root of its tree. It is updated on insert and lets lookups find the
+/// lowest common ancestor of two frames without walking either of them up to the root.
+///
+/// A sharded tree keeps every buffer behind its own lock, so `try_append` can insert into
+/// a frame whose topology does not change through a shared reference to the tree. Clones
+/// are never sharded: they hold each buffer as it was when the clone was taken.
+///
+/// Frames can be given their own `BufferConfig`; all other buffers are created with the
+/// default configuration passed to `insert`.
+///
+/// Inserts enforce the `Limits` of the tree, rejecting transforms or evicting older data once
+/// a cap is reached. The bytes held by all buffers are tracked as they change, so checking
+/// the byte cap does not visit every buffer.
+///
+/// Every change to the topology bumps a generation counter, so paths resolved against one
+/// version of the tree can tell whether they are still current.
+///
+/// Consecutive static links are folded into a single `StaticRun` per frame, kept up to date
+/// whenever a static transform is inserted, so lookups can cross them with one product.
+#[derive(Default)]
+pub(crate) struct FrameTree {
+    buffers: HashMap<FrameId, Slot>,
+    index: Arc<HashMap<FrameId, FrameNode>>,
+    configs: Arc<HashMap<FrameId, BufferConfig>>,
+    static_runs: Arc<HashMap<FrameId, StaticRun>>,
+    generation: u64,
+    limits: Limits,
+    validation: ValidationPolicy,
+    anomaly_hook: Option<AnomalyHook>,
+    /// The sum of `Buffer::memory_usage` over all buffers, kept up to date on every insert.
+    bytes: AtomicUsize,
+    #[cfg(feature = "async")]
+    sharded: bool,
+}
+
+/// A callback receiving every transform that exceeds the `MotionLimits` of its frame.
+pub(crate) type AnomalyHook = Arc<dyn Fn(&MotionAnomaly) + Send + Sync>;
+
+/// The product of the unbroken chain of static links above a frame.
+#[derive(Debug, Clone)]
+pub(crate) struct StaticRun {
+    /// The frame the run ends in, which is the parent of its topmost link.
+    pub top: FrameId,
+    /// The number of links in the run.
+    pub len: usize,
+    /// The transform from `top` down to the frame the run starts at.
+    pub transform: Transform,
+}
+
+impl Clone for FrameTree {
+    fn clone(&self) -> Self {
+        Self {
+            buffers: self.buffers.clone(),
+            index: Arc::clone(&self.index),
+            configs: Arc::clone(&self.configs),
+            static_runs: Arc::clone(&self.static_runs),
+            generation: self.generation,
+            limits: self.limits,
+            validation: self.validation,
+            anomaly_hook: self.anomaly_hook.clone(),
+            bytes: AtomicUsize::new(self.bytes.load(Ordering::Relaxed)),
+            #[cfg(feature = "async")]
+            sharded: false,
+        }
+    }
+}
+
+/// The outcome of `FrameTree::try_append`.
+#[cfg(feature = "async")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Append {
+    /// The transform was inserted.
+    Appended,
+    /// The transform was inserted, but expiring old transforms changed the parent of its
+    /// frame, which has to be updated through `reindex`.
+    Reindex,
+    /// The transform may change the topology and was not inserted.
+    Exclusive,
+}
+
+impl FrameTree {
+    /// Creates an empty tree that keeps every buffer behind its own lock.
+    #[cfg(feature = "async")]
+    pub fn sharded() -> Self {
+        Self {
+            sharded: true,
+            ..Self::default()
+        }
+    }
+
+    /// Returns the buffer holding the transforms into `frame`, locking it if it is sharded.
+    pub fn buffer(
+        &self,
+        frame: FrameId,
+    ) -> Option<SlotRef<'_>> {
+        self.buffers.get(&frame).map(Slot::load)
+    }
+
+    /// Iterates over every frame known to the index, including root frames.
+    pub fn frames(&self) -> impl Iterator<Item = FrameId> + '_ {
+        self.index.keys().copied()
+    }
+
+    /// Returns the parent of `frame` according to its most recent transform.
+    pub fn parent(
+        &self,
+        frame: FrameId,
+    ) -> Option<FrameId> {
+        self.index.get(&frame)?.parent
+    }
+
+    /// Returns the number of topology changes the tree has seen.
+    pub fn generation(&self) -> u64 {
+        self.generation
+    }
+
+    /// Iterates over `frame` and its ancestors, ending at the root of its tree.
+    pub fn ancestors(
+        &self,
+        frame: FrameId,
+    ) -> impl Iterator<Item = FrameId> + '_ {
+        iter::successors(Some(frame), |frame| self.parent(*frame))
+    }
+
+    /// Returns the frames whose most recent transform is parented to `frame`.
+    pub fn children(
+        &self,
+        frame: FrameId,
+    ) -> &[FrameId] {
+        self.index
+            .get(&frame)
+            .map_or(&[], |node| node.children.as_slice())
+    }
+
+    /// Returns the number of links between `frame` and the root of its tree.
+    pub fn depth(
+        &self,
+        frame: FrameId,
+    ) -> Option<usize> {
+        self.index.get(&frame).map(|node| node.depth)
+    }
+
+    /// Sets the configuration of the buffer holding the transforms into `frame`.
+    ///
+    /// An existing buffer is reconfigured in place; otherwise the configuration is used
+    /// once the first transform into `frame` arrives.
+    pub fn configure(
+        &mut self,
+        frame: FrameId,
+        config: BufferConfig,
+    ) {
+        Arc::make_mut(&mut self.configs).insert(frame, config);
+        if let Some(slot) = self.buffers.get_mut(&frame) {
+            let buffer = slot.get_mut();
+            let before = buffer.memory_usage();
+            buffer.reconfigure(config);
+            let bytes = self.bytes.get_mut();
+            *bytes = *bytes + buffer.memory_usage() - before;
+        }
+    }
+
+    /// Adds a transform to the buffer of its child frame and updates the index.
+    ///
+    /// # Returns
+    ///
+    /// Whether the topology of the tree changed.
+    ///
+    /// # Errors
+    ///
+    /// Returns `BufferError::FrameCycle` if the transform would make a frame its own ancestor,
+    /// `BufferError::LimitExceeded` if it names a frame past the cap on interned names, or
+    /// the error of `Buffer::try_insert` if the buffer of the frame rejects it.
+    pub fn insert(
+        &mut self,
+        t: Transform,
+        default_config: &BufferConfig,
+    ) -> Result<bool, BufferError> {
+        let child = t.child;
+        if t.parent == FrameId::OVERFLOW || child == FrameId::OVERFLOW {
+            let max = FrameId::max_names().unwrap_or_default();
+            return Err(BufferError::LimitExceeded(Limit::Names, max));
+        }
+
+        // A frame parented to itself never leads anywhere, so the index treats it as a root
+        let self_reference = t.parent == child;
+        if !self_reference
+            && self.parent(child) != Some(t.parent)
+            && self.is_ancestor(child, t.parent)
+        {
+            return Err(BufferError::FrameCycle(t.parent, child));
+        }
+
+        let new_buffer = (!self.buffers.contains_key(&child))
+            .then(|| Buffer::with_config(*self.configs.get(&child).unwrap_or(default_config)));
+        if self.limits.policy == LimitPolicy::Reject {
+            self.check_limits(&t, new_buffer.as_ref())?;
+        }
+
+        let evict = self.limits.policy == LimitPolicy::Evict;
+        let mut evicted = false;
+        if let Some(buffer) = new_buffer {
+            if let Some(max) = self.limits.max_frames.filter(|_| evict) {
+                while self.buffers.len() >= max && self.evict_frame(Some(child)) {
+                    evicted = true;
+                }
+            }
+            *self.bytes.get_mut() += buffer.memory_usage();
+            let slot = self.new_slot(buffer);
+            self.buffers.insert(child, slot);
+        }
+
+        let Some(buffer) = self.buffers.get_mut(&child).map(Slot::get_mut) else {
+            unreachable!("the buffer was just created");
+        };
+        let before = buffer.memory_usage();
+        let was_static = buffer.is_static();
+        let inserted = buffer.try_insert(t);
+        notify(self.anomaly_hook.as_ref(), &inserted);
+        inserted?;
+        if let Some(max) = self.limits.max_samples.filter(|_| evict) {
+            while buffer.len() > max {
+                buffer.pop_oldest();
+            }
+        }
+        let bytes = self.bytes.get_mut();
+        *bytes = *bytes + buffer.memory_usage() - before;
+
+        if let Some(max) = self.limits.max_bytes.filter(|_| evict) {
+            while *self.bytes.get_mut() > max && self.evict_frame(Some(child)) {
+                evicted = true;
+            }
+            self.shrink_to(child, max);
+        }
+
+        Ok(self.reindex(child, was_static) || evicted)
+    }
+
+    /// Sets the callback receiving every transform that exceeds the `MotionLimits` of
+    /// its frame, whether it is rejected or flagged.
+    pub fn set_anomaly_hook(
+        &mut self,
+        hook: AnomalyHook,
+    ) {
+        self.anomaly_hook = Some(hook);
+    }
+
+    /// Returns what happens to transforms that fail validation on insert.
+    pub fn validation(&self) -> ValidationPolicy {
+        self.validation
+    }
+
+    /// Sets what happens to transforms that fail validation on insert.
+    pub fn set_validation(
+        &mut self,
+        policy: ValidationPolicy,
+    ) {
+        self.validation = policy;
+    }
+
+    /// Sets the caps the tree enforces on insert.
+    ///
+    /// With `LimitPolicy::Evict`, data over the new caps is evicted right away; with
+    /// `LimitPolicy::Reject`, it is kept and only new transforms are rejected.
+    ///
+    /// # Returns
+    ///
+    /// Whether the topology of the tree changed.
+    pub fn set_limits(
+        &mut self,
+        limits: Limits,
+    ) -> bool {
+        self.limits = limits;
+        if limits.policy == LimitPolicy::Reject {
+            return false;
+        }
+
+        if let Some(max) = limits.max_samples {
+            let frames: Vec<FrameId> = self.buffers.keys().copied().collect();
+            for frame in frames {
+                self.shrink_by_samples(frame, max);
+            }
+        }
+
+        let mut evicted = false;
+        while limits
+            .max_frames
+            .is_some_and(|max| self.buffers.len() > max)
+            || limits
+                .max_bytes
+                .is_some_and(|max| *self.bytes.get_mut() > max)
+        {
+            if !self.evict_frame(None) {
+                break;
+            }
+            evicted = true;
+        }
+        evicted
+    }
+
+    /// Reports the memory held by the buffers of the tree.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        let frames: HashMap<FrameId, FrameUsage> = self
+            .buffers
+            .iter()
+            .map(|(frame, slot)| {
+                let buffer = slot.load();
+                let usage = FrameUsage {
+                    samples: buffer.len(),
+                    bytes: buffer.memory_usage(),
+                };
+                (*frame, usage)
+            })
+            .collect();
+        MemoryUsage {
+            total_bytes: self.bytes.load(Ordering::Relaxed),
+            frames,
+            interned_names: FrameId::interned_names(),
+            interned_bytes: FrameId::interned_bytes(),
+        }
+    }
+
+    /// Returns an error if inserting `t` would exceed one of the limits.
+    ///
+    /// `new_buffer` is the buffer that would be created for the child frame, if it has none.
+    fn check_limits(
+        &self,
+        t: &Transform,
+        new_buffer: Option<&Buffer>,
+    ) -> Result<(), BufferError> {
+        let limits = self.limits;
+        let (samples, growth) = match new_buffer {
+            Some(buffer) => {
+                if let Some(max) = limits.max_frames.filter(|max| self.buffers.len() >= *max) {
+                    return Err(BufferError::LimitExceeded(Limit::Frames, max));
+                }
+                (0, buffer.memory_usage() + buffer.growth(t))
+            }
+            None => match self.buffer(t.child) {
+                Some(buffer) if !buffer.adds_sample(t) => return Ok(()),
+                Some(buffer) => (buffer.len(), buffer.growth(t)),
+                None => (0, 0),
+            },
+        };
+
+        if let Some(max) = limits.max_samples.filter(|max| samples >= *max) {
+            return Err(BufferError::LimitExceeded(Limit::Samples, max));
+        }
+        let bytes = self.bytes.load(Ordering::Relaxed);
+        if let Some(max) = limits.max_bytes.filter(|max| bytes + growth > *max) {
+            return Err(BufferError::LimitExceeded(Limit::Bytes, max));
+        }
+        Ok(())
+    }
+
+    /// Removes the least recently updated frame other than `keep`.
+    ///
+    /// Dynamic frames go first, ordered by their newest transform; static frames only once
+    /// no dynamic frame is left.
+    ///
+    /// # Returns
+    ///
+    /// Whether a frame was removed.
+    fn evict_frame(
+        &mut self,
+        keep: Option<FrameId>,
+    ) -> bool {
+        let oldest = self
+            .buffers
+            .iter()
+            .filter(|(frame, _)| Some(**frame) != keep)
+            .map(|(frame, slot)| {
+                let buffer = slot.load();
+                let newest = buffer.latest().map(|tf| tf.timestamp);
+                (buffer.is_static(), newest, *frame)
+            })
+            .min();
+
+        match oldest {
+            Some((_, _, frame)) => {
+                self.remove(frame);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the buffer of `frame`, along with the frames it leaves without any link.
+    fn remove(
+        &mut self,
+        frame: FrameId,
+    ) {
+        let Some(slot) = self.buffers.remove(&frame) else {
+            return;
+        };
+        let (bytes, was_static) = {
+            let buffer = slot.load();
+            (buffer.memory_usage(), buffer.is_static())
+        };
+        *self.bytes.get_mut() -= bytes;
+
+        let parent = self.parent(frame);
+        self.set_parent(frame, None);
+        for frame in iter::once(frame).chain(parent) {
+            if self.children(frame).is_empty() && !self.buffers.contains_key(&frame) {
+                Arc::make_mut(&mut self.index).remove(&frame);
+            }
+        }
+        if was_static {
+            self.update_static_runs(frame);
+        }
+        self.generation += 1;
+    }
+
+    /// Drops the oldest transforms of `frame` until its buffer holds at most `max`.
+    fn shrink_by_samples(
+        &mut self,
+        frame: FrameId,
+        max: usize,
+    ) {
+        let Some(buffer) = self.buffers.get_mut(&frame).map(Slot::get_mut) else {
+            return;
+        };
+        let before = buffer.memory_usage();
+        while buffer.len() > max {
+            buffer.pop_oldest();
+        }
+        *self.bytes.get_mut() -= before - buffer.memory_usage();
+    }
+
+    /// Drops the oldest transforms of `frame` until the tree holds at most `max` bytes,
+    /// keeping at least its newest transform.
+    fn shrink_to(
+        &mut self,
+        frame: FrameId,
+        max: usize,
+    ) {
+        let Some(buffer) = self.buffers.get_mut(&frame).map(Slot::get_mut) else {
+            return;
+        };
+        let bytes = self.bytes.get_mut();
+        while *bytes > max && buffer.len() > 1 {
+            let before = buffer.memory_usage();
+            buffer.pop_oldest();
+            *bytes -= before - buffer.memory_usage();
+        }
+    }
+
+    /// Inserts a transform that leaves the topology unchanged, through a shared reference.
+    ///
+    /// Only a sharded tree can do this, and only for a dynamic transform into a known,
+    /// dynamic frame that keeps its parent. Everything else needs `insert`. The frame stays
+    /// locked while `publish` is called with its buffer, so writes into one frame are
+    /// published in order.
+    #[cfg(feature = "async")]
+    pub fn try_append(
+        &self,
+        t: Transform,
+        publish: impl FnOnce(&Arc<Buffer>),
+    ) -> Append {
+        let child = t.child;
+        let indexed_parent = self.parent(child);
+        if t.timestamp.nanoseconds == 0 || indexed_parent != Some(t.parent) || t.parent == child {
+            return Append::Exclusive;
+        }
+        let Some(mut buffer) = self.buffers.get(&child).and_then(Slot::lock) else {
+            return Append::Exclusive;
+        };
+        if buffer.is_static()
+            || self
+                .limits
+                .max_samples
+                .is_some_and(|max| buffer.len() >= max)
+        {
+            return Append::Exclusive;
+        }
+        // Rejected transforms are counted and reported by `insert`, and flagged ones only
+        // once inserted, as they may yet need `insert`
+        let Ok(anomaly) = buffer.screen(&t) else {
+            return Append::Exclusive;
+        };
+
+        // Reserve the growth up front, so concurrent writers cannot overshoot the byte cap
+        let growth = buffer.growth(&t);
+        let reserved = self
+            .bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |bytes| {
+                let max = self.limits.max_bytes.unwrap_or(usize::MAX);
+                (bytes + growth <= max).then_some(bytes + growth)
+            });
+        if reserved.is_err() {
+            return Append::Exclusive;
+        }
+
+        let buffer_mut = Arc::make_mut(&mut buffer);
+        let before = buffer_mut.memory_usage();
+        buffer_mut.insert_screened(t);
+        notify(self.anomaly_hook.as_ref(), &Ok(anomaly));
+        // Aging into an archive can take more than the reservation; the cap is then
+        // enforced on the next exclusive insert
+        let reserved = before + growth;
+        let after = buffer_mut.memory_usage();
+        if after > reserved {
+            self.bytes.fetch_add(after - reserved, Ordering::Relaxed);
+        } else {
+            self.bytes.fetch_sub(reserved - after, Ordering::Relaxed);
+        }
+        if buffer_mut.latest().map(|tf| tf.parent) != indexed_parent {
+            return Append::Reindex;
+        }
+        publish(&buffer);
+        Append::Appended
+    }
+
+    /// Returns a frozen copy of the tree with the buffer of `frame` replaced.
+    #[cfg(feature = "async")]
+    pub fn with_buffer(
+        &self,
+        frame: FrameId,
+        buffer: Arc<Buffer>,
+    ) -> Self {
+        let mut tree = self.clone();
+        tree.buffers.insert(frame, Slot::Frozen(buffer));
+        tree
+    }
+
+    /// Updates the index and the static runs after the buffer of `child` changed.
+    ///
+    /// # Returns
+    ///
+    /// Whether the topology of the tree changed.
+    pub fn reindex(
+        &mut self,
+        child: FrameId,
+        was_static: bool,
+    ) -> bool {
+        let (latest_parent, is_static) = match self.buffer(child) {
+            Some(buffer) => (
+                buffer
+                    .latest()
+                    .map(|tf| tf.parent)
+                    .filter(|parent| *parent != child),
+                buffer.is_static(),
+            ),
+            None => (None, false),
+        };
+        let indexed = self.index.get(&child);
+        let topology_changed = indexed.map(|node| node.parent) != Some(latest_parent);
+
+        if topology_changed {
+            self.set_parent(child, latest_parent);
+            self.generation += 1;
+        }
+        if was_static || is_static {
+            self.update_static_runs(child);
+        }
+        topology_changed
+    }
+
+    /// Creates the slot for a new buffer, sharded if the tree is.
+    fn new_slot(
+        &self,
+        buffer: Buffer,
+    ) -> Slot {
+        #[cfg(feature = "async")]
+        if self.sharded {
+            return Slot::sharded(buffer);
+        }
+        Slot::frozen(buffer)
+    }
+
+    /// Returns the run of static links that starts at `frame`, if its link is static.
+    pub fn static_run(
+        &self,
+        frame: FrameId,
+    ) -> Option<&StaticRun> {
+        self.static_runs.get(&frame)
+    }
+
+    /// Folds the static links above `frame` and every static frame below it.
+    ///
+    /// The run of a frame builds on the run of its parent, so once `frame` is updated its
+    /// static descendants are updated top-down. Dynamic frames end the runs below them and
+    /// are not descended into.
+    fn update_static_runs(
+        &mut self,
+        frame: FrameId,
+    ) {
+        let runs = Arc::make_mut(&mut self.static_runs);
+        let mut queue = VecDeque::from([frame]);
+
+        while let Some(frame) = queue.pop_front() {
+            let link = self
+                .buffers
+                .get(&frame)
+                .map(Slot::load)
+                .filter(|buffer| buffer.is_static())
+                .and_then(|buffer| buffer.latest().copied())
+                .filter(|link| link.parent != link.child);
+
+            let run = link.and_then(|link| match runs.get(&link.parent) {
+                Some(above) => Some(StaticRun {
+                    top: above.top,
+                    len: above.len + 1,
+                    transform: (above.transform * link).ok()?,
+                }),
+                None => Some(StaticRun {
+                    top: link.parent,
+                    len: 1,
+                    transform: link,
+                }),
+            });
+
+            match run {
+                Some(run) => runs.insert(frame, run),
+                None => runs.remove(&frame),
+            };
+
+            if let Some(node) = self.index.get(&frame) {
+                queue.extend(node.children.iter().copied().filter(|child| {
+                    self.buffers
+                        .get(child)
+                        .is_some_and(|slot| slot.load().is_static())
+                }));
+            }
+        }
+    }
+
+    /// Returns whether `ancestor` is `frame` or one of its ancestors.
+    fn is_ancestor(
+        &self,
+        ancestor: FrameId,
+        frame: FrameId,
+    ) -> bool {
+        let mut current = Some(frame);
+        while let Some(frame) = current {
+            if frame == ancestor {
+                return true;
+            }
+            current = self.parent(frame);
+        }
+        false
+    }
+
+    /// Re-parents `frame` in the index and updates the depth of its subtree.
+    fn set_parent(
+        &mut self,
+        frame: FrameId,
+        parent: Option<FrameId>,
+    ) {
+        let index = Arc::make_mut(&mut self.index);
+
+        let old_parent = index.entry(frame).or_default().parent.take();
+        if let Some(node) = old_parent.and_then(|old| index.get_mut(&old)) {
+            node.children.retain(|child| *child != frame);
+        }
+
+        let depth = match parent {
+            Some(parent) => {
+                let node = index.entry(parent).or_default();
+                node.children.push(frame);
+                node.depth + 1
+            }
+            None => 0,
+        };
+
+        if let Some(node) = index.get_mut(&frame) {
+            node.parent = parent;
+            node.depth = depth;
+        }
+
+        let mut queue = VecDeque::from([frame]);
+        while let Some(current) = queue.pop_front() {
+            let (depth, children) = match index.get(&current) {
+                Some(node) => (node.depth, node.children.clone()),
+                None => continue,
+            };
+            for child in children {
+                if let Some(node) = index.get_mut(&child) {
+                    node.depth = depth + 1;
+                }
+                queue.push_back(child);
+            }
+        }
+    }
+}
+
+/// Passes the anomaly of a screened transform, whether it was rejected or flagged, to
+/// `hook`.
+fn notify(
+    hook: Option<&AnomalyHook>,
+    screened: &Result<Option<MotionAnomaly>, BufferError>,
+) {
+    let anomaly = match screened {
+        Ok(Some(anomaly)) | Err(BufferError::MotionAnomaly(anomaly)) => anomaly,
+        _ => return,
+    };
+    if let Some(hook) = hook {
+        hook(anomaly);
+    }
+}
@@ -0,0 +1,195 @@
+//! An immutable, frozen view of a registry.
+//!
+//! This module provides the `Snapshot` struct, which captures the state of a `Registry`
+//! at one point in time. A snapshot shares its buffers with the registry it was taken
+//! from, so taking one only copies the per-frame handles, and cloning one is a reference
+//! count increment.
+//!
+//! Writers can keep inserting into the registry while a snapshot is alive: buffers are
+//! copied on write, so the snapshot never changes underneath its users. This makes it a
+//! good fit for planning cycles, where many lookups must all agree on the same state.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use std::time::Duration;
+//! use transforms::{
+//!     geometry::{Quaternion, Transform, Vector3},
+//!     time::Timestamp,
+//!     Registry,
+//! };
+//!
+//! let mut registry = Registry::new(Duration::from_secs(60));
+//! let timestamp = Timestamp::zero();
+//!
+//! let transform = Transform {
+//!     translation: Vector3::new(1.0, 0.0, 0.0),
+//!     rotation: Quaternion::identity(),
+//!     timestamp,
+//!     parent: "base".into(),
+//!     child: "sensor".into(),
+//! };
+//! registry.add_transform(transform.clone()).unwrap();
+//!
+//! // Freeze the registry for the duration of a planning cycle
+//! let snapshot = registry.snapshot();
+//!
+//! // Writers keep going
+//! let moved = Transform {
+//!     translation: Vector3::new(2.0, 0.0, 0.0),
+//!     ..transform.clone()
+//! };
+//! registry.add_transform(moved).unwrap();
+//!
+//! // The snapshot still reflects the state it was taken in
+//! let result = snapshot.get_transform("base", "sensor", timestamp).unwrap();
+//! assert_eq!(result, transform);
+//! # }
+//! ```
+
+use crate::{
+    core::{
+        registry::FrameTree, DecimationStats, InterpolationMode, LookupResult, MemoryUsage,
+        RejectionStats, TreePoses,
+    },
+    errors::TransformError,
+    frame::FrameId,
+    geometry::Transform,
+    time::Timestamp,
+    Registry,
+};
+use std::sync::Arc;
+
+/// An immutable view of the frame tree of a registry at one point in time.
+///
+/// Snapshots are obtained from `Registry::snapshot` or `RegistryReader::snapshot`. They
+/// offer the same lookup and introspection methods as the registry and are cheap to clone
+/// and send to other threads.
+#[derive(Clone)]
+pub struct Snapshot {
+    data: Arc<FrameTree>,
+}
+
+impl Snapshot {
+    pub(crate) fn new(data: Arc<FrameTree>) -> Self {
+        Self { data }
+    }
+
+    /// Retrieves a transform from the snapshot.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The source frame.
+    /// * `to` - The destination frame.
+    /// * `timestamp` - The timestamp for which the transform is requested.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the transform cannot be found.
+    pub fn get_transform(
+        &self,
+        from: impl Into<FrameId>,
+        to: impl Into<FrameId>,
+        timestamp: Timestamp,
+    ) -> Result<Transform, TransformError> {
+        Registry::process_get_transform(from.into(), to.into(), timestamp, None, &self.data, None)
+    }
+
+    /// Retrieves a transform from the snapshot, answering every link in `mode` rather than
+    /// the `InterpolationMode` its buffer is configured with.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the transform cannot be found.
+    pub fn get_transform_with(
+        &self,
+        from: impl Into<FrameId>,
+        to: impl Into<FrameId>,
+        timestamp: Timestamp,
+        mode: InterpolationMode,
+    ) -> Result<Transform, TransformError> {
+        Registry::process_get_transform(
+            from.into(),
+            to.into(),
+            timestamp,
+            Some(mode),
+            &self.data,
+            None,
+        )
+    }
+
+    /// Retrieves a transform from the snapshot, along with the frames it passed through and
+    /// how the transform of each link was obtained.
+    ///
+    /// # Errors
+    ///
+    /// Returns a `TransformError` if the transform cannot be found.
+    pub fn lookup(
+        &self,
+        from: impl Into<FrameId>,
+        to: impl Into<FrameId>,
+        timestamp: Timestamp,
+    ) -> Result<LookupResult, TransformError> {
+        Registry::process_lookup(from.into(), to.into(), timestamp, &self.data)
+    }
+
+    /// Lists every frame known to the snapshot, in sorted order.
+    pub fn frames(&self) -> Vec<FrameId> {
+        Registry::process_frames(&self.data)
+    }
+
+    /// Returns the parent of a frame according to its most recent transform.
+    ///
+    /// Returns `None` for root frames and frames the snapshot does not know about.
+    pub fn parent(
+        &self,
+        frame: impl Into<FrameId>,
+    ) -> Option<FrameId> {
+        Registry::process_parent(frame.into(), &self.data)
+    }
+
+    /// Returns the oldest and newest timestamps buffered for the transform into `frame`.
+    pub fn time_range(
+        &self,
+        frame: impl Into<FrameId>,
+    ) -> Option<(Timestamp, Timestamp)> {
+        Registry::process_time_range(frame.into(), &self.data)
+    }
+
+    /// Returns how many transforms into `frame` its `Decimation` had thinned out.
+    pub fn decimation_stats(
+        &self,
+        frame: impl Into<FrameId>,
+    ) -> Option<DecimationStats> {
+        Registry::process_decimation_stats(frame.into(), &self.data)
+    }
+
+    /// Returns how many transforms into `frame` its `BufferConfig` had turned away.
+    pub fn rejection_stats(
+        &self,
+        frame: impl Into<FrameId>,
+    ) -> Option<RejectionStats> {
+        Registry::process_rejection_stats(frame.into(), &self.data)
+    }
+
+    /// Evaluates the transform from `root` to every frame below it at one timestamp.
+    ///
+    /// Frames that cannot be resolved are reported in `TreePoses::unresolved`.
+    pub fn tree_at(
+        &self,
+        root: impl Into<FrameId>,
+        timestamp: Timestamp,
+    ) -> TreePoses {
+        Registry::process_tree_at(root.into(), timestamp, &self.data)
+    }
+
+    /// Reports the memory held by the buffers of the snapshot, per frame.
+    pub fn memory_usage(&self) -> MemoryUsage {
+        Registry::process_memory_usage(&self.data)
+    }
+}
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,120 @@
+#[cfg(not(feature = "async"))]
+#[cfg(test)]
+mod snapshot_tests {
+    use crate::{
+        geometry::{Quaternion, Transform, Vector3},
+        time::Timestamp,
+        Registry,
+    };
+    use std::time::Duration;
+
+    fn create_transform(
+        parent: &str,
+        child: &str,
+        x: f64,
+        timestamp: Timestamp,
+    ) -> Transform {
+        Transform {
+            translation: Vector3 { x, y: 0., z: 0. },
+            rotation: Quaternion::identity(),
+            timestamp,
+            parent: parent.into(),
+            child: child.into(),
+        }
+    }
+
+    #[test]
+    fn snapshot_is_frozen() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        registry
+            .add_transform(create_transform("a", "b", 1., t))
+            .unwrap();
+
+        let snapshot = registry.snapshot();
+
+        // Overwrite the existing sample and extend the tree
+        registry
+            .add_transform(create_transform("a", "b", 5., t))
+            .unwrap();
+        registry
+            .add_transform(create_transform("b", "c", 1., t))
+            .unwrap();
+
+        let r = snapshot.get_transform("a", "b", t);
+        assert!(r.is_ok(), "Snapshot returned Error, expected Ok");
+        assert_eq!(r.unwrap().translation.x, 1.);
+        assert!(snapshot.get_transform("a", "c", t).is_err());
+
+        let r = registry.get_transform("a", "b", t);
+        assert_eq!(r.unwrap().translation.x, 5.);
+        assert!(registry.get_transform("a", "c", t).is_ok());
+    }
+
+    #[test]
+    fn snapshot_clones_share_state() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        registry
+            .add_transform(create_transform("a", "b", 1., t))
+            .unwrap();
+
+        let snapshot = registry.snapshot();
+        let clone = snapshot.clone();
+
+        assert_eq!(
+            snapshot.get_transform("a", "b", t).unwrap(),
+            clone.get_transform("a", "b", t).unwrap()
+        );
+    }
+
+    #[test]
+    fn snapshot_introspection() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let t1 = Timestamp::now();
+        let t2 = (t1 + Duration::from_millis(100)).unwrap();
+        registry
+            .add_transform(create_transform("a", "b", 1., t1))
+            .unwrap();
+        registry
+            .add_transform(create_transform("a", "b", 2., t2))
+            .unwrap();
+        registry
+            .add_transform(create_transform("b", "c", 1., t1))
+            .unwrap();
+
+        let snapshot = registry.snapshot();
+        registry
+            .add_transform(create_transform("c", "d", 1., t1))
+            .unwrap();
+
+        assert_eq!(snapshot.frames(), vec!["a", "b", "c"]);
+        assert_eq!(registry.frames(), vec!["a", "b", "c", "d"]);
+
+        assert_eq!(snapshot.parent("c"), Some("b".into()));
+        assert_eq!(snapshot.parent("a"), None);
+        assert_eq!(snapshot.parent("d"), None);
+
+        assert_eq!(snapshot.time_range("b"), Some((t1, t2)));
+        assert_eq!(snapshot.time_range("a"), None);
+    }
+
+    #[test]
+    fn reader_snapshot_is_frozen() {
+        let mut registry = Registry::new(Duration::from_secs(10));
+        let t = Timestamp::now();
+        let reader = registry.reader();
+        registry
+            .add_transform(create_transform("a", "b", 1., t))
+            .unwrap();
+
+        let snapshot = reader.snapshot();
+        registry
+            .add_transform(create_transform("b", "c", 1., t))
+            .unwrap();
+
+        assert!(snapshot.get_transform("a", "c", t).is_err());
+        assert!(reader.get_transform("a", "c", t).is_ok());
+        assert!(reader.snapshot().get_transform("a", "c", t).is_ok());
+    }
+}
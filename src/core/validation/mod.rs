@@ -0,0 +1,138 @@
+//! Validation of transforms as they are added to a registry.
+//!
+//! This module provides `ValidationPolicy`, set with `Registry::set_validation`, which
+//! decides what happens to a transform that would otherwise only fail later, deep inside a
+//! lookup: one with a non-finite translation, a zero or non-unit rotation, an empty frame
+//! name, or a frame parented to itself.
+//!
+//! # Examples
+//!
+//! ```
+//! # #[cfg(not(feature = "async"))]
+//! # {
+//! use std::time::Duration;
+//! use transforms::{
+//!     core::ValidationPolicy,
+//!     errors::BufferError,
+//!     geometry::{Quaternion, Transform},
+//!     time::Timestamp,
+//!     Registry,
+//! };
+//!
+//! let mut registry = Registry::new(Duration::from_secs(60));
+//! let mut transform = Transform::identity();
+//! transform.parent = "a".into();
+//! transform.child = "b".into();
+//! transform.rotation = Quaternion {
+//!     w: 2.0,
+//!     x: 0.0,
+//!     y: 0.0,
+//!     z: 0.0,
+//! };
+//!
+//! // Rejected by default
+//! let result = registry.add_transform(transform);
+//! assert!(matches!(result, Err(BufferError::NonUnitRotation(..))));
+//!
+//! // Normalized on insert if asked to
+//! registry.set_validation(ValidationPolicy::Normalize);
+//! registry.add_transform(transform).unwrap();
+//! let stored = registry.get_transform("a", "b", Timestamp::zero()).unwrap();
+//! assert_eq!(stored.rotation, Quaternion::identity());
+//! # }
+//! ```
+
+use crate::{
+    errors::{BufferError, TransformError},
+    geometry::Transform,
+};
+
+/// How far the norm of a rotation may be from one before it counts as non-unit.
+///
+/// Generous enough for quaternions normalized in single precision.
+pub const UNIT_NORM_TOLERANCE: f64 = 1e-6;
+
+/// What a registry does with a transform that fails validation on insert.
+///
+/// Registries reject invalid transforms by default. Earlier versions inserted them as they
+/// were; `Warn` keeps that behaviour while logging each one.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ValidationPolicy {
+    /// The transform is rejected with the `BufferError` describing the problem.
+    #[default]
+    Reject,
+    /// A non-unit rotation is normalized and the transform inserted. Problems that cannot
+    /// be repaired are rejected as with `Reject`.
+    Normalize,
+    /// The problem is logged as a warning and the transform inserted as it is.
+    Warn,
+}
+
+/// Checks a transform before it is inserted, according to `policy`.
+///
+/// # Returns
+///
+/// The transform to insert, which `ValidationPolicy::Normalize` may have repaired.
+///
+/// # Errors
+///
+/// Returns the `BufferError` of the first problem found, unless the policy accepts it.
+pub(crate) fn validate(
+    mut transform: Transform,
+    policy: ValidationPolicy,
+) -> Result<Transform, BufferError> {
+    let Err(error) = check(&transform) else {
+        return Ok(transform);
+    };
+
+    match (policy, &error) {
+        (ValidationPolicy::Warn, _) => {
+            log::warn!("Inserting invalid transform: {error}");
+            Ok(transform)
+        }
+        (ValidationPolicy::Normalize, BufferError::NonUnitRotation(..)) => {
+            transform.rotation = transform
+                .rotation
+                .normalize()
+                .map_err(TransformError::from)?;
+            Ok(transform)
+        }
+        _ => Err(error),
+    }
+}
+
+/// Finds the first problem with a transform, if any.
+fn check(transform: &Transform) -> Result<(), BufferError> {
+    let (parent, child) = (transform.parent, transform.child);
+    if parent.as_str().is_empty() || child.as_str().is_empty() {
+        return Err(BufferError::EmptyFrameName(parent, child));
+    }
+    if parent == child {
+        return Err(BufferError::SelfParented(child));
+    }
+
+    let translation = transform.translation;
+    if ![translation.x, translation.y, translation.z]
+        .iter()
+        .all(|value| value.is_finite())
+    {
+        return Err(BufferError::NonFiniteTranslation(parent, child));
+    }
+
+    let rotation = transform.rotation;
+    if ![rotation.w, rotation.x, rotation.y, rotation.z]
+        .iter()
+        .all(|value| value.is_finite())
+    {
+        return Err(BufferError::NonFiniteRotation(parent, child));
+    }
+    let norm = rotation.norm();
+    if norm < f64::EPSILON {
+        return Err(BufferError::ZeroRotation(parent, child));
+    }
+    if (norm - 1.0).abs() > UNIT_NORM_TOLERANCE {
+        return Err(BufferError::NonUnitRotation(parent, child, norm));
+    }
+
+    Ok(())
+}
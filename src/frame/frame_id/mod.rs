@@ -0,0 +1,310 @@
+use core::{cmp::Ordering, fmt};
+use std::{
+    collections::HashMap,
+    sync::{OnceLock, PoisonError, RwLock},
+};
+
+/// An interned frame name.
+///
+/// A `FrameId` is a small, copyable handle to a frame name. Resolving a name to a `FrameId`
+/// happens once; afterwards ids are compared, hashed and copied as plain integers, which
+/// keeps transforms free of owned strings and frame lookups free of string hashing.
+///
+/// Names are interned in a process-wide table, so the same name always yields the same id
+/// and an id can always be turned back into its name. Interned names are never freed,
+/// which suits the small, long-lived set of frames a robot describes. To stay safe from a
+/// source of unbounded names, cap the table with `FrameId::set_max_names`: names past the
+/// cap resolve to `FrameId::OVERFLOW`, which registries reject.
+///
+/// Most APIs accept anything that converts into a `FrameId`, so names can be used wherever
+/// an id is expected.
+///
+/// # Examples
+///
+/// ```
+/// use transforms::frame::FrameId;
+///
+/// let base = FrameId::new("base");
+/// let same: FrameId = "base".into();
+///
+/// assert_eq!(base, same);
+/// assert_eq!(base, "base");
+/// assert_eq!(base.as_str(), "base");
+/// assert_eq!(base.to_string(), "base");
+/// ```
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct FrameId(u32);
+
+struct Interner {
+    ids: HashMap<&'static str, FrameId>,
+    names: Vec<&'static str>,
+    bytes: usize,
+    max_names: Option<usize>,
+}
+
+/// The name `FrameId::OVERFLOW` displays as. It is never interned, so a frame of the same
+/// name keeps an id of its own.
+const OVERFLOW_NAME: &str = "<overflow>";
+
+impl Interner {
+    fn global() -> &'static RwLock<Interner> {
+        static INTERNER: OnceLock<RwLock<Interner>> = OnceLock::new();
+        INTERNER.get_or_init(|| {
+            // The empty name is always interned first so that it can serve as the default
+            RwLock::new(Interner {
+                ids: HashMap::from([("", FrameId(0))]),
+                names: vec![""],
+                bytes: 0,
+                max_names: None,
+            })
+        })
+    }
+}
+
+impl FrameId {
+    /// The id of every name interned past the cap set with `FrameId::set_max_names`.
+    ///
+    /// Registries reject transforms from or to it with `BufferError::LimitExceeded`.
+    pub const OVERFLOW: FrameId = FrameId(u32::MAX);
+
+    /// Returns the id of `name`, interning it if it has not been seen before.
+    ///
+    /// Once the cap set with `FrameId::set_max_names` is reached, names that have not been
+    /// seen before are not interned and yield `FrameId::OVERFLOW` instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::frame::FrameId;
+    ///
+    /// let camera = FrameId::new("camera");
+    /// assert_eq!(FrameId::new("camera"), camera);
+    /// assert_ne!(FrameId::new("lidar"), camera);
+    /// ```
+    pub fn new(name: &str) -> Self {
+        if let Some(id) = Self::get(name) {
+            return id;
+        }
+
+        let mut interner = Interner::global()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner);
+        if let Some(id) = interner.ids.get(name) {
+            return *id;
+        }
+
+        // The empty name is not counted towards the cap
+        if interner
+            .max_names
+            .is_some_and(|max| interner.names.len() > max)
+        {
+            return Self::OVERFLOW;
+        }
+
+        let name: &'static str = Box::leak(name.to_owned().into_boxed_str());
+        let id = FrameId(interner.names.len() as u32);
+        interner.names.push(name);
+        interner.ids.insert(name, id);
+        interner.bytes += name.len() + ENTRY_BYTES;
+        id
+    }
+
+    /// Caps the number of distinct names the process interns, or lifts the cap with
+    /// `None`. Names interned before stay valid even if they exceed a new cap.
+    ///
+    /// The table is shared by every registry in the process, so the cap is global too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::frame::FrameId;
+    ///
+    /// FrameId::set_max_names(Some(FrameId::interned_names()));
+    /// assert_eq!(FrameId::new("one_name_too_many"), FrameId::OVERFLOW);
+    /// FrameId::set_max_names(None);
+    /// assert_ne!(FrameId::new("one_name_too_many"), FrameId::OVERFLOW);
+    /// ```
+    pub fn set_max_names(max: Option<usize>) {
+        Interner::global()
+            .write()
+            .unwrap_or_else(PoisonError::into_inner)
+            .max_names = max;
+    }
+
+    /// Returns the cap set with `FrameId::set_max_names`, if any.
+    pub fn max_names() -> Option<usize> {
+        Interner::global()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .max_names
+    }
+
+    /// Returns how many distinct names the process has interned, not counting the empty
+    /// name.
+    pub fn interned_names() -> usize {
+        Interner::global()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .names
+            .len()
+            - 1
+    }
+
+    /// Returns an estimate of the bytes the interned names hold, which are never freed.
+    pub fn interned_bytes() -> usize {
+        Interner::global()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .bytes
+    }
+
+    /// Returns the id of `name` if it has been interned, without interning it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::frame::FrameId;
+    ///
+    /// let map = FrameId::new("map");
+    /// assert_eq!(FrameId::get("map"), Some(map));
+    /// assert_eq!(FrameId::get("never_used_anywhere"), None);
+    /// ```
+    pub fn get(name: &str) -> Option<Self> {
+        Interner::global()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .ids
+            .get(name)
+            .copied()
+    }
+
+    /// Returns the name this id was interned from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::frame::FrameId;
+    ///
+    /// assert_eq!(FrameId::new("odom").as_str(), "odom");
+    /// ```
+    pub fn as_str(self) -> &'static str {
+        if self == Self::OVERFLOW {
+            return OVERFLOW_NAME;
+        }
+        Interner::global()
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .names[self.0 as usize]
+    }
+}
+
+/// The bytes each interned name holds besides the name itself: its entries in the id map
+/// and in the name table.
+const ENTRY_BYTES: usize = size_of::<(&str, FrameId)>() + size_of::<&str>();
+
+impl Default for FrameId {
+    /// Returns the id of the empty frame name.
+    fn default() -> Self {
+        FrameId(0)
+    }
+}
+
+impl PartialOrd for FrameId {
+    fn partial_cmp(
+        &self,
+        other: &Self,
+    ) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for FrameId {
+    /// Frames are ordered by name, as their names would be.
+    fn cmp(
+        &self,
+        other: &Self,
+    ) -> Ordering {
+        if self.0 == other.0 {
+            return Ordering::Equal;
+        }
+        self.as_str().cmp(other.as_str())
+    }
+}
+
+impl From<&str> for FrameId {
+    fn from(name: &str) -> Self {
+        FrameId::new(name)
+    }
+}
+
+impl From<&String> for FrameId {
+    fn from(name: &String) -> Self {
+        FrameId::new(name)
+    }
+}
+
+impl From<String> for FrameId {
+    fn from(name: String) -> Self {
+        FrameId::new(&name)
+    }
+}
+
+impl From<&FrameId> for FrameId {
+    fn from(id: &FrameId) -> Self {
+        *id
+    }
+}
+
+impl From<FrameId> for String {
+    fn from(id: FrameId) -> Self {
+        id.as_str().to_string()
+    }
+}
+
+impl PartialEq<str> for FrameId {
+    fn eq(
+        &self,
+        other: &str,
+    ) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for FrameId {
+    fn eq(
+        &self,
+        other: &&str,
+    ) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl PartialEq<String> for FrameId {
+    fn eq(
+        &self,
+        other: &String,
+    ) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl fmt::Debug for FrameId {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        fmt::Debug::fmt(self.as_str(), f)
+    }
+}
+
+impl fmt::Display for FrameId {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests;
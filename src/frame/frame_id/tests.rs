@@ -0,0 +1,68 @@
+#[cfg(test)]
+mod frame_id_tests {
+    use crate::frame::FrameId;
+    use std::{collections::HashSet, thread};
+
+    #[test]
+    fn interning() {
+        let a = FrameId::new("frame_id_tests_a");
+        let b = FrameId::new("frame_id_tests_b");
+
+        assert_eq!(a, FrameId::new("frame_id_tests_a"));
+        assert_ne!(a, b);
+        assert_eq!(a.as_str(), "frame_id_tests_a");
+        assert_eq!(FrameId::from(String::from("frame_id_tests_b")), b);
+    }
+
+    #[test]
+    fn get_does_not_intern() {
+        assert_eq!(FrameId::get("frame_id_tests_unseen"), None);
+        let id = FrameId::new("frame_id_tests_seen");
+        assert_eq!(FrameId::get("frame_id_tests_seen"), Some(id));
+    }
+
+    #[test]
+    fn default_is_empty_name() {
+        assert_eq!(FrameId::default(), "");
+        assert_eq!(FrameId::new(""), FrameId::default());
+    }
+
+    #[test]
+    fn ordering_follows_names() {
+        // Interned in reverse order so ids and names disagree
+        let z = FrameId::new("frame_id_tests_z");
+        let y = FrameId::new("frame_id_tests_y");
+
+        assert!(y < z);
+        let mut frames = vec![z, y];
+        frames.sort();
+        assert_eq!(frames, vec!["frame_id_tests_y", "frame_id_tests_z"]);
+    }
+
+    #[test]
+    fn concurrent_interning() {
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                thread::spawn(|| {
+                    (0..100)
+                        .map(|i| FrameId::new(&format!("frame_id_tests_concurrent_{i}")))
+                        .collect::<Vec<_>>()
+                })
+            })
+            .collect();
+
+        let results: Vec<_> = handles.into_iter().map(|h| h.join().unwrap()).collect();
+        for result in &results {
+            assert_eq!(result, &results[0]);
+        }
+        let unique: HashSet<_> = results[0].iter().collect();
+        assert_eq!(unique.len(), 100);
+    }
+
+    #[test]
+    fn formatting() {
+        let id = FrameId::new("frame_id_tests_fmt");
+        assert_eq!(format!("{}", id), "frame_id_tests_fmt");
+        assert_eq!(format!("{:?}", id), "\"frame_id_tests_fmt\"");
+    }
+}
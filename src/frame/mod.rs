@@ -0,0 +1,2 @@
+pub mod frame_id;
+pub use frame_id::FrameId;
@@ -1,5 +1,6 @@
 use crate::{
     errors::TransformError,
+    frame::FrameId,
     geometry::{Quaternion, Vector3},
     time::Timestamp,
     Transform, Transformable,
@@ -32,7 +33,7 @@ mod error;
 ///     z: 0.0,
 /// };
 /// let timestamp = Timestamp::now();
-/// let frame = String::from("a");
+/// let frame = "a".into();
 ///
 /// let point = Point {
 ///     position,
@@ -49,7 +50,7 @@ pub struct Point {
     pub position: Vector3,
     pub orientation: Quaternion,
     pub timestamp: Timestamp,
-    pub frame: String,
+    pub frame: FrameId,
 }
 
 /// The `Transformable` trait defines an interface for objects that can be transformed
@@ -79,7 +80,7 @@ pub struct Point {
 ///     z: 0.0,
 /// };
 /// let timestamp = Timestamp { nanoseconds: 0 };
-/// let frame = String::from("b");
+/// let frame = "b".into();
 ///
 /// let mut point = Point {
 ///     position,
@@ -128,8 +129,8 @@ impl Transformable for Point {
         }
         if self.timestamp != transform.timestamp {
             return Err(TransformError::TimestampMismatch(
-                self.timestamp.nanoseconds as f64,
-                transform.timestamp.nanoseconds as f64,
+                self.timestamp,
+                transform.timestamp,
             ));
         }
         self.position = transform.rotation.rotate_vector(self.position) + transform.translation;
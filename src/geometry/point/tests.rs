@@ -25,7 +25,7 @@ mod point_tests {
             position: v,
             orientation: q,
             timestamp: t,
-            frame: f,
+            frame: f.into(),
         };
     }
 }
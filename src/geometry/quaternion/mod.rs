@@ -235,7 +235,10 @@ impl Quaternion {
     }
     /// Performs spherical linear interpolation (slerp) between two quaternions.
     ///
-    /// Interpolates between `self` and `other` by the factor `t`.
+    /// Interpolates between `self` and `other` by the factor `t`, along the shortest arc
+    /// between the rotations they describe. As q and -q describe the same rotation, `other`
+    /// is negated first if it lies on the opposite hemisphere, so the result may be the
+    /// negation of `other` at `t = 1`.
     ///
     /// # Examples
     ///
@@ -273,7 +276,15 @@ impl Quaternion {
         other: Quaternion,
         t: f64,
     ) -> Quaternion {
-        let dot = self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z;
+        let mut dot = self.w * other.w + self.x * other.x + self.y * other.y + self.z * other.z;
+
+        // Take the shortest arc across the double cover
+        let other = if dot < 0.0 {
+            dot = -dot;
+            other.scale(-1.0)
+        } else {
+            other
+        };
 
         let dot = dot.clamp(-1.0, 1.0);
         let theta = dot.acos();
@@ -288,6 +299,184 @@ impl Quaternion {
 
         self.scale(scale_self) + other.scale(scale_other)
     }
+
+    /// Returns whether `self` and `other` describe the same rotation within `epsilon`.
+    ///
+    /// Unlike `==` and `abs_diff_eq`, which compare the components, this treats q and -q
+    /// as equal, since both describe the same rotation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::geometry::Quaternion;
+    ///
+    /// let q = Quaternion {
+    ///     w: (0.5_f64).sqrt(),
+    ///     x: 0.0,
+    ///     y: (0.5_f64).sqrt(),
+    ///     z: 0.0,
+    /// };
+    /// let negated = q.scale(-1.0);
+    /// assert_ne!(q, negated);
+    /// assert!(q.rotation_eq(&negated, f64::EPSILON));
+    /// ```
+    #[inline]
+    pub fn rotation_eq(
+        &self,
+        other: &Quaternion,
+        epsilon: f64,
+    ) -> bool {
+        self.abs_diff_eq(other, epsilon) || self.abs_diff_eq(&other.scale(-1.0), epsilon)
+    }
+
+    /// Computes the logarithm of a unit quaternion.
+    ///
+    /// The result is a pure quaternion, with a real part of zero, whose vector part is the
+    /// rotation axis scaled by half the rotation angle.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::geometry::Quaternion;
+    /// # use approx::assert_relative_eq;
+    ///
+    /// let q = Quaternion {
+    ///     w: (0.5_f64).sqrt(),
+    ///     x: 0.0,
+    ///     y: 0.0,
+    ///     z: (0.5_f64).sqrt(),
+    /// };
+    /// let ln = q.ln();
+    /// assert_relative_eq!(ln.z, std::f64::consts::FRAC_PI_4);
+    /// assert_relative_eq!(ln.exp().z, q.z);
+    /// ```
+    #[inline]
+    pub fn ln(self) -> Quaternion {
+        let sin_half = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        if sin_half < f64::EPSILON {
+            return Quaternion {
+                w: 0.0,
+                x: 0.0,
+                y: 0.0,
+                z: 0.0,
+            };
+        }
+        let half_angle = sin_half.atan2(self.w);
+        let factor = half_angle / sin_half;
+        Quaternion {
+            w: 0.0,
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+
+    /// Computes the exponential of a pure quaternion, the inverse of `ln`.
+    ///
+    /// The real part of `self` is ignored.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::geometry::Quaternion;
+    ///
+    /// let zero = Quaternion {
+    ///     w: 0.0,
+    ///     x: 0.0,
+    ///     y: 0.0,
+    ///     z: 0.0,
+    /// };
+    /// assert_eq!(zero.exp(), Quaternion::identity());
+    /// ```
+    #[inline]
+    pub fn exp(self) -> Quaternion {
+        let half_angle = (self.x * self.x + self.y * self.y + self.z * self.z).sqrt();
+        if half_angle < f64::EPSILON {
+            return Quaternion::identity();
+        }
+        let factor = half_angle.sin() / half_angle;
+        Quaternion {
+            w: half_angle.cos(),
+            x: self.x * factor,
+            y: self.y * factor,
+            z: self.z * factor,
+        }
+    }
+
+    /// Performs spherical quadrangle interpolation (SQUAD) between two quaternions.
+    ///
+    /// Interpolates between `self` and `other` by the factor `t`, bending the path towards
+    /// the control points `a` and `b`. With controls from `squad_control` at every sample,
+    /// consecutive segments join with a continuous angular velocity. When `a` is `self` and
+    /// `b` is `other`, this is `slerp`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::geometry::Quaternion;
+    /// # use approx::assert_relative_eq;
+    ///
+    /// let q1 = Quaternion::identity();
+    /// let q2 = Quaternion {
+    ///     w: (0.5_f64).sqrt(),
+    ///     x: (0.5_f64).sqrt(),
+    ///     y: 0.0,
+    ///     z: 0.0,
+    /// };
+    /// let result = q1.squad(q1, q2, q2, 0.3);
+    /// assert_relative_eq!(result.x, q1.slerp(q2, 0.3).x, epsilon = 1e-12);
+    /// ```
+    #[inline]
+    pub fn squad(
+        self,
+        a: Quaternion,
+        b: Quaternion,
+        other: Quaternion,
+        t: f64,
+    ) -> Quaternion {
+        self.slerp(other, t)
+            .slerp(a.slerp(b, t), 2.0 * t * (1.0 - t))
+    }
+
+    /// Computes the SQUAD control point at `self`, between its neighbours `previous`
+    /// and `next`.
+    ///
+    /// Neighbours on the opposite hemisphere are negated first, as they describe the same
+    /// rotation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::geometry::Quaternion;
+    /// # use approx::assert_relative_eq;
+    ///
+    /// // At constant angular velocity the control point is the sample itself
+    /// let angle = |a: f64| Quaternion {
+    ///     w: (a / 2.0).cos(),
+    ///     x: 0.0,
+    ///     y: 0.0,
+    ///     z: (a / 2.0).sin(),
+    /// };
+    /// let control = angle(0.2).squad_control(angle(0.1), angle(0.3));
+    /// assert_relative_eq!(control.z, angle(0.2).z, epsilon = 1e-12);
+    /// ```
+    #[inline]
+    pub fn squad_control(
+        self,
+        previous: Quaternion,
+        next: Quaternion,
+    ) -> Quaternion {
+        let inverse = self.conjugate();
+        let towards = |neighbour: Quaternion| {
+            let relative = inverse * neighbour;
+            if relative.w < 0.0 {
+                relative.scale(-1.0).ln()
+            } else {
+                relative.ln()
+            }
+        };
+        self * (towards(previous) + towards(next)).scale(-0.25).exp()
+    }
 }
 
 impl Add for Quaternion {
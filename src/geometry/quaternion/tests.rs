@@ -4,7 +4,7 @@ mod quaternion_tests {
         errors::QuaternionError,
         geometry::{Quaternion, Vector3},
     };
-    use approx::assert_relative_eq;
+    use approx::{assert_relative_eq, AbsDiffEq};
     use core::f64;
 
     #[test]
@@ -339,4 +339,106 @@ mod quaternion_tests {
         assert_relative_eq!(result.y, expected.y, epsilon = f64::EPSILON);
         assert_relative_eq!(result.z, expected.z, epsilon = f64::EPSILON);
     }
+
+    /// A rotation by `angle` radians about `axis`.
+    fn about(
+        axis: Vector3,
+        angle: f64,
+    ) -> Quaternion {
+        let sin = (angle / 2.0).sin();
+        Quaternion {
+            w: (angle / 2.0).cos(),
+            x: axis.x * sin,
+            y: axis.y * sin,
+            z: axis.z * sin,
+        }
+    }
+
+    #[test]
+    fn ln_and_exp() {
+        let axis = Vector3::new(1.0, 2.0, 2.0) / 3.0;
+        let q = about(axis, 1.2);
+
+        let ln = q.ln();
+        assert_relative_eq!(ln.w, 0.0);
+        assert_relative_eq!(ln.x, axis.x * 0.6, epsilon = 1e-12);
+        assert_relative_eq!(ln.y, axis.y * 0.6, epsilon = 1e-12);
+        assert_relative_eq!(ln.z, axis.z * 0.6, epsilon = 1e-12);
+
+        let back = ln.exp();
+        assert_relative_eq!(back.w, q.w, epsilon = 1e-12);
+        assert_relative_eq!(back.x, q.x, epsilon = 1e-12);
+        assert_relative_eq!(back.y, q.y, epsilon = 1e-12);
+        assert_relative_eq!(back.z, q.z, epsilon = 1e-12);
+
+        assert_eq!(Quaternion::identity().ln().exp(), Quaternion::identity());
+    }
+
+    #[test]
+    fn squad() {
+        let axis = Vector3::new(0.0, 0.6, 0.8);
+        let q0 = about(axis, 0.0);
+        let q1 = about(axis, 0.4);
+        let q2 = about(axis, 0.8);
+        let q3 = about(axis, 1.2);
+
+        // At constant angular velocity, SQUAD follows the rotation exactly
+        let a = q1.squad_control(q0, q2);
+        let b = q2.squad_control(q1, q3);
+        let result = q1.squad(a, b, q2, 0.25);
+        let expected = about(axis, 0.5);
+        assert_relative_eq!(result.w, expected.w, epsilon = 1e-12);
+        assert_relative_eq!(result.x, expected.x, epsilon = 1e-12);
+        assert_relative_eq!(result.y, expected.y, epsilon = 1e-12);
+        assert_relative_eq!(result.z, expected.z, epsilon = 1e-12);
+
+        // Negated neighbours describe the same rotation
+        let flipped = q1.squad_control(q0.scale(-1.0), q2);
+        assert_relative_eq!(flipped.w, a.w, epsilon = 1e-12);
+        assert_relative_eq!(flipped.y, a.y, epsilon = 1e-12);
+
+        // The path always passes through both samples
+        let bent = q1.squad(about(Vector3::unit_x(), 0.3), b, q2, 0.0);
+        assert_relative_eq!(bent.w, q1.w, epsilon = 1e-12);
+        assert_relative_eq!(bent.y, q1.y, epsilon = 1e-12);
+        let bent = q1.squad(about(Vector3::unit_x(), 0.3), b, q2, 1.0);
+        assert_relative_eq!(bent.w, q2.w, epsilon = 1e-12);
+        assert_relative_eq!(bent.z, q2.z, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn slerp_takes_the_shortest_arc() {
+        let axis = Vector3::unit_z();
+        let from = about(axis, 0.2);
+        // The same rotation as about(axis, 0.6), across the double cover
+        let to = about(axis, 0.6).scale(-1.0);
+        assert!(to.w < 0.0);
+
+        let result = from.slerp(to, 0.5);
+        let expected = about(axis, 0.4);
+        assert!(
+            result.rotation_eq(&expected, 1e-12),
+            "Slerp across the double cover went the long way: {:?}",
+            result
+        );
+        assert_relative_eq!(result.norm(), 1.0, epsilon = 1e-12);
+
+        // The endpoints are the same rotations as the inputs
+        assert!(from.slerp(to, 0.0).rotation_eq(&from, 1e-12));
+        assert!(from.slerp(to, 1.0).rotation_eq(&to, 1e-12));
+
+        // Interpolating a rotation towards its own negation stays put
+        let still = from.slerp(from.scale(-1.0), 0.5);
+        assert!(still.rotation_eq(&from, 1e-12));
+    }
+
+    #[test]
+    fn rotation_eq() {
+        let q = about(Vector3::new(0.6, 0.0, 0.8), 1.0);
+        assert!(q.rotation_eq(&q, f64::EPSILON));
+        assert!(q.rotation_eq(&q.scale(-1.0), f64::EPSILON));
+        assert!(!q.abs_diff_eq(&q.scale(-1.0), f64::EPSILON));
+        assert!(!q.rotation_eq(&q.conjugate(), 1e-6));
+        assert!(q.rotation_eq(&(q + q.scale(1e-9)), 1e-6));
+    }
 }
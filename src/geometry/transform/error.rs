@@ -1,10 +1,14 @@
-use crate::errors::{QuaternionError, TimestampError};
+use crate::{
+    errors::{QuaternionError, TimestampError},
+    frame::FrameId,
+    time::Timestamp,
+};
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 pub enum TransformError {
     #[error("Transform timestamps do not match (lhs: {0}, rhs: {1})")]
-    TimestampMismatch(f64, f64),
+    TimestampMismatch(Timestamp, Timestamp),
 
     #[error("Cannot multiply transforms with the same frame")]
     SameFrameMultiplication,
@@ -13,7 +17,30 @@ pub enum TransformError {
     IncompatibleFrames,
 
     #[error("Transform not found from {0} to {1}")]
-    NotFound(String, String),
+    NotFound(FrameId, FrameId),
+
+    /// The frame has never been part of a transform.
+    #[error("Frame {0} is unknown")]
+    UnknownFrame(FrameId),
+
+    /// The frames are known but not connected by any chain of transforms at the timestamp.
+    #[error("Frames {0} and {1} are not connected")]
+    Disconnected(FrameId, FrameId),
+
+    /// The link from parent `{0}` to child `{1}` was requested at `{2}`, before its oldest
+    /// transform; its transforms range from `{3}` to `{4}`.
+    #[error("Transform from {0} to {1} at {2} is older than its available range {3} to {4}")]
+    TooOld(FrameId, FrameId, Timestamp, Timestamp, Timestamp),
+
+    /// The link from parent `{0}` to child `{1}` was requested at `{2}`, after its newest
+    /// transform; its transforms range from `{3}` to `{4}`.
+    #[error("Transform from {0} to {1} at {2} is newer than its available range {3} to {4}")]
+    TooNew(FrameId, FrameId, Timestamp, Timestamp, Timestamp),
+
+    /// The link from parent `{0}` to child `{1}` cannot answer at `{2}` although it lies
+    /// within its range from `{3}` to `{4}`, such as inside a gap or in exact mode.
+    #[error("Transform from {0} to {1} at {2} is unavailable within its range {3} to {4}")]
+    LinkUnavailable(FrameId, FrameId, Timestamp, Timestamp, Timestamp),
 
     #[error("Transform tree is empty")]
     TransformTreeEmpty,
@@ -1,4 +1,5 @@
 use crate::{
+    frame::FrameId,
     geometry::{Quaternion, Vector3},
     time::Timestamp,
 };
@@ -15,7 +16,9 @@ mod traits;
 ///
 /// The `Transform` struct is used to represent a transformation in 3D space,
 /// including translation, rotation, and associated metadata such as timestamps
-/// and frame identifiers.
+/// and frame identifiers. Frames are interned `FrameId`s, so a `Transform` owns no
+/// heap data and frame names convert into them with `.into()`. It is plain data and
+/// `Copy`, which lets buffers hand out stored transforms without any allocation.
 ///
 /// # Examples
 ///
@@ -44,13 +47,13 @@ mod traits;
 ///     }
 /// );
 /// ```
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy)]
 pub struct Transform {
     pub translation: Vector3,
     pub rotation: Quaternion,
     pub timestamp: Timestamp,
-    pub parent: String,
-    pub child: String,
+    pub parent: FrameId,
+    pub child: FrameId,
 }
 
 impl Transform {
@@ -129,18 +132,18 @@ impl Transform {
     ///     nanoseconds: 1_000_000_000,
     /// };
     ///
-    /// let interpolated = Transform::interpolate(from, to, timestamp).unwrap();
+    /// let interpolated = Transform::interpolate(&from, &to, timestamp).unwrap();
     /// assert_eq!(result, interpolated);
     /// ```
     pub fn interpolate(
-        from: Transform,
-        to: Transform,
+        from: &Transform,
+        to: &Transform,
         timestamp: Timestamp,
     ) -> Result<Transform, TransformError> {
         if from.timestamp > to.timestamp || timestamp < from.timestamp || timestamp > to.timestamp {
             return Err(TransformError::TimestampMismatch(
-                to.timestamp.as_seconds()?,
-                from.timestamp.as_seconds()?,
+                to.timestamp,
+                from.timestamp,
             ));
         }
         if from.child != to.child || from.parent != to.parent {
@@ -149,7 +152,7 @@ impl Transform {
 
         let range = to.timestamp.nanoseconds - from.timestamp.nanoseconds;
         if range == 0 {
-            return Ok(from);
+            return Ok(*from);
         }
 
         let diff = timestamp.nanoseconds - from.timestamp.nanoseconds;
@@ -163,6 +166,113 @@ impl Transform {
         })
     }
 
+    /// Interpolates smoothly between two transforms at a given timestamp.
+    ///
+    /// The translation follows a cubic Hermite spline with Catmull-Rom tangents and the
+    /// rotation follows SQUAD, so that consecutive segments join without a jump in velocity.
+    /// The tangents at `from` and `to` are taken from their neighbours `previous` and `next`;
+    /// at an end without a neighbour the segment leaves or arrives as a linear interpolation
+    /// would, and without either neighbour this is `Transform::interpolate`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `TransformError::TimestampMismatch` if the timestamp is outside the range
+    /// of `from` and `to`, or if the neighbours are not strictly before `from` and after `to`.
+    /// Returns `TransformError::IncompatibleFrames` if the transforms are not all between
+    /// the same frames.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use transforms::{
+    ///     geometry::{Transform, Vector3},
+    ///     time::Timestamp,
+    /// };
+    /// # use approx::assert_relative_eq;
+    ///
+    /// // Samples of x = t², one second apart
+    /// let sample = |seconds: u128| Transform {
+    ///     translation: Vector3::new((seconds * seconds) as f64, 0.0, 0.0),
+    ///     timestamp: Timestamp {
+    ///         nanoseconds: seconds * 1_000_000_000,
+    ///     },
+    ///     ..Transform::identity()
+    /// };
+    /// let timestamp = Timestamp {
+    ///     nanoseconds: 1_500_000_000,
+    /// };
+    ///
+    /// let smooth = Transform::interpolate_cubic(
+    ///     Some(&sample(0)),
+    ///     &sample(1),
+    ///     &sample(2),
+    ///     Some(&sample(3)),
+    ///     timestamp,
+    /// )
+    /// .unwrap();
+    /// assert_relative_eq!(smooth.translation.x, 2.25);
+    ///
+    /// let linear = Transform::interpolate(&sample(1), &sample(2), timestamp).unwrap();
+    /// assert_relative_eq!(linear.translation.x, 2.5);
+    /// ```
+    pub fn interpolate_cubic(
+        previous: Option<&Transform>,
+        from: &Transform,
+        to: &Transform,
+        next: Option<&Transform>,
+        timestamp: Timestamp,
+    ) -> Result<Transform, TransformError> {
+        let linear = Self::interpolate(from, to, timestamp)?;
+        for neighbour in previous.iter().chain(next.iter()) {
+            if neighbour.child != from.child || neighbour.parent != from.parent {
+                return Err(TransformError::IncompatibleFrames);
+            }
+        }
+        if previous.is_some_and(|previous| previous.timestamp >= from.timestamp)
+            || next.is_some_and(|next| next.timestamp <= to.timestamp)
+        {
+            return Err(TransformError::TimestampMismatch(
+                to.timestamp,
+                from.timestamp,
+            ));
+        }
+
+        let range = (to.timestamp.nanoseconds - from.timestamp.nanoseconds) as f64;
+        if range == 0.0 {
+            return Ok(linear);
+        }
+        let s = (timestamp.nanoseconds - from.timestamp.nanoseconds) as f64 / range;
+
+        // Tangents are velocities per nanosecond, scaled to the segment
+        let secant = (to.translation - from.translation) / range;
+        let velocity = |before: &Transform, after: &Transform| {
+            (after.translation - before.translation)
+                / (after.timestamp.nanoseconds - before.timestamp.nanoseconds) as f64
+        };
+        let start = previous.map_or(secant, |previous| velocity(previous, to));
+        let end = next.map_or(secant, |next| velocity(from, next));
+
+        let s2 = s * s;
+        let s3 = s2 * s;
+        let translation = (2.0 * s3 - 3.0 * s2 + 1.0) * from.translation
+            + ((s3 - 2.0 * s2 + s) * range) * start
+            + (-2.0 * s3 + 3.0 * s2) * to.translation
+            + ((s3 - s2) * range) * end;
+
+        let a = previous.map_or(from.rotation, |previous| {
+            from.rotation.squad_control(previous.rotation, to.rotation)
+        });
+        let b = next.map_or(to.rotation, |next| {
+            to.rotation.squad_control(from.rotation, next.rotation)
+        });
+
+        Ok(Transform {
+            translation,
+            rotation: from.rotation.squad(a, b, to.rotation, s),
+            ..linear
+        })
+    }
+
     /// Returns the identity transform.
     ///
     /// The identity transform has no translation or rotation and is often used
@@ -210,8 +320,8 @@ impl Transform {
                 z: 0.0,
             },
             timestamp: Timestamp::zero(),
-            parent: "".into(),
-            child: "".into(),
+            parent: FrameId::default(),
+            child: FrameId::default(),
         }
     }
 
@@ -257,8 +367,8 @@ impl Transform {
             translation: inverse_translation,
             rotation: inverse_rotation,
             timestamp: self.timestamp,
-            parent: self.child.clone(),
-            child: self.parent.clone(),
+            parent: self.child,
+            child: self.parent,
         })
     }
 }
@@ -279,8 +389,8 @@ impl Mul for Transform {
 
         if duration.as_secs_f64() > 2.0 * f64::EPSILON {
             return Err(TransformError::TimestampMismatch(
-                self.timestamp.as_seconds()?,
-                rhs.timestamp.as_seconds()?,
+                self.timestamp,
+                rhs.timestamp,
             ));
         }
 
@@ -1,9 +1,11 @@
 #[cfg(test)]
 mod transform_tests {
     use crate::{
+        errors::TransformError,
         geometry::{Quaternion, Transform, Vector3},
         time::Timestamp,
     };
+    use approx::assert_relative_eq;
 
     #[test]
     fn transform_creation() {
@@ -30,4 +32,129 @@ mod transform_tests {
             child,
         };
     }
+
+    fn sample(
+        seconds: u128,
+        x: f64,
+    ) -> Transform {
+        Transform {
+            translation: Vector3::new(x, 0.0, 0.0),
+            rotation: Quaternion {
+                w: (x / 20.0).cos(),
+                x: 0.0,
+                y: 0.0,
+                z: (x / 20.0).sin(),
+            },
+            timestamp: Timestamp {
+                nanoseconds: seconds * 1_000_000_000,
+            },
+            parent: "map".into(),
+            child: "base".into(),
+        }
+    }
+
+    #[test]
+    fn interpolate_cubic_without_neighbours_is_linear() {
+        let (from, to) = (sample(1, 1.0), sample(2, 4.0));
+        let timestamp = Timestamp {
+            nanoseconds: 1_300_000_000,
+        };
+
+        let cubic = Transform::interpolate_cubic(None, &from, &to, None, timestamp).unwrap();
+        let linear = Transform::interpolate(&from, &to, timestamp).unwrap();
+        assert_relative_eq!(cubic.translation, linear.translation, epsilon = 1e-12);
+        assert_relative_eq!(cubic.rotation.w, linear.rotation.w, epsilon = 1e-12);
+        assert_relative_eq!(cubic.rotation.z, linear.rotation.z, epsilon = 1e-12);
+        assert_eq!(cubic.timestamp, timestamp);
+    }
+
+    #[test]
+    fn interpolate_cubic_rejects_invalid_neighbours() {
+        let (from, to) = (sample(1, 1.0), sample(2, 4.0));
+        let timestamp = Timestamp {
+            nanoseconds: 1_500_000_000,
+        };
+
+        let late = sample(1, 0.0);
+        assert!(matches!(
+            Transform::interpolate_cubic(Some(&late), &from, &to, None, timestamp),
+            Err(TransformError::TimestampMismatch(_, _))
+        ));
+        let early = sample(2, 9.0);
+        assert!(matches!(
+            Transform::interpolate_cubic(None, &from, &to, Some(&early), timestamp),
+            Err(TransformError::TimestampMismatch(_, _))
+        ));
+
+        let mut elsewhere = sample(0, 0.0);
+        elsewhere.parent = "odom".into();
+        assert!(matches!(
+            Transform::interpolate_cubic(Some(&elsewhere), &from, &to, None, timestamp),
+            Err(TransformError::IncompatibleFrames)
+        ));
+    }
+
+    #[test]
+    fn interpolate_across_the_double_cover() {
+        let mut from = sample(1, 0.0);
+        let mut to = sample(2, 0.0);
+        // Yaws of 170° and -170°, both published with a positive w, on opposite hemispheres
+        let yaw = |degrees: f64| {
+            let half = degrees.to_radians() / 2.0;
+            Quaternion {
+                w: half.cos(),
+                x: 0.0,
+                y: 0.0,
+                z: half.sin(),
+            }
+        };
+        from.rotation = yaw(170.0);
+        to.rotation = yaw(-170.0);
+        assert!(to.rotation.w > 0.0 && from.rotation.w > 0.0);
+
+        let midpoint = Transform::interpolate(
+            &from,
+            &to,
+            Timestamp {
+                nanoseconds: 1_500_000_000,
+            },
+        )
+        .unwrap();
+        assert!(
+            midpoint.rotation.rotation_eq(&yaw(180.0), 1e-12),
+            "Interpolated the long way round: {:?}",
+            midpoint.rotation
+        );
+
+        let cubic = Transform::interpolate_cubic(
+            None,
+            &from,
+            &to,
+            None,
+            Timestamp {
+                nanoseconds: 1_250_000_000,
+            },
+        )
+        .unwrap();
+        assert!(cubic.rotation.rotation_eq(&yaw(175.0), 1e-12));
+    }
+
+    #[test]
+    fn timestamp_mismatch_keeps_epoch_timestamps() {
+        let now = Timestamp::now();
+        let later = Timestamp {
+            nanoseconds: now.nanoseconds + 1_000_001,
+        };
+        let mut lhs = sample(0, 1.0);
+        lhs.timestamp = now;
+        let mut rhs = sample(0, 2.0);
+        rhs.parent = "base".into();
+        rhs.child = "camera".into();
+        rhs.timestamp = later;
+
+        match rhs * lhs {
+            Err(TransformError::TimestampMismatch(a, b)) => assert_eq!((a, b), (later, now)),
+            other => panic!("Expected TimestampMismatch, got {other:?}"),
+        }
+    }
 }
@@ -13,7 +13,13 @@
 //!
 //! - **Registry**: The main interface for managing transforms
 //! - **Buffer**: Internal storage for transforms between specific frames
+//! - **RegistryReader**: A wait-free read handle on snapshots published by the registry
+//! - **Snapshot**: An immutable, frozen view of the registry for consistent lookups
+//! - **LookupHandle**: A precompiled lookup between a fixed pair of frames
+//! - **TreePoses**: The pose of every frame below a root, evaluated in one pass by `tree_at`
+//! - **LookupResult**: A transform together with the provenance of every link it combines
 //! - **Transform**: The core data structure representing spatial transformations
+//! - **FrameId**: A cheap, copyable handle to an interned frame name
 //!
 //! # Features
 //!
@@ -21,6 +27,9 @@
 //! - **Transform Chaining**: Automatic computation of transforms between indirectly connected frames
 //! - **Thread-safe Operations**: Safe concurrent access to the transform registry
 //! - **Time-based Buffer Management**: Automatic cleanup of old transforms
+//! - **Input Validation**: Invalid transforms are rejected, normalized or logged on insert (`ValidationPolicy`)
+//! - **Anomaly Detection**: Per-frame velocity and acceleration limits reject or flag implausible jumps on insert (`MotionLimits`)
+//! - **Sample Policies**: Per-frame handling of duplicate timestamps and late transforms, with counters of what was turned away (`DuplicatePolicy`)
 //!
 //! # Non-Goals
 //!
@@ -182,8 +191,33 @@
 //! # Performance Considerations
 //!
 //! - Transform lookups are optimized for O(log n) time complexity
+//! - The lowest common ancestor of two frames comes from a depth index kept up to date on insert,
+//!   so lookups only evaluate the links between the two frames
+//! - Successful lookups on the registry, its readers and snapshots do not allocate once frame
+//!   names are interned and, for the registry, the path between the frames is cached
+//! - Chains of static links are folded into one cached transform, so a lookup crosses them with
+//!   a single multiplication
 //! - Automatic cleanup of old transforms prevents unbounded memory growth
+//! - High-rate input can be decimated per frame (`BufferConfig::decimation`): transforms arriving
+//!   faster than a minimum interval are dropped or merged before they reach the storage
+//! - Long histories can be compressed (`BufferConfig::compression`): transforms that interpolation
+//!   reproduces within a translation and rotation tolerance are dropped as they are superseded
+//! - Buffers can keep a tiered history (`BufferConfig::archive`): a hot window at full rate for
+//!   low-latency lookups, and hours of decimated or compressed history behind it
+//! - Each buffer answers lookups between its transforms in an `InterpolationMode` (linear, cubic,
+//!   nearest, zero-order hold or exact only), which `get_transform_with` overrides per query
+//! - Buffers can refuse to interpolate across sensor dropouts (`BufferConfig::max_interpolation_gap`),
+//!   failing lookups inside a longer gap rather than making up a transform
+//! - `Registry::lookup` returns a `LookupResult` with the frames a transform passed through and,
+//!   per link, whether it was exact, interpolated, carried over or static, and how stale its data is
+//! - `Registry::memory_usage` reports the bytes held per frame, and `Registry::set_limits` caps
+//!   the frames, samples and bytes a registry holds, rejecting or evicting data over a cap;
+//!   `FrameId::set_max_names` bounds the frame names the process interns
+//! - Buffers can use a preallocated ring buffer (`Storage::Ring`) instead of a tree map, which
+//!   makes appending and expiring high-rate, monotonic input constant time
 //! - Lock-free data structures are used where possible in the async implementation
+//! - The async registry locks buffers per frame: writers into different frames do not contend,
+//!   and lookups only lock the buffers on their chain
 //!
 //! # Safety
 //!
@@ -192,6 +226,7 @@
 
 pub mod core;
 pub mod errors;
+pub mod frame;
 pub mod geometry;
 pub mod time;
 
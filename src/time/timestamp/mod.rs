@@ -1,6 +1,7 @@
 use core::ops::{Add, Sub};
 use std::{
     cmp::Ordering,
+    fmt,
     time::{Duration, SystemTime, UNIX_EPOCH},
 };
 
@@ -101,6 +102,34 @@ impl Timestamp {
     }
 }
 
+/// Formats the timestamp as seconds since the UNIX epoch, with every nanosecond digit.
+///
+/// Unlike `as_seconds`, this is exact for any timestamp.
+///
+/// # Examples
+///
+/// ```
+/// use transforms::time::Timestamp;
+///
+/// let timestamp = Timestamp {
+///     nanoseconds: 1_700_000_000_000_000_001,
+/// };
+/// assert_eq!(timestamp.to_string(), "1700000000.000000001s");
+/// ```
+impl fmt::Display for Timestamp {
+    fn fmt(
+        &self,
+        f: &mut fmt::Formatter<'_>,
+    ) -> fmt::Result {
+        write!(
+            f,
+            "{}.{:09}s",
+            self.nanoseconds / 1_000_000_000,
+            self.nanoseconds % 1_000_000_000
+        )
+    }
+}
+
 impl Sub<Timestamp> for Timestamp {
     type Output = Result<Duration, TimestampError>;
 
@@ -51,4 +51,23 @@ mod timestamp_tests {
             Err(TimestampError::AccuracyLoss)
         ));
     }
+
+    #[test]
+    fn display() {
+        assert_eq!(Timestamp::zero().to_string(), "0.000000000s");
+        assert_eq!(
+            Timestamp {
+                nanoseconds: 1_500_000_000,
+            }
+            .to_string(),
+            "1.500000000s"
+        );
+
+        // Epoch timestamps keep every digit, where as_seconds loses accuracy
+        let timestamp = Timestamp {
+            nanoseconds: 1_760_000_000_123_456_789,
+        };
+        assert!(timestamp.as_seconds().is_err());
+        assert_eq!(timestamp.to_string(), "1760000000.123456789s");
+    }
 }
@@ -0,0 +1,158 @@
+#[cfg(not(feature = "async"))]
+use {
+    std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+        time::Duration,
+    },
+    transforms::geometry::{Quaternion, Transform, Vector3},
+    transforms::time::Timestamp,
+    transforms::Registry,
+};
+
+/// Counts the allocations made by the current thread, so tests running in parallel do not
+/// disturb each other.
+#[cfg(not(feature = "async"))]
+struct CountingAllocator;
+
+#[cfg(not(feature = "async"))]
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+#[cfg(not(feature = "async"))]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(
+        &self,
+        layout: Layout,
+    ) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+    ) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(
+        &self,
+        ptr: *mut u8,
+        layout: Layout,
+        new_size: usize,
+    ) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[cfg(not(feature = "async"))]
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[cfg(not(feature = "async"))]
+fn allocations_during<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+    (result, after - before)
+}
+
+/// Builds A -> B -> C and A -> D, with two samples per link so lookups interpolate.
+#[cfg(not(feature = "async"))]
+fn populate(
+    registry: &mut Registry,
+    t: Timestamp,
+) {
+    for (parent, child) in [("a", "b"), ("b", "c"), ("a", "d")] {
+        for (offset, x) in [(0, 1.), (2, 3.)] {
+            let transform = Transform {
+                translation: Vector3 { x, y: 0., z: 0. },
+                rotation: Quaternion {
+                    w: 1.,
+                    x: 0.,
+                    y: 0.,
+                    z: 0.,
+                },
+                timestamp: (t + Duration::from_secs(offset)).unwrap(),
+                parent: parent.into(),
+                child: child.into(),
+            };
+            registry.add_transform(transform).unwrap();
+        }
+    }
+}
+
+#[cfg(not(feature = "async"))]
+#[test]
+fn test_registry_lookup_does_not_allocate() {
+    let mut registry = Registry::new(Duration::from_secs(60));
+    let t = Timestamp::now();
+    populate(&mut registry, t);
+    let t_lookup = (t + Duration::from_secs(1)).unwrap();
+
+    // Warm up the path cache
+    registry.get_transform("c", "d", t_lookup).unwrap();
+
+    let (result, allocations) = allocations_during(|| registry.get_transform("c", "d", t_lookup));
+    assert!(result.is_ok());
+    assert_eq!(
+        allocations, 0,
+        "get_transform allocated {allocations} times"
+    );
+}
+
+#[cfg(not(feature = "async"))]
+#[test]
+fn test_reader_lookup_does_not_allocate() {
+    let mut registry = Registry::new(Duration::from_secs(60));
+    let reader = registry.reader();
+    let t = Timestamp::now();
+    populate(&mut registry, t);
+    let t_lookup = (t + Duration::from_secs(1)).unwrap();
+
+    reader.get_transform("c", "d", t_lookup).unwrap();
+
+    let (result, allocations) = allocations_during(|| reader.get_transform("c", "d", t_lookup));
+    assert!(result.is_ok());
+    assert_eq!(allocations, 0, "reader allocated {allocations} times");
+}
+
+#[cfg(not(feature = "async"))]
+#[test]
+fn test_snapshot_lookup_does_not_allocate() {
+    let mut registry = Registry::new(Duration::from_secs(60));
+    let t = Timestamp::now();
+    populate(&mut registry, t);
+    let snapshot = registry.snapshot();
+    let t_lookup = (t + Duration::from_secs(1)).unwrap();
+
+    snapshot.get_transform("c", "d", t_lookup).unwrap();
+
+    let (result, allocations) = allocations_during(|| snapshot.get_transform("c", "d", t_lookup));
+    assert!(result.is_ok());
+    assert_eq!(allocations, 0, "snapshot allocated {allocations} times");
+}
+
+#[cfg(not(feature = "async"))]
+#[test]
+fn test_lookup_handle_does_not_allocate() {
+    let mut registry = Registry::new(Duration::from_secs(60));
+    let mut handle = registry.lookup_handle("c", "d");
+    let t = Timestamp::now();
+    populate(&mut registry, t);
+    let t_lookup = (t + Duration::from_secs(1)).unwrap();
+
+    // Resolves the path
+    handle.at(t_lookup).unwrap();
+
+    let (result, allocations) = allocations_during(|| handle.at(t_lookup));
+    assert!(result.is_ok());
+    assert_eq!(
+        allocations, 0,
+        "lookup handle allocated {allocations} times"
+    );
+}
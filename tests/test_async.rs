@@ -85,10 +85,10 @@ async fn test_async_matching_tree() {
         child: "c".into(),
     };
 
-    registry.add_transform(t_a_b_0.clone()).await.unwrap();
-    registry.add_transform(t_a_b_1.clone()).await.unwrap();
-    registry.add_transform(t_b_c_0.clone()).await.unwrap();
-    registry.add_transform(t_b_c_1.clone()).await.unwrap();
+    registry.add_transform(t_a_b_0).await.unwrap();
+    registry.add_transform(t_a_b_1).await.unwrap();
+    registry.add_transform(t_b_c_0).await.unwrap();
+    registry.add_transform(t_b_c_1).await.unwrap();
 
     let middle_timestamp = (t + Duration::from_millis(750)).unwrap();
     let t_a_c = Transform {
@@ -200,10 +200,10 @@ async fn test_async_non_matching_tree() {
         child: "c".into(),
     };
 
-    registry.add_transform(t_a_b_0.clone()).await.unwrap();
-    registry.add_transform(t_a_b_1.clone()).await.unwrap();
-    registry.add_transform(t_b_c_0.clone()).await.unwrap();
-    registry.add_transform(t_b_c_1.clone()).await.unwrap();
+    registry.add_transform(t_a_b_0).await.unwrap();
+    registry.add_transform(t_a_b_1).await.unwrap();
+    registry.add_transform(t_b_c_0).await.unwrap();
+    registry.add_transform(t_b_c_1).await.unwrap();
 
     let r = registry.get_transform("a", "c", t).await;
 
@@ -211,3 +211,332 @@ async fn test_async_non_matching_tree() {
 
     assert!(r.is_err(), "Registry returned Ok, expected Err");
 }
+
+#[cfg(all(test, feature = "async"))]
+fn translation_x(
+    parent: &str,
+    child: &str,
+    x: f64,
+    timestamp: Timestamp,
+) -> Transform {
+    Transform {
+        translation: Vector3 { x, y: 0., z: 0. },
+        rotation: Quaternion::identity(),
+        timestamp,
+        parent: parent.into(),
+        child: child.into(),
+    }
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_async_concurrent_writers_into_different_frames() {
+    let _ = env_logger::try_init();
+    let registry = std::sync::Arc::new(Registry::new(Duration::from_secs(60)));
+    let reader = registry.reader().await;
+    let t = Timestamp::now();
+
+    for arm in ["left", "right"] {
+        registry
+            .add_transform(translation_x("base", arm, 0., t))
+            .await
+            .unwrap();
+    }
+
+    let writers: Vec<_> = ["left", "right"]
+        .into_iter()
+        .map(|arm| {
+            let registry = std::sync::Arc::clone(&registry);
+            tokio::spawn(async move {
+                for i in 1..=100 {
+                    let timestamp = (t + Duration::from_millis(i)).unwrap();
+                    registry
+                        .add_transform(translation_x("base", arm, i as f64, timestamp))
+                        .await
+                        .unwrap();
+                }
+            })
+        })
+        .collect();
+    for writer in writers {
+        writer.await.unwrap();
+    }
+
+    // Every write is published, whichever frame it went to
+    let last = (t + Duration::from_millis(100)).unwrap();
+    for arm in ["left", "right"] {
+        let r = registry.get_transform("base", arm, last).await.unwrap();
+        assert_eq!(r.translation.x, 100.);
+        let r = reader.get_transform("base", arm, last).unwrap();
+        assert_eq!(r.translation.x, 100.);
+    }
+    let r = reader.get_transform("left", "right", last).unwrap();
+    assert_eq!(r.translation.x, 0.);
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test]
+async fn test_async_reparent_after_appends() {
+    let _ = env_logger::try_init();
+    let registry = Registry::new(Duration::from_secs(60));
+    let reader = registry.reader().await;
+    let t1 = Timestamp::now();
+    let t2 = (t1 + Duration::from_secs(1)).unwrap();
+    let t3 = (t1 + Duration::from_secs(2)).unwrap();
+
+    registry
+        .add_transform(translation_x("a", "b", 1., t1))
+        .await
+        .unwrap();
+    registry
+        .add_transform(translation_x("b", "c", 1., t1))
+        .await
+        .unwrap();
+    registry
+        .add_transform(translation_x("b", "c", 2., t2))
+        .await
+        .unwrap();
+    assert_eq!(registry.parent("c").await, Some("b".into()));
+
+    // Moving C onto A changes the topology and still takes effect
+    registry
+        .add_transform(translation_x("a", "c", 5., t3))
+        .await
+        .unwrap();
+    assert_eq!(registry.parent("c").await, Some("a".into()));
+    let r = reader.get_transform("a", "c", t3).unwrap();
+    assert_eq!(r.translation.x, 5.);
+    let r = registry
+        .snapshot()
+        .await
+        .get_transform("c", "a", t3)
+        .unwrap();
+    assert_eq!(r.translation.x, -5.);
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test]
+async fn test_async_lookup_across_reparent() {
+    let registry = Registry::new(Duration::from_secs(60));
+    let t1 = Timestamp::now();
+    let t2 = (t1 + Duration::from_secs(1)).unwrap();
+
+    for (parent, child) in [("a", "b"), ("b", "c")] {
+        registry
+            .add_transform(translation_x(parent, child, 1., t1))
+            .await
+            .unwrap();
+    }
+    registry
+        .add_transform(translation_x("a", "c", 5., t2))
+        .await
+        .unwrap();
+
+    // Before the re-parent, C was reached through B
+    let result = registry.lookup("a", "c", t1).await.unwrap();
+    assert_eq!(result.frames, vec!["a", "b", "c"]);
+    assert_eq!(result.transform.translation.x, 2.);
+
+    let result = registry.lookup("a", "c", t2).await.unwrap();
+    assert_eq!(result.frames, vec!["a", "c"]);
+    assert_eq!(result.transform.translation.x, 5.);
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn test_async_byte_cap_under_concurrent_writers() {
+    use transforms::core::Limits;
+
+    let _ = env_logger::try_init();
+    let registry = std::sync::Arc::new(Registry::new(Duration::from_secs(60)));
+    let t = Timestamp::now();
+
+    for arm in ["left", "right"] {
+        registry
+            .add_transform(translation_x("base", arm, 0., t))
+            .await
+            .unwrap();
+    }
+    let max = 2 * registry.memory_usage().await.total_bytes;
+    registry
+        .set_limits(Limits {
+            max_bytes: Some(max),
+            ..Limits::default()
+        })
+        .await;
+
+    let writers: Vec<_> = ["left", "right"]
+        .into_iter()
+        .map(|arm| {
+            let registry = std::sync::Arc::clone(&registry);
+            tokio::spawn(async move {
+                let mut accepted = 0;
+                for i in 1..=100 {
+                    let timestamp = (t + Duration::from_millis(i)).unwrap();
+                    let transform = translation_x("base", arm, i as f64, timestamp);
+                    if registry.add_transform(transform).await.is_ok() {
+                        accepted += 1;
+                    }
+                }
+                accepted
+            })
+        })
+        .collect();
+    let mut accepted = 0;
+    for writer in writers {
+        accepted += writer.await.unwrap();
+    }
+
+    // Writers racing on the fast path never overshoot the cap together
+    let usage = registry.memory_usage().await;
+    assert!(usage.total_bytes <= max);
+    assert!(accepted > 0);
+    let samples: usize = usage.frames.values().map(|frame| frame.samples).sum();
+    assert_eq!(samples, accepted + 2);
+    let bytes: usize = usage.frames.values().map(|frame| frame.bytes).sum();
+    assert_eq!(usage.total_bytes, bytes);
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test]
+async fn test_async_validation_on_existing_buffer() {
+    use transforms::{core::ValidationPolicy, errors::BufferError};
+
+    let registry = Registry::new(Duration::from_secs(60));
+    let t1 = Timestamp::now();
+    let t2 = (t1 + Duration::from_secs(1)).unwrap();
+    registry
+        .add_transform(translation_x("a", "b", 1., t1))
+        .await
+        .unwrap();
+
+    // Appending to an existing buffer is validated as well
+    let mut invalid = translation_x("a", "b", f64::NAN, t2);
+    let result = registry.add_transform(invalid).await;
+    assert!(matches!(result, Err(BufferError::NonFiniteTranslation(..))));
+
+    registry.set_validation(ValidationPolicy::Normalize).await;
+    invalid.translation.x = 2.;
+    invalid.rotation.w = 4.;
+    registry.add_transform(invalid).await.unwrap();
+    let stored = registry.get_transform("a", "b", t2).await.unwrap();
+    assert_eq!(stored.rotation, Quaternion::identity());
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test]
+async fn test_async_motion_anomalies_on_existing_buffer() {
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+    use transforms::{
+        core::{AnomalyAction, BufferConfig, MotionLimits},
+        errors::BufferError,
+    };
+
+    let registry = Registry::new(Duration::from_secs(60));
+    let t1 = Timestamp::now();
+    let t2 = (t1 + Duration::from_secs(1)).unwrap();
+    let limited = |action| BufferConfig {
+        motion_limits: Some(MotionLimits {
+            max_linear_velocity: Some(5.),
+            action,
+            ..MotionLimits::default()
+        }),
+        ..BufferConfig::new(Duration::from_secs(60))
+    };
+    registry
+        .set_frame_config("odom", limited(AnomalyAction::Reject))
+        .await;
+    registry
+        .set_frame_config("base", limited(AnomalyAction::Flag))
+        .await;
+    let reported = Arc::new(AtomicUsize::new(0));
+    let count = Arc::clone(&reported);
+    registry
+        .set_anomaly_hook(move |_| {
+            count.fetch_add(1, Ordering::Relaxed);
+        })
+        .await;
+
+    for (parent, child) in [("map", "odom"), ("odom", "base")] {
+        registry
+            .add_transform(translation_x(parent, child, 0., t1))
+            .await
+            .unwrap();
+    }
+
+    // Appending to the existing buffers is checked as well
+    let result = registry
+        .add_transform(translation_x("map", "odom", 20., t2))
+        .await;
+    assert!(matches!(result, Err(BufferError::MotionAnomaly(..))));
+    registry
+        .add_transform(translation_x("odom", "base", 20., t2))
+        .await
+        .unwrap();
+    let stored = registry.get_transform("odom", "base", t2).await.unwrap();
+    assert_eq!(stored.translation.x, 20.);
+    assert_eq!(reported.load(Ordering::Relaxed), 2);
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test]
+async fn test_async_rejection_stats() {
+    use transforms::{
+        core::{BufferConfig, DuplicatePolicy},
+        errors::BufferError,
+    };
+
+    let registry = Registry::new(Duration::from_secs(60));
+    let t1 = Timestamp::now();
+    let t2 = (t1 + Duration::from_secs(1)).unwrap();
+    registry
+        .set_frame_config(
+            "b",
+            BufferConfig {
+                duplicates: DuplicatePolicy::KeepFirst,
+                max_lateness: Some(Duration::from_millis(100)),
+                ..BufferConfig::new(Duration::from_secs(60))
+            },
+        )
+        .await;
+
+    registry
+        .add_transform(translation_x("a", "b", 1., t2))
+        .await
+        .unwrap();
+    registry
+        .add_transform(translation_x("a", "b", 2., t2))
+        .await
+        .unwrap();
+    let result = registry
+        .add_transform(translation_x("a", "b", 3., t1))
+        .await;
+    assert!(matches!(result, Err(BufferError::LateTransform(..))));
+
+    let stored = registry.get_transform("a", "b", t2).await.unwrap();
+    assert_eq!(stored.translation.x, 1.);
+    let stats = registry.rejection_stats("b").await.unwrap();
+    assert_eq!((stats.late, stats.duplicates), (1, 1));
+}
+
+#[cfg(all(test, feature = "async"))]
+#[tokio::test]
+async fn test_async_add_transforms_publishes_batch() {
+    let registry = Registry::new(Duration::from_secs(60));
+    let reader = registry.reader().await;
+    let t = Timestamp::now();
+
+    registry
+        .add_transforms([
+            translation_x("a", "b", 1., t),
+            translation_x("b", "c", 2., t),
+        ])
+        .await
+        .unwrap();
+
+    let result = reader.get_transform("a", "c", t).unwrap();
+    assert_eq!(result.translation.x, 3.);
+}
@@ -0,0 +1,53 @@
+//! The cap on interned frame names is process-wide, so it is tested in a process of its own.
+
+#[cfg(not(feature = "async"))]
+use {
+    std::time::Duration,
+    transforms::{
+        core::Limit, errors::BufferError, frame::FrameId, geometry::Transform, time::Timestamp,
+        Registry,
+    },
+};
+
+#[cfg(not(feature = "async"))]
+#[test]
+fn frame_names_are_capped() {
+    let mut registry = Registry::new(Duration::from_secs(60));
+    let link = |child: &str| {
+        let mut transform = Transform::identity();
+        transform.parent = "map".into();
+        transform.child = child.into();
+        transform.timestamp = Timestamp::now();
+        transform
+    };
+    registry.add_transform(link("robot_0")).unwrap();
+
+    let names = FrameId::interned_names();
+    let bytes = FrameId::interned_bytes();
+    FrameId::set_max_names(Some(names + 2));
+    for i in 1..10 {
+        let result = registry.add_transform(link(&format!("robot_{i}")));
+        if i < 3 {
+            result.unwrap();
+        } else {
+            assert!(matches!(
+                result,
+                Err(BufferError::LimitExceeded(Limit::Names, max)) if max == names + 2
+            ));
+        }
+    }
+
+    // Names past the cap are not kept, and the ones before it are accounted for
+    assert_eq!(FrameId::get("robot_3"), None);
+    let usage = registry.memory_usage();
+    assert_eq!(usage.frames.len(), 3);
+    assert_eq!(usage.interned_names, names + 2);
+    assert!(usage.interned_bytes > bytes);
+    assert_eq!(FrameId::OVERFLOW.to_string(), "<overflow>");
+
+    // Names interned before the cap still resolve
+    assert_ne!(FrameId::new("robot_1"), FrameId::OVERFLOW);
+
+    FrameId::set_max_names(None);
+    registry.add_transform(link("robot_3")).unwrap();
+}
@@ -85,10 +85,10 @@ fn test_sync_matching_tree() {
         child: "c".into(),
     };
 
-    registry.add_transform(t_a_b_0.clone()).unwrap();
-    registry.add_transform(t_a_b_1.clone()).unwrap();
-    registry.add_transform(t_b_c_0.clone()).unwrap();
-    registry.add_transform(t_b_c_1.clone()).unwrap();
+    registry.add_transform(t_a_b_0).unwrap();
+    registry.add_transform(t_a_b_1).unwrap();
+    registry.add_transform(t_b_c_0).unwrap();
+    registry.add_transform(t_b_c_1).unwrap();
 
     let middle_timestamp = (t + Duration::from_millis(750)).unwrap();
     let t_a_c = Transform {
@@ -200,10 +200,10 @@ fn test_sync_non_matching_tree() {
         child: "c".into(),
     };
 
-    registry.add_transform(t_a_b_0.clone()).unwrap();
-    registry.add_transform(t_a_b_1.clone()).unwrap();
-    registry.add_transform(t_b_c_0.clone()).unwrap();
-    registry.add_transform(t_b_c_1.clone()).unwrap();
+    registry.add_transform(t_a_b_0).unwrap();
+    registry.add_transform(t_a_b_1).unwrap();
+    registry.add_transform(t_b_c_0).unwrap();
+    registry.add_transform(t_b_c_1).unwrap();
 
     let r = registry.get_transform("a", "c", t);
 